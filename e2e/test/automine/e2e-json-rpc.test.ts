@@ -1,8 +1,8 @@
 import { expect } from "chai";
-import { keccak256 } from "ethers";
+import { encodeRlp, keccak256, toBeHex } from "ethers";
 import { Block, Bytes, TransactionReceipt } from "web3-types";
 
-import { ALICE, BOB } from "../helpers/account";
+import { ALICE, BOB, CHARLIE } from "../helpers/account";
 import { isStratus } from "../helpers/network";
 import {
     CHAIN_ID,
@@ -19,6 +19,7 @@ import {
     sendAndGetError,
     sendEvmMine,
     sendExpect,
+    sendGetNonce,
     sendRawTransaction,
     sendReset,
     subscribeAndGetEvent,
@@ -68,7 +69,7 @@ describe("JSON-RPC", () => {
             expect(gas).match(HEX_PATTERN, "format");
 
             const gasDec = parseInt(gas, 16);
-            expect(gasDec).to.be.greaterThan(0).and.lessThan(1_000_000);
+            expect(gasDec).eq(21_000);
         });
     });
 
@@ -81,6 +82,15 @@ describe("JSON-RPC", () => {
         it("eth_getBalance", async () => {
             (await sendExpect("eth_getBalance", [ALICE])).eq(TEST_BALANCE);
             (await sendExpect("eth_getBalance", [ALICE, "latest"])).eq(TEST_BALANCE);
+            (await sendExpect("eth_getBalance", [ALICE, "safe"])).eq(TEST_BALANCE);
+            (await sendExpect("eth_getBalance", [ALICE, "finalized"])).eq(TEST_BALANCE);
+        });
+        it("accepts EIP-1898 block parameters", async () => {
+            const genesis = await send("eth_getBlockByNumber", [ZERO, false]);
+            (await sendExpect("eth_getBalance", [ALICE, { blockNumber: ZERO }])).eq(TEST_BALANCE);
+            (await sendExpect("eth_getBalance", [ALICE, { blockHash: genesis.hash, requireCanonical: true }])).eq(TEST_BALANCE);
+            (await sendExpect("eth_getStorageAt", [ALICE, ZERO, { blockNumber: ZERO }])).eq(toPaddedHex(0, 32));
+            (await sendExpect("eth_getCode", [ALICE, { blockHash: genesis.hash }])).eq("0x");
         });
         describe("eth_getCode", () => {
             it("contract code is available in the block it was deployed", async () => {
@@ -128,6 +138,74 @@ describe("JSON-RPC", () => {
                 expect(block).to.be.null;
             });
         });
+        it("links blocks by parent hash and hashes the RLP-encoded header", async function () {
+            await sendReset();
+            const contract = await deployTestContractBalances();
+            const txResponse = await contract.connect(ALICE.signer()).add(ALICE.address, 10);
+            const txReceipt = await ETHERJS.getTransactionReceipt(txResponse.hash);
+            const blockNumber = txReceipt?.blockNumber ?? 0;
+
+            const block = await send("eth_getBlockByNumber", [toHex(blockNumber), false]);
+            const parent = await send("eth_getBlockByNumber", [toHex(blockNumber - 1), false]);
+            expect(block.parentHash).eq(parent.hash);
+
+            if (!isStratus) this.skip();
+            const quantity = (value: string) => (BigInt(value) === 0n ? "0x" : toBeHex(BigInt(value)));
+            const header = [
+                block.parentHash,
+                block.sha3Uncles,
+                block.miner,
+                block.stateRoot,
+                block.transactionsRoot,
+                block.receiptsRoot,
+                block.logsBloom,
+                quantity(block.difficulty),
+                quantity(block.number),
+                quantity(block.gasLimit),
+                quantity(block.gasUsed),
+                quantity(block.timestamp),
+                block.extraData,
+                block.mixHash,
+                block.nonce,
+                quantity(block.baseFeePerGas),
+            ];
+            expect(keccak256(encodeRlp(header))).eq(block.hash);
+        });
+        describe("lookups by block", () => {
+            let block: any;
+            before(async () => {
+                await sendReset();
+                const contract = await deployTestContractBalances();
+                const txResponse = await contract.connect(ALICE.signer()).add(ALICE.address, 10);
+                const txReceipt = await ETHERJS.getTransactionReceipt(txResponse.hash);
+                block = await send("eth_getBlockByNumber", [toHex(txReceipt?.blockNumber ?? 0), false]);
+            });
+            it("eth_getBlockTransactionCountByHash / ByNumber", async () => {
+                (await sendExpect("eth_getBlockTransactionCountByHash", [block.hash])).eq(ONE);
+                (await sendExpect("eth_getBlockTransactionCountByNumber", [block.number])).eq(ONE);
+                (await sendExpect("eth_getBlockTransactionCountByHash", [HASH_ZERO])).eq(null);
+            });
+            it("eth_getTransactionByBlockHashAndIndex / ByBlockNumberAndIndex", async () => {
+                const byHash = await send("eth_getTransactionByBlockHashAndIndex", [block.hash, ZERO]);
+                const byNumber = await send("eth_getTransactionByBlockNumberAndIndex", [block.number, ZERO]);
+                expect(byHash.hash).eq(block.transactions[0]);
+                expect(byNumber.hash).eq(block.transactions[0]);
+                (await sendExpect("eth_getTransactionByBlockNumberAndIndex", [block.number, ONE])).eq(null);
+            });
+            it("eth_getBlockReceipts", async () => {
+                const receipts = await send("eth_getBlockReceipts", [block.number]);
+                expect(receipts).length(1);
+                expect(receipts[0].transactionHash).eq(block.transactions[0]);
+                expect(receipts[0].blockHash).eq(block.hash);
+            });
+            it("eth_getUncleCountByBlockHash / ByBlockNumber", async () => {
+                (await sendExpect("eth_getUncleCountByBlockHash", [block.hash])).eq(ZERO);
+                (await sendExpect("eth_getUncleCountByBlockNumber", [block.number])).eq(ZERO);
+            });
+            it("eth_syncing", async () => {
+                (await sendExpect("eth_syncing")).eq(false);
+            });
+        });
         it("eth_getUncleByBlockHashAndIndex", async function () {
             if (isStratus) {
                 (await sendExpect("eth_getUncleByBlockHashAndIndex", [ZERO, ZERO])).eq(null);
@@ -213,6 +291,217 @@ describe("JSON-RPC", () => {
                 const expectedAliceBalance = toPaddedHex(0, 32);
                 expect(currentAliceBalance).eq(expectedAliceBalance);
             });
+
+            it("Returns the decoded revert reason", async function () {
+                if (!isStratus) this.skip();
+                const contract = await deployTestContractBalances();
+
+                const data = contract.interface.encodeFunctionData("sub", [ALICE.address, 1]);
+                const error = await sendAndGetError("eth_call", [{ to: contract.target, data }, "latest"]);
+                expect(error.message).match(/^execution reverted: .*Insufficient balance/);
+                expect(error.data).match(/^0x08c379a0/);
+            });
+
+            it("Applies state overrides", async function () {
+                if (!isStratus) this.skip();
+
+                // PUSH1 1, PUSH1 0, MSTORE, PUSH1 32, PUSH1 0, RETURN
+                const code = "0x600160005260206000f3";
+                const transaction = { to: BOB.address, data: "0x01" };
+                const result = await send("eth_call", [transaction, "latest", { [BOB.address]: { code } }]);
+                expect(result).eq(toPaddedHex(1, 32));
+
+                // overrides are not persisted
+                (await sendExpect("eth_getCode", [BOB.address, "latest"])).eq("0x");
+            });
+
+            it("Applies block overrides", async function () {
+                if (!isStratus) this.skip();
+
+                // TIMESTAMP, PUSH1 0, MSTORE, PUSH1 32, PUSH1 0, RETURN
+                const code = "0x4260005260206000f3";
+                const transaction = { to: BOB.address, data: "0x01" };
+                const result = await send("eth_call", [transaction, "latest", { [BOB.address]: { code } }, { time: "0x1234" }]);
+                expect(result).eq(toPaddedHex(0x1234, 32));
+            });
+
+            it("Rejects state overrides with both state and stateDiff", async function () {
+                if (!isStratus) this.skip();
+
+                const transaction = { to: BOB.address, data: "0x01" };
+                const error = await sendAndGetError("eth_call", [transaction, "latest", { [BOB.address]: { state: {}, stateDiff: {} } }]);
+                expect(error.code).eq(-32602);
+            });
+        });
+
+        describe("eth_createAccessList", () => {
+            it("Returns accessed slots with the gas used", async function () {
+                if (!isStratus) this.skip();
+                const contract = await deployTestContractBalances();
+
+                const data = contract.interface.encodeFunctionData("add", [ALICE.address, 5]);
+                const result = await send("eth_createAccessList", [{ from: ALICE.address, to: contract.target, data }, "latest"]);
+
+                expect(result.accessList).length(1);
+                expect(result.accessList[0].address).eq((contract.target as string).toLowerCase());
+                expect(result.accessList[0].storageKeys).length(1);
+                expect(parseInt(result.gasUsed, 16)).gt(21000);
+                expect(result.error).to.be.undefined;
+            });
+
+            it("Does not include precompiles", async function () {
+                if (!isStratus) this.skip();
+
+                // sha256 precompile
+                const precompile = "0x0000000000000000000000000000000000000002";
+                const result = await send("eth_createAccessList", [{ from: ALICE.address, to: precompile, data: "0x01" }, "latest"]);
+
+                expect(result.accessList).length(0);
+                expect(result.error).to.be.undefined;
+            });
+        });
+
+        describe("stratus_simulateBundle", () => {
+            it("Chains state changes between calls", async function () {
+                if (!isStratus) this.skip();
+                const contract = await deployTestContractBalances();
+
+                const add = { to: contract.target, data: contract.interface.encodeFunctionData("add", [ALICE.address, 5]) };
+                const get = { to: contract.target, data: contract.interface.encodeFunctionData("get", [ALICE.address]) };
+                const results = await send("stratus_simulateBundle", [[add, get], "latest", { stateDiff: true }]);
+
+                expect(results).length(2);
+                expect(results[0].status).eq(ONE);
+                expect(results[0].stateDiff).to.not.be.undefined;
+                expect(results[1].returnData).eq(toPaddedHex(5, 32));
+
+                // simulated changes are not persisted
+                expect(await send("eth_call", [get, "latest"])).eq(toPaddedHex(0, 32));
+            });
+        });
+    });
+
+    describe("Otterscan", () => {
+        let contractAddress: string;
+        let deployHash: string;
+        let addHash: string;
+        before(async function () {
+            if (!isStratus) this.skip();
+            await sendReset();
+            const contract = await deployTestContractBalances();
+            contractAddress = (contract.target as string).toLowerCase();
+            deployHash = contract.deploymentTransaction()?.hash ?? "";
+            const txResponse = await contract.connect(ALICE.signer()).add(ALICE.address, 10);
+            await txResponse.wait();
+            addHash = txResponse.hash;
+        });
+        it("ots_getApiLevel", async () => {
+            (await sendExpect("ots_getApiLevel")).eq(8);
+        });
+        it("ots_hasCode", async () => {
+            (await sendExpect("ots_hasCode", [contractAddress, "latest"])).eq(true);
+            (await sendExpect("ots_hasCode", [ALICE.address, "latest"])).eq(false);
+        });
+        it("ots_getTransactionBySenderAndNonce", async () => {
+            (await sendExpect("ots_getTransactionBySenderAndNonce", [ALICE.address, 0])).eq(addHash);
+            (await sendExpect("ots_getTransactionBySenderAndNonce", [ALICE.address, 1])).eq(null);
+        });
+        it("ots_getContractCreator", async () => {
+            const creator = await send("ots_getContractCreator", [contractAddress]);
+            expect(creator.hash).eq(deployHash);
+            expect(creator.creator).eq(CHARLIE.address.toLowerCase());
+            (await sendExpect("ots_getContractCreator", [ALICE.address])).eq(null);
+        });
+        it("ots_searchTransactionsBefore / After", async () => {
+            const before = await send("ots_searchTransactionsBefore", [contractAddress, 0, 1]);
+            expect(before.txs.map((tx: any) => tx.hash)).deep.eq([addHash]);
+            expect(before.receipts[0].timestamp).to.be.a("number");
+            expect(before.firstPage).eq(true);
+            expect(before.lastPage).eq(false);
+
+            const after = await send("ots_searchTransactionsAfter", [contractAddress, 0, 10]);
+            expect(after.txs.map((tx: any) => tx.hash)).deep.eq([addHash, deployHash]);
+            expect(after.firstPage).eq(true);
+            expect(after.lastPage).eq(true);
+        });
+    });
+
+    describe("State diff", () => {
+        let contractAddress: string;
+        let addHash: string;
+        let addBlockHash: string;
+        before(async function () {
+            if (!isStratus) this.skip();
+            await sendReset();
+            const contract = await deployTestContractBalances();
+            contractAddress = (contract.target as string).toLowerCase();
+            const txResponse = await contract.connect(ALICE.signer()).add(ALICE.address, 10);
+            const receipt = await txResponse.wait();
+            addHash = txResponse.hash;
+            addBlockHash = receipt?.blockHash ?? "";
+        });
+        it("stratus_getStateDiff by transaction hash", async () => {
+            const diff = await send("stratus_getStateDiff", [addHash]);
+            expect(diff.transactionHash).eq(addHash);
+            expect(diff.stateDiff[ALICE.address.toLowerCase()].nonce["*"]).deep.eq({ from: "0x0", to: "0x1" });
+            expect(Object.keys(diff.stateDiff[contractAddress].storage)).length(1);
+        });
+        it("stratus_getStateDiff by block hash", async () => {
+            const diffs = await send("stratus_getStateDiff", [addBlockHash]);
+            expect(diffs.map((diff: any) => diff.transactionHash)).deep.eq([addHash]);
+        });
+        it("trace_replayBlockTransactions with stateDiff", async () => {
+            const traces = await send("trace_replayBlockTransactions", [addBlockHash, ["stateDiff"]]);
+            expect(traces[0].transactionHash).eq(addHash);
+            expect(traces[0].stateDiff[contractAddress]).to.not.be.undefined;
+
+            const error = await sendAndGetError("trace_replayBlockTransactions", [addBlockHash, ["vmTrace"]]);
+            expect(error.code).eq(-32602);
+        });
+    });
+
+    describe("TxPool", () => {
+        it("queues transactions with nonce ahead of the sender nonce until the gap is filled", async () => {
+            if (!isStratus) return;
+            await sendReset();
+            const contract = await deployTestContractBalances();
+            const nonce = await sendGetNonce(ALICE);
+            const sender = ALICE.address.toLowerCase();
+
+            // send transaction with the next nonce before the current one
+            const queuedTx = await prepareSignedTx({
+                contract,
+                account: ALICE,
+                methodName: "add",
+                methodParameters: [ALICE.address, 1],
+                custom_nonce: nonce + 1,
+            });
+            expect(await sendRawTransaction(queuedTx)).eq(keccak256(queuedTx));
+            expect((await send("txpool_status")).queued).eq(ONE);
+            expect((await send("txpool_content")).queued[sender][String(nonce + 1)].hash).eq(keccak256(queuedTx));
+            expect((await send("txpool_inspect")).queued[sender][String(nonce + 1)]).match(/wei \+ \d+ gas × \d+ wei$/);
+
+            // replace transaction with the same nonce, which is not allowed without fee market by default
+            const replacementTx = await prepareSignedTx({
+                contract,
+                account: ALICE,
+                methodName: "add",
+                methodParameters: [ALICE.address, 2],
+                custom_nonce: nonce + 1,
+            });
+            const error = await sendAndGetError("eth_sendRawTransaction", [replacementTx]);
+            expect(error.message).contain("cannot be replaced");
+
+            // send transaction with the current nonce
+            const currentTx = await prepareSignedTx({
+                contract,
+                account: ALICE,
+                methodName: "add",
+                methodParameters: [ALICE.address, 1],
+            });
+            await sendRawTransaction(currentTx);
+            expect((await send("txpool_status")).queued).eq(ZERO);
+            expect(await sendGetNonce(ALICE)).eq(nonce + 2);
         });
     });
 
@@ -267,6 +556,90 @@ describe("JSON-RPC", () => {
                 await send("evm_setNextBlockTimestamp", [0]);
             });
         });
+
+        it("evm_increaseTime", async () => {
+            const prev_timestamp = (await latest()).timestamp;
+            await send("evm_increaseTime", [toHex(3600)]);
+            await sendEvmMine();
+            expect((await latest()).timestamp).gte(prev_timestamp + 3600);
+
+            await send("evm_setNextBlockTimestamp", [0]);
+        });
+
+        it("hardhat_mine", async () => {
+            const prev = await latest();
+            await send("hardhat_mine", [toHex(3), toHex(60)]);
+            const current = await latest();
+            expect(current.block_number).eq(prev.block_number + 3);
+            expect(current.timestamp).gte(prev.timestamp + 120);
+
+            await send("evm_setNextBlockTimestamp", [0]);
+        });
+
+        it("hardhat_mine refuses to mine too many blocks", async () => {
+            const prev = await latest();
+            const error = await sendAndGetError("hardhat_mine", [toHex(1_000_000)]);
+            expect(error.code).eq(-32602);
+            expect((await latest()).block_number).eq(prev.block_number);
+        });
+
+        it("evm_snapshot and evm_revert", async () => {
+            const address = "0x000000000000000000000000000000000000d00d";
+            await send("hardhat_setBalance", [address, toHex(1000)]);
+            const prev = await latest();
+            const snapshot = await send("evm_snapshot");
+
+            // changes the state of the snapshot block, the time and mines a block
+            await send("hardhat_setBalance", [address, toHex(2000)]);
+            await send("evm_increaseTime", [toHex(3600)]);
+            await sendEvmMine();
+
+            (await sendExpect("evm_revert", [snapshot])).eq(true);
+            (await sendExpect("eth_getBalance", [address, "latest"])).eq(toHex(1000));
+            expect((await latest()).block_number).eq(prev.block_number);
+            await sendEvmMine();
+            expect((await latest()).timestamp).lt(prev.timestamp + 3600);
+
+            (await sendExpect("evm_revert", [snapshot])).eq(false);
+        });
+
+        describe("Account state", () => {
+            const address = "0x000000000000000000000000000000000000beef";
+
+            it("hardhat_setBalance", async () => {
+                (await sendExpect("hardhat_setBalance", [address, toHex(1000)])).eq(true);
+                (await sendExpect("eth_getBalance", [address, "latest"])).eq(toHex(1000));
+            });
+
+            it("hardhat_setNonce", async () => {
+                (await sendExpect("hardhat_setNonce", [address, toHex(5)])).eq(true);
+                expect(await sendGetNonce(address)).eq(5);
+            });
+
+            it("hardhat_setCode", async () => {
+                (await sendExpect("hardhat_setCode", [address, "0x6001"])).eq(true);
+                (await sendExpect("eth_getCode", [address, "latest"])).eq("0x6001");
+            });
+
+            it("hardhat_setStorageAt", async () => {
+                (await sendExpect("hardhat_setStorageAt", [address, toHex(1), toPaddedHex(2, 32)])).eq(true);
+                (await sendExpect("eth_getStorageAt", [address, toHex(1), "latest"])).eq(toPaddedHex(2, 32));
+            });
+        });
+
+        it("hardhat_impersonateAccount", async () => {
+            const address = "0x000000000000000000000000000000000000cafe";
+            const tx = { from: address, to: BOB.address, value: toHex(1) };
+            await send("hardhat_setBalance", [address, TEST_BALANCE]);
+
+            (await sendExpect("hardhat_impersonateAccount", [address])).eq(true);
+            const nonce = await sendGetNonce(address);
+            await send("eth_sendTransaction", [tx]);
+            expect(await sendGetNonce(address)).eq(nonce + 1);
+
+            (await sendExpect("hardhat_stopImpersonatingAccount", [address])).eq(true);
+            expect(await sendAndGetError("eth_sendTransaction", [tx])).to.not.be.undefined;
+        });
     });
 
     describe("Subscription", () => {
@@ -300,6 +673,14 @@ describe("JSON-RPC", () => {
                 expect(response.result).to.not.be.undefined;
             });
 
+            it("subscribes to droppedTransactions receives success subscription event", async () => {
+                const waitTimeInMilliseconds = 40;
+                const response = await subscribeAndGetEvent("droppedTransactions", waitTimeInMilliseconds);
+                expect(response).to.not.be.undefined;
+                expect(response.id).to.not.be.undefined;
+                expect(response.result).to.not.be.undefined;
+            });
+
             it("subscribes to unsupported receives error subscription event", async () => {
                 const waitTimeInMilliseconds = 40;
                 const response = await subscribeAndGetEvent("unsupportedSubscription", waitTimeInMilliseconds);
@@ -52,7 +52,7 @@ async fn run(config: ImporterOfflineConfig) -> anyhow::Result<()> {
 
     // init services
     let rpc_storage = config.rpc_storage.init().await?;
-    let storage = config.storage.init()?;
+    let storage = config.storage.init().await?;
     let miner = config.miner.init_with_mode(MinerMode::External, Arc::clone(&storage)).await?;
     let executor = config.executor.init(Arc::clone(&storage), Arc::clone(&miner));
 
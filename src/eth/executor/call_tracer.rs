@@ -0,0 +1,176 @@
+use revm::interpreter::CallInputs;
+use revm::interpreter::CallOutcome;
+use revm::interpreter::CallScheme;
+use revm::interpreter::CreateInputs;
+use revm::interpreter::CreateOutcome;
+use revm::interpreter::InstructionResult;
+use revm::interpreter::InterpreterResult;
+use revm::primitives::CreateScheme;
+use revm::Database;
+use revm::EvmContext;
+use revm::Inspector;
+
+use crate::alias::RevmAddress;
+use crate::alias::RevmLog;
+use crate::eth::primitives::decode_revert_reason;
+use crate::eth::primitives::Bytes;
+use crate::eth::primitives::CallFrame;
+use crate::eth::primitives::CallFrameLog;
+use crate::eth::primitives::CallKind;
+use crate::eth::primitives::Gas;
+use crate::eth::primitives::TracerConfig;
+use crate::ext::not;
+use crate::if_else;
+
+/// Inspector that tracks call frames executed by the EVM in the same format of Geth `callTracer`.
+#[derive(Default)]
+pub struct CallTracer {
+    /// Tracer configuration received from the client.
+    config: TracerConfig,
+
+    /// Call frames that started executing, but did not finish yet.
+    stack: Vec<CallFrame>,
+
+    /// Top-level call frame after the execution finishes.
+    root: Option<CallFrame>,
+}
+
+impl CallTracer {
+    /// Creates a new tracer for a single execution.
+    pub fn new(config: TracerConfig) -> Self {
+        Self {
+            config,
+            stack: Vec::new(),
+            root: None,
+        }
+    }
+
+    /// Takes the top-level call frame tracked during the execution.
+    pub fn take_root(&mut self) -> Option<CallFrame> {
+        let mut root = self.root.take()?;
+        root.clear_failed_logs();
+        Some(root)
+    }
+
+    fn enter(&mut self, frame: CallFrame) {
+        self.stack.push(frame);
+    }
+
+    fn exit(&mut self, result: &InterpreterResult, created_address: Option<RevmAddress>) {
+        let Some(mut frame) = self.stack.pop() else {
+            tracing::error!("call tracer exited a call frame that was never entered");
+            return;
+        };
+
+        // fill results
+        frame.gas_used = Gas::from(result.gas.spent());
+        if let Some(created_address) = created_address {
+            frame.to = Some(created_address.into());
+        }
+        if not(result.output.is_empty()) {
+            frame.output = Some(Bytes::from(&result.output));
+        }
+        if not(result.is_ok()) {
+            frame.error = Some(error_message(result.result));
+        }
+        if result.is_revert() {
+            frame.revert_reason = decode_revert_reason(&result.output);
+        }
+
+        // attach to parent frame or finish execution
+        match self.stack.last_mut() {
+            Some(parent) =>
+                if not(self.config.only_top_call) {
+                    parent.calls.push(frame);
+                },
+            None => {
+                self.root = Some(frame);
+            }
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CallTracer {
+    fn log(&mut self, _: &mut EvmContext<DB>, log: &RevmLog) {
+        if not(self.config.with_log) {
+            return;
+        }
+        let Some(frame) = self.stack.last_mut() else { return };
+
+        frame.logs.push(CallFrameLog {
+            address: log.address.into(),
+            topics: log.data.topics().iter().copied().map(Into::into).collect(),
+            data: Bytes::from(&log.data.data),
+            position: frame.calls.len(),
+        });
+    }
+
+    fn call(&mut self, _: &mut EvmContext<DB>, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        let kind = match inputs.scheme {
+            CallScheme::Call => CallKind::Call,
+            CallScheme::CallCode => CallKind::CallCode,
+            CallScheme::DelegateCall => CallKind::DelegateCall,
+            CallScheme::StaticCall => CallKind::StaticCall,
+        };
+        let value = if_else!(kind == CallKind::StaticCall, None, Some(inputs.call_value().into()));
+
+        self.enter(CallFrame::new(
+            kind,
+            inputs.caller.into(),
+            Some(inputs.target_address.into()),
+            value,
+            Gas::from(inputs.gas_limit),
+            Bytes::from(&inputs.input),
+        ));
+        None
+    }
+
+    fn call_end(&mut self, _: &mut EvmContext<DB>, _: &CallInputs, outcome: CallOutcome) -> CallOutcome {
+        self.exit(&outcome.result, None);
+        outcome
+    }
+
+    fn create(&mut self, _: &mut EvmContext<DB>, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        let kind = match inputs.scheme {
+            CreateScheme::Create => CallKind::Create,
+            CreateScheme::Create2 { .. } => CallKind::Create2,
+        };
+
+        self.enter(CallFrame::new(
+            kind,
+            inputs.caller.into(),
+            None,
+            Some(inputs.value.into()),
+            Gas::from(inputs.gas_limit),
+            Bytes::from(&inputs.init_code),
+        ));
+        None
+    }
+
+    fn create_end(&mut self, _: &mut EvmContext<DB>, _: &CreateInputs, outcome: CreateOutcome) -> CreateOutcome {
+        self.exit(&outcome.result, outcome.address);
+        outcome
+    }
+}
+
+/// Converts the failure reason to the same message used by Geth.
+fn error_message(result: InstructionResult) -> String {
+    match result {
+        InstructionResult::Revert => "execution reverted".to_owned(),
+        InstructionResult::OutOfGas
+        | InstructionResult::MemoryOOG
+        | InstructionResult::MemoryLimitOOG
+        | InstructionResult::PrecompileOOG
+        | InstructionResult::InvalidOperandOOG => "out of gas".to_owned(),
+        InstructionResult::OpcodeNotFound => "invalid opcode".to_owned(),
+        InstructionResult::InvalidJump => "invalid jump destination".to_owned(),
+        InstructionResult::StackUnderflow => "stack underflow".to_owned(),
+        InstructionResult::StackOverflow => "stack limit reached".to_owned(),
+        InstructionResult::StateChangeDuringStaticCall | InstructionResult::CallNotAllowedInsideStatic => "write protection".to_owned(),
+        InstructionResult::CallTooDeep => "max call depth exceeded".to_owned(),
+        InstructionResult::OutOfFunds => "insufficient balance for transfer".to_owned(),
+        InstructionResult::CreateCollision => "contract address collision".to_owned(),
+        InstructionResult::CreateContractSizeLimit => "max code size exceeded".to_owned(),
+        other => format!("{:?}", other),
+    }
+}
@@ -4,16 +4,19 @@ use std::sync::Arc;
 
 use anyhow::anyhow;
 use itertools::Itertools;
+use revm::handler::register::EvmHandler;
+use revm::inspector_handle_register;
 use revm::primitives::AccountInfo;
 use revm::primitives::AnalysisKind;
 use revm::primitives::EVMError;
 use revm::primitives::ExecutionResult as RevmExecutionResult;
+use revm::primitives::HaltReason;
 use revm::primitives::InvalidTransaction;
 use revm::primitives::ResultAndState as RevmResultAndState;
-use revm::primitives::SpecId;
 use revm::primitives::State as RevmState;
 use revm::primitives::TransactTo;
 use revm::primitives::B256;
+use revm::primitives::KECCAK_EMPTY;
 use revm::primitives::U256;
 use revm::Database;
 use revm::Evm as RevmEvm;
@@ -21,14 +24,19 @@ use revm::Handler;
 
 use crate::alias::RevmAddress;
 use crate::alias::RevmBytecode;
+use crate::eth::executor::CallTracer;
 use crate::eth::executor::EvmExecutionResult;
 use crate::eth::executor::EvmInput;
 use crate::eth::executor::ExecutorConfig;
+use crate::eth::executor::InternalTransactionsTracer;
 use crate::eth::primitives::Account;
 use crate::eth::primitives::Address;
+use crate::eth::primitives::BlockNumber;
 use crate::eth::primitives::Bytes;
+use crate::eth::primitives::CodeHash;
 use crate::eth::primitives::EvmExecution;
 use crate::eth::primitives::EvmExecutionMetrics;
+use crate::eth::primitives::EvmTrace;
 use crate::eth::primitives::ExecutionAccountChanges;
 use crate::eth::primitives::ExecutionChanges;
 use crate::eth::primitives::ExecutionResult;
@@ -38,11 +46,14 @@ use crate::eth::primitives::Log;
 use crate::eth::primitives::Slot;
 use crate::eth::primitives::SlotIndex;
 use crate::eth::primitives::StratusError;
+use crate::eth::primitives::TracerOptions;
+use crate::eth::primitives::TracerType;
 use crate::eth::storage::StratusStorage;
 use crate::ext::not;
 use crate::ext::OptionExt;
 #[cfg(feature = "metrics")]
 use crate::infra::metrics;
+use crate::log_and_err;
 
 /// Maximum gas limit allowed for a transaction. Prevents a transaction from consuming too many resources.
 const GAS_MAX_LIMIT: u64 = 1_000_000_000;
@@ -50,139 +61,216 @@ const GAS_MAX_LIMIT: u64 = 1_000_000_000;
 /// Implementation of EVM using [`revm`](https://crates.io/crates/revm).
 pub struct Evm {
     evm: RevmEvm<'static, (), RevmSession>,
+    call_tracer: RevmEvm<'static, CallTracer, RevmSession>,
+    internal_transactions_tracer: Option<RevmEvm<'static, InternalTransactionsTracer, RevmSession>>,
 }
 
 impl Evm {
     /// Creates a new instance of the Evm.
-    #[allow(clippy::arc_with_non_send_sync)]
     pub fn new(storage: Arc<StratusStorage>, config: ExecutorConfig) -> Self {
         tracing::info!(?config, "creating revm");
 
-        // configure handler
-        let mut handler = Handler::mainnet_with_spec(SpecId::LONDON);
-
-        // handler custom validators
-        let validate_tx_against_state = handler.validation.tx_against_state;
-        handler.validation.tx_against_state = Arc::new(move |ctx| {
-            let result = validate_tx_against_state(ctx);
-            if result.is_err() {
-                let _ = ctx.evm.inner.journaled_state.finalize(); // clear revm state on validation failure
-            }
-            result
+        let evm = build_revm(Arc::clone(&storage), config.clone(), ());
+        let internal_transactions_tracer = config.executor_internal_transactions.then(|| {
+            build_revm(Arc::clone(&storage), config.clone(), InternalTransactionsTracer::default())
+                .modify()
+                .append_handler_register(inspector_handle_register)
+                .build()
         });
-
-        // handler custom instructions
-        let instructions = handler.take_instruction_table();
-        handler.set_instruction_table(instructions);
-
-        // configure revm
-        let chain_id = config.executor_chain_id;
-        let mut evm = RevmEvm::builder()
-            .with_external_context(())
-            .with_db(RevmSession::new(storage, config))
-            .with_handler(handler)
+        let call_tracer = build_revm(storage, config, CallTracer::default())
+            .modify()
+            .append_handler_register(inspector_handle_register)
             .build();
 
-        // global general config
-        let cfg_env = evm.cfg_mut();
-        cfg_env.chain_id = chain_id;
-        cfg_env.limit_contract_code_size = Some(usize::MAX);
-        cfg_env.perf_analyse_created_bytecodes = AnalysisKind::Raw;
-
-        // global block config
-        let block_env = evm.block_mut();
-        block_env.coinbase = Address::COINBASE.into();
-
-        // global tx config
-        let tx_env = evm.tx_mut();
-        tx_env.gas_priority_fee = None;
-
-        Self { evm }
+        Self {
+            evm,
+            call_tracer,
+            internal_transactions_tracer,
+        }
     }
 
     /// Execute a transaction that deploys a contract or call a contract function.
+    ///
+    /// Internal transactions are recorded in the execution if enabled in the executor.
     pub fn execute(&mut self, input: EvmInput) -> Result<EvmExecutionResult, StratusError> {
-        #[cfg(feature = "metrics")]
-        let start = metrics::now();
-
-        // configure session
-        let evm = &mut self.evm;
-        evm.db_mut().reset(input.clone());
-
-        // configure block params
-        let block_env = evm.block_mut();
-        block_env.basefee = U256::ZERO;
-        block_env.timestamp = input.block_timestamp.into();
-        block_env.number = input.block_number.into();
-        let block_env_log = block_env.clone();
-
-        // configure tx params
-        let tx_env = &mut evm.tx_mut();
-        tx_env.caller = input.from.into();
-        tx_env.transact_to = match input.to {
-            Some(contract) => TransactTo::Call(contract.into()),
-            None => TransactTo::Create,
+        let Some(ref mut internal_transactions_tracer) = self.internal_transactions_tracer else {
+            return execute_in_revm(&mut self.evm, input);
         };
-        tx_env.gas_limit = min(input.gas_limit.into(), GAS_MAX_LIMIT);
-        tx_env.gas_price = input.gas_price.into();
-        tx_env.chain_id = input.chain_id.map_into();
-        tx_env.nonce = input.nonce.map_into();
-        tx_env.data = input.data.into();
-        tx_env.value = input.value.into();
-        let tx_env_log = tx_env.clone();
-
-        // execute transaction
-        tracing::info!(block_env = ?block_env_log, tx_env = ?tx_env_log, "executing transaction in revm");
-        let evm_result = evm.transact();
-
-        // extract results
-        let session = evm.db_mut();
-        let session_input = std::mem::take(&mut session.input);
-        let session_storage_changes = std::mem::take(&mut session.storage_changes);
-        let session_metrics = std::mem::take(&mut session.metrics);
-        #[cfg(feature = "metrics")]
-        let session_point_in_time = std::mem::take(&mut session.input.point_in_time);
-
-        // parse result
-        let execution = match evm_result {
-            // executed
-            Ok(result) => Ok(parse_revm_execution(result, session_input, session_storage_changes)),
-
-            // nonce errors
-            Err(EVMError::Transaction(InvalidTransaction::NonceTooHigh { tx, state })) => Err(StratusError::TransactionNonce {
-                transaction: tx.into(),
-                account: state.into(),
-            }),
-            Err(EVMError::Transaction(InvalidTransaction::NonceTooLow { tx, state })) => Err(StratusError::TransactionNonce {
-                transaction: tx.into(),
-                account: state.into(),
-            }),
-
-            // storage error
-            Err(EVMError::Database(e)) => {
-                tracing::warn!(reason = ?e, "evm storage error");
-                Err(e)
-            }
 
-            // unexpected errors
-            Err(e) => {
-                tracing::warn!(reason = ?e, "evm transaction error");
-                Err(StratusError::TransactionEvmFailed(e.to_string()))
+        let result = execute_in_revm(internal_transactions_tracer, input);
+        let internal_transactions = internal_transactions_tracer.context.external.take_transactions();
+        result.map(|mut result| {
+            result.execution.internal_transactions = internal_transactions;
+            result
+        })
+    }
+
+    /// Execute a transaction that deploys a contract or call a contract function inspecting it with the specified tracer.
+    pub fn trace(&mut self, input: EvmInput, options: TracerOptions) -> Result<EvmTrace, StratusError> {
+        match options.tracer {
+            TracerType::CallTracer => {
+                let gas_limit = min(input.gas_limit.into(), GAS_MAX_LIMIT);
+
+                // execute with tracer attached
+                self.call_tracer.context.external = CallTracer::new(options.tracer_config);
+                let result = execute_in_revm(&mut self.call_tracer, input);
+                let root = std::mem::take(&mut self.call_tracer.context.external).take_root();
+                let result = result?;
+
+                // top-level frame reports the transaction gas instead of the gas available after intrinsic costs
+                let Some(mut root) = root else {
+                    return log_and_err!("call tracer did not track the top-level call frame").map_err(Into::into);
+                };
+                root.gas = Gas::from(gas_limit);
+                root.gas_used = result.execution.gas;
+                Ok(EvmTrace::Call(root))
             }
-        };
+            TracerType::PrestateTracer => {
+                let result = self.execute(input)?;
+                Ok(EvmTrace::from_changes(&result.execution.changes, options.tracer_config.diff_mode))
+            }
+        }
+    }
+}
 
-        // track metrics
-        #[cfg(feature = "metrics")]
-        {
-            metrics::inc_evm_execution(start.elapsed(), &session_point_in_time, execution.is_ok());
-            metrics::inc_evm_execution_account_reads(session_metrics.account_reads);
+/// Creates a revm instance with Stratus configuration and the specified external context.
+#[allow(clippy::arc_with_non_send_sync)]
+fn build_revm<EXT>(storage: Arc<StratusStorage>, config: ExecutorConfig, external: EXT) -> RevmEvm<'static, EXT, RevmSession> {
+    // configure handler
+    let mut handler = Handler::mainnet_with_spec(config.executor_hardforks.spec_at(BlockNumber::ZERO));
+    handler.append_handler_register_plain(stratus_handler_register);
+
+    // configure revm
+    let chain_id = config.executor_chain_id;
+    let mut evm = RevmEvm::builder()
+        .with_external_context(external)
+        .with_db(RevmSession::new(storage, config))
+        .with_handler(handler)
+        .build();
+
+    // global general config
+    let cfg_env = evm.cfg_mut();
+    cfg_env.chain_id = chain_id;
+    cfg_env.limit_contract_code_size = Some(usize::MAX);
+    cfg_env.perf_analyse_created_bytecodes = AnalysisKind::Raw;
+
+    // global block config
+    let block_env = evm.block_mut();
+    block_env.coinbase = Address::COINBASE.into();
+
+    evm
+}
+
+/// Customizes the revm handler with Stratus behaviour.
+///
+/// Customizations are applied as a handler register because revm recreates the handler when the spec changes.
+fn stratus_handler_register<EXT>(handler: &mut EvmHandler<'_, EXT, RevmSession>) {
+    // handler custom validators
+    let validate_tx_against_state = Arc::clone(&handler.validation.tx_against_state);
+    handler.validation.tx_against_state = Arc::new(move |ctx| {
+        let result = validate_tx_against_state(ctx);
+        if result.is_err() {
+            let _ = ctx.evm.inner.journaled_state.finalize(); // clear revm state on validation failure
         }
+        result
+    });
 
-        execution.map(|execution| EvmExecutionResult {
-            execution,
-            metrics: session_metrics,
-        })
+    // handler custom instructions
+    let instructions = handler.take_instruction_table();
+    handler.set_instruction_table(instructions);
+}
+
+/// Execute a transaction that deploys a contract or call a contract function in the specified revm instance.
+fn execute_in_revm<EXT>(evm: &mut RevmEvm<'static, EXT, RevmSession>, input: EvmInput) -> Result<EvmExecutionResult, StratusError> {
+    #[cfg(feature = "metrics")]
+    let start = metrics::now();
+
+    // configure session
+    evm.db_mut().reset(input.clone());
+
+    // configure hardfork rules of the block
+    let spec_id = evm.db().config.executor_hardforks.spec_at(input.block_number);
+    evm.modify_spec_id(spec_id);
+
+    // configure block params
+    let block_env = evm.block_mut();
+    block_env.basefee = input.block_base_fee.into();
+    block_env.timestamp = input.block_timestamp.into();
+    block_env.number = input.block_number.into();
+    let block_env_log = block_env.clone();
+
+    // configure tx params
+    let tx_env = &mut evm.tx_mut();
+    tx_env.caller = input.from.into();
+    tx_env.transact_to = match input.to {
+        Some(contract) => TransactTo::Call(contract.into()),
+        None => TransactTo::Create,
+    };
+    tx_env.gas_limit = min(input.gas_limit.into(), GAS_MAX_LIMIT);
+    tx_env.gas_price = input.gas_price.into();
+    tx_env.gas_priority_fee = input.gas_priority_fee.map_into();
+    tx_env.chain_id = input.chain_id.map_into();
+    tx_env.nonce = input.nonce.map_into();
+    tx_env.data = input.data.into();
+    tx_env.value = input.value.into();
+    let tx_env_log = tx_env.clone();
+
+    // execute transaction
+    tracing::info!(block_env = ?block_env_log, tx_env = ?tx_env_log, ?spec_id, "executing transaction in revm");
+    let evm_result = evm.transact();
+
+    // extract results
+    let session = evm.db_mut();
+    let session_input = std::mem::take(&mut session.input);
+    let session_storage_changes = std::mem::take(&mut session.storage_changes);
+    let session_metrics = std::mem::take(&mut session.metrics);
+    #[cfg(feature = "metrics")]
+    let session_point_in_time = std::mem::take(&mut session.input.point_in_time);
+
+    // parse result
+    let execution = match evm_result {
+        // executed
+        Ok(result) => Ok(parse_revm_execution(result, session_input, session_storage_changes)),
+
+        // nonce errors
+        Err(EVMError::Transaction(InvalidTransaction::NonceTooHigh { tx, state })) => Err(StratusError::TransactionNonce {
+            transaction: tx.into(),
+            account: state.into(),
+        }),
+        Err(EVMError::Transaction(InvalidTransaction::NonceTooLow { tx, state })) => Err(StratusError::TransactionNonce {
+            transaction: tx.into(),
+            account: state.into(),
+        }),
+
+        // fee errors
+        Err(EVMError::Transaction(InvalidTransaction::GasPriceLessThanBasefee)) => Err(StratusError::TransactionFeeTooLow {
+            base_fee: input.block_base_fee,
+        }),
+
+        // storage error
+        Err(EVMError::Database(e)) => {
+            tracing::warn!(reason = ?e, "evm storage error");
+            Err(e)
+        }
+
+        // unexpected errors
+        Err(e) => {
+            tracing::warn!(reason = ?e, "evm transaction error");
+            Err(StratusError::TransactionEvmFailed(e.to_string()))
+        }
+    };
+
+    // track metrics
+    #[cfg(feature = "metrics")]
+    {
+        metrics::inc_evm_execution(start.elapsed(), &session_point_in_time, execution.is_ok());
+        metrics::inc_evm_execution_account_reads(session_metrics.account_reads);
     }
+
+    execution.map(|execution| EvmExecutionResult {
+        execution,
+        metrics: session_metrics,
+    })
 }
 
 // -----------------------------------------------------------------------------
@@ -233,9 +321,17 @@ impl Database for RevmSession {
     fn basic(&mut self, revm_address: RevmAddress) -> Result<Option<AccountInfo>, StratusError> {
         self.metrics.account_reads += 1;
 
-        // retrieve account
+        // retrieve account (changes made by previous transactions have precedence over storage)
         let address: Address = revm_address.into();
-        let account = self.storage.read_account(&address, &self.input.point_in_time)?;
+        let mut account = match self.input.prior_changes.get(&address) {
+            Some(changes) => changes.to_account(),
+            None => self.storage.read_account(&address, &self.input.point_in_time)?,
+        };
+
+        // apply values overridden by the caller
+        if let Some(account_override) = self.input.state_override.get(&address) {
+            account_override.apply(&mut account);
+        }
 
         // warn if the loaded account is the `to` account and it does not have a bytecode
         if let Some(ref to_address) = self.input.to {
@@ -260,8 +356,44 @@ impl Database for RevmSession {
         Ok(Some(revm_account))
     }
 
-    fn code_by_hash(&mut self, _: B256) -> Result<RevmBytecode, StratusError> {
-        todo!()
+    fn code_by_hash(&mut self, revm_code_hash: B256) -> Result<RevmBytecode, StratusError> {
+        // accounts without bytecode reference the hash of an empty bytecode
+        if revm_code_hash == KECCAK_EMPTY {
+            return Ok(RevmBytecode::new());
+        }
+
+        // retrieve bytecode (bytecodes overridden by the caller or deployed by previous transactions or in this transaction have precedence over storage)
+        let code_hash: CodeHash = revm_code_hash.into();
+        let overridden_bytecode = self
+            .input
+            .state_override
+            .values()
+            .filter_map(|account_override| account_override.code.as_ref())
+            .find(|code| CodeHash::from_bytecode(Some((*code).clone())) == code_hash)
+            .cloned();
+        let changed_bytecode = overridden_bytecode.or_else(|| {
+            self.input
+                .prior_changes
+                .values()
+                .chain(self.storage_changes.values())
+                .filter(|changes| changes.code_hash == code_hash)
+                .find_map(|changes| changes.bytecode.take_ref().cloned().flatten())
+        });
+        let bytecode = match changed_bytecode {
+            Some(bytecode) => bytecode,
+            None => match self.storage.read_bytecode(&code_hash)? {
+                Some(bytecode) => bytecode,
+                None => {
+                    tracing::error!(reason = "bytecode not found", ?code_hash);
+                    return Err(StratusError::Unexpected(anyhow!(
+                        "Bytecode with hash '{:?}' was expected to exist, but it was not",
+                        code_hash
+                    )));
+                }
+            },
+        };
+
+        Ok(bytecode.into())
     }
 
     fn storage(&mut self, revm_address: RevmAddress, revm_index: U256) -> Result<U256, StratusError> {
@@ -271,8 +403,14 @@ impl Database for RevmSession {
         let address: Address = revm_address.into();
         let index: SlotIndex = revm_index.into();
 
-        // load slot from storage
-        let slot = self.storage.read_slot(&address, &index, &self.input.point_in_time)?;
+        // load slot (values overridden by the caller and changes made by previous transactions have precedence over storage)
+        let override_value = self.input.state_override.get(&address).and_then(|overrides| overrides.slot(&index));
+        let prior_slot = self.input.prior_changes.get(&address).and_then(|changes| changes.slots.get(&index));
+        let slot = match (override_value, prior_slot.and_then(|slot| slot.take_ref())) {
+            (Some(value), _) => Slot::new(index, value),
+            (None, Some(slot)) => *slot,
+            (None, None) => self.storage.read_slot(&address, &index, &self.input.point_in_time)?,
+        };
 
         // track original value, except if ignored address
         if not(address.is_ignored()) {
@@ -293,8 +431,19 @@ impl Database for RevmSession {
         Ok(slot.value.into())
     }
 
-    fn block_hash(&mut self, _: U256) -> Result<B256, StratusError> {
-        todo!()
+    fn block_hash(&mut self, revm_number: U256) -> Result<B256, StratusError> {
+        // only the 256 blocks before the current one are available
+        let number: u64 = revm_number.saturating_to();
+        let current = self.input.block_number.as_u64();
+        if number >= current || current - number > 256 {
+            return Ok(B256::ZERO);
+        }
+
+        // retrieve block hash (blocks that are not stored are not known)
+        match self.storage.read_block_hash(number.into())? {
+            Some(hash) => Ok(hash.0 .0.into()),
+            None => Ok(B256::ZERO),
+        }
     }
 }
 
@@ -323,6 +472,7 @@ fn parse_revm_execution(revm_result: RevmResultAndState, input: EvmInput, execut
         gas,
         changes,
         deployed_contract_address,
+        internal_transactions: Vec::new(),
     }
 }
 
@@ -342,7 +492,8 @@ fn parse_revm_result(result: RevmExecutionResult) -> (ExecutionResult, Bytes, Ve
             (result, output, Vec::new(), gas)
         }
         RevmExecutionResult::Halt { reason, gas_used } => {
-            let result = ExecutionResult::new_halted(format!("{:?}", reason));
+            let out_of_gas = matches!(reason, HaltReason::OutOfGas(_));
+            let result = ExecutionResult::new_halted(format!("{:?}", reason), out_of_gas);
             let output = Bytes::default();
             let gas = Gas::from(gas_used);
             (result, output, Vec::new(), gas)
@@ -1,16 +1,22 @@
+use std::collections::HashMap;
+
 use display_json::DebugAsJson;
 
 use crate::eth::primitives::Address;
 use crate::eth::primitives::Block;
 use crate::eth::primitives::BlockNumber;
+use crate::eth::primitives::BlockOverride;
 use crate::eth::primitives::Bytes;
 use crate::eth::primitives::CallInput;
 use crate::eth::primitives::ChainId;
+use crate::eth::primitives::ExecutionChanges;
 use crate::eth::primitives::ExternalReceipt;
 use crate::eth::primitives::ExternalTransaction;
 use crate::eth::primitives::Gas;
 use crate::eth::primitives::Nonce;
+use crate::eth::primitives::StateOverride;
 use crate::eth::primitives::TransactionInput;
+use crate::eth::primitives::TransactionMined;
 use crate::eth::primitives::UnixTime;
 use crate::eth::primitives::Wei;
 use crate::eth::storage::StoragePointInTime;
@@ -62,14 +68,26 @@ pub struct EvmInput {
     pub gas_limit: Gas,
 
     /// Gas price paid by each unit of gas consumed by the transaction.
+    ///
+    /// In EIP-1559 transactions, it is the max fee per gas.
     pub gas_price: Wei,
 
+    /// Max fee per gas paid to the block producer on top of the base fee.
+    ///
+    /// Present only in EIP-1559 transactions.
+    pub gas_priority_fee: Option<Wei>,
+
     /// Number of the block where the transaction will be or was included.
     pub block_number: BlockNumber,
 
     /// Timestamp of the block where the transaction will be or was included.
     pub block_timestamp: UnixTime,
 
+    /// Base fee per gas of the block where the transaction will be or was included.
+    ///
+    /// It is zero when the fee market is disabled.
+    pub block_base_fee: Wei,
+
     /// Point-in-time from where accounts and slots will be read.
     pub point_in_time: StoragePointInTime,
 
@@ -77,28 +95,47 @@ pub struct EvmInput {
     ///
     /// If not specified, it will not be validated.
     pub chain_id: Option<ChainId>,
+
+    /// Changes made by previous transactions that are not present in the storage at `point_in_time`.
+    ///
+    /// Accounts and slots present here are read from these changes instead of the storage.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub prior_changes: ExecutionChanges,
+
+    /// Accounts overridden by the caller of an `eth_call`.
+    ///
+    /// Overridden values have precedence over both `prior_changes` and the storage.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub state_override: StateOverride,
 }
 
 impl EvmInput {
     /// Creates from a transaction that was sent directly to Stratus with `eth_sendRawTransaction`.
-    pub fn from_eth_transaction(input: TransactionInput, pending_block_number: BlockNumber) -> Self {
+    pub fn from_eth_transaction(input: TransactionInput, pending_block_number: BlockNumber, pending_base_fee: Wei) -> Self {
+        let (gas_limit, gas_price, gas_priority_fee) = transaction_fees(&input, pending_base_fee);
         Self {
             from: input.signer,
             to: input.to,
             value: input.value,
             data: input.input,
-            gas_limit: Gas::MAX,
-            gas_price: Wei::ZERO,
+            gas_limit,
+            gas_price,
+            gas_priority_fee,
             nonce: Some(input.nonce),
             block_number: pending_block_number,
             block_timestamp: UnixTime::now(), // TODO: this should come from the pending block
+            block_base_fee: pending_base_fee,
             point_in_time: StoragePointInTime::Pending,
             chain_id: input.chain_id,
+            prior_changes: ExecutionChanges::default(),
+            state_override: StateOverride::default(),
         }
     }
 
     /// Creates from a call that was sent directly to Stratus with `eth_call` or `eth_estimateGas`.
     ///
+    /// Block values specified in `block_override` replace the ones derived from `point_in_time`.
+    ///
     /// # Errors:
     ///
     /// If `point_in_time` is `MinedPast` it's required that `mined_block` is `Some`, otherwise, this function returns an error.
@@ -107,14 +144,17 @@ impl EvmInput {
         point_in_time: StoragePointInTime,
         pending_block_number: BlockNumber,
         mined_block: Option<Block>,
+        state_override: StateOverride,
+        block_override: BlockOverride,
     ) -> anyhow::Result<Self> {
-        Ok(Self {
+        let mut evm_input = Self {
             from: input.from.unwrap_or(Address::ZERO),
             to: input.to.map_into(),
             value: input.value,
             data: input.data,
             gas_limit: Gas::MAX,
             gas_price: Wei::ZERO,
+            gas_priority_fee: None,
             nonce: None,
             block_number: match point_in_time {
                 StoragePointInTime::Mined | StoragePointInTime::Pending => pending_block_number,
@@ -127,9 +167,22 @@ impl EvmInput {
                     None => return log_and_err!("failed to create EvmInput: couldn't determine mined block timestamp"),
                 },
             },
+            block_base_fee: Wei::ZERO,
             point_in_time,
             chain_id: None,
-        })
+            prior_changes: ExecutionChanges::default(),
+            state_override,
+        };
+
+        // apply block overrides
+        if let Some(number) = block_override.number {
+            evm_input.block_number = number;
+        }
+        if let Some(timestamp) = block_override.timestamp() {
+            evm_input.block_timestamp = timestamp;
+        }
+
+        Ok(evm_input)
     }
 
     /// Creates a transaction that was executed in an external blockchain and imported to Stratus.
@@ -144,16 +197,50 @@ impl EvmInput {
             nonce: Some(tx.0.nonce.try_into()?),
             gas_limit: if_else!(receipt.is_success(), Gas::MAX, tx.0.gas.try_into()?),
             gas_price: if_else!(receipt.is_success(), Wei::ZERO, tx.0.gas_price.map_into().unwrap_or(Wei::ZERO)),
+            gas_priority_fee: None,
             point_in_time: StoragePointInTime::Pending,
             block_number,
             block_timestamp,
+            block_base_fee: Wei::ZERO,
             chain_id: match tx.0.chain_id {
                 Some(chain_id) => Some(chain_id.try_into()?),
                 None => None,
             },
+            prior_changes: ExecutionChanges::default(),
+            state_override: StateOverride::default(),
         })
     }
 
+    /// Creates from a transaction that was already mined, so it can be re-executed with the same state it was originally executed.
+    ///
+    /// The state is read from the end of the previous block and `prior_changes` must contain the changes made by the transactions that precede it in the same block.
+    ///
+    /// Like external transactions, failed transactions executed without fee market are re-executed with their own gas limit and gas price to reproduce the failure.
+    pub fn from_mined_transaction(tx: TransactionMined, block_timestamp: UnixTime, block_base_fee: Wei, prior_changes: ExecutionChanges) -> Self {
+        let (gas_limit, gas_price, gas_priority_fee) = if_else!(
+            block_base_fee.is_zero() && not(tx.is_success()),
+            (tx.input.gas_limit, tx.input.gas_price, None),
+            transaction_fees(&tx.input, block_base_fee)
+        );
+        Self {
+            from: tx.input.signer,
+            to: tx.input.to,
+            value: tx.input.value,
+            data: tx.input.input,
+            nonce: Some(tx.input.nonce),
+            gas_limit,
+            gas_price,
+            gas_priority_fee,
+            block_number: tx.block_number,
+            block_timestamp,
+            block_base_fee,
+            point_in_time: StoragePointInTime::MinedPast(tx.block_number.prev().unwrap_or(BlockNumber::ZERO)),
+            chain_id: tx.input.chain_id,
+            prior_changes,
+            state_override: StateOverride::default(),
+        }
+    }
+
     /// Checks if the input is a contract call.
     ///
     /// It is when there is a `to` address and the `data` field is also populated.
@@ -161,3 +248,16 @@ impl EvmInput {
         self.to.is_some() && not(self.data.is_empty())
     }
 }
+
+/// Selects the gas limit, gas price and priority fee a transaction executes with in a block with the specified base fee.
+///
+/// When the base fee is zero, the fee market is disabled and the transaction executes with max gas and zero gas price.
+fn transaction_fees(input: &TransactionInput, base_fee: Wei) -> (Gas, Wei, Option<Wei>) {
+    if base_fee.is_zero() {
+        return (Gas::MAX, Wei::ZERO, None);
+    }
+    match (input.max_fee_per_gas, input.max_priority_fee_per_gas) {
+        (Some(max_fee), Some(max_priority_fee)) => (input.gas_limit, max_fee, Some(max_priority_fee)),
+        _ => (input.gas_limit, input.gas_price, None),
+    }
+}
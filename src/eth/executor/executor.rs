@@ -1,11 +1,14 @@
 use std::cmp::max;
+use std::cmp::min;
 use std::mem;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::sync::MutexGuard;
 
 use anyhow::anyhow;
 use cfg_if::cfg_if;
+use itertools::Itertools;
 use tracing::info_span;
 use tracing::Span;
 
@@ -15,20 +18,36 @@ use crate::eth::executor::Evm;
 use crate::eth::executor::EvmExecutionResult;
 use crate::eth::executor::EvmInput;
 use crate::eth::executor::ExecutorConfig;
+use crate::eth::executor::QueueLimits;
+use crate::eth::executor::ReplacementPolicy;
+use crate::eth::executor::TransactionPool;
 use crate::eth::miner::Miner;
+use crate::eth::primitives::AccessListWithGasUsed;
+use crate::eth::primitives::Address;
 use crate::eth::primitives::BlockFilter;
 use crate::eth::primitives::BlockNumber;
+use crate::eth::primitives::BlockOverride;
 use crate::eth::primitives::CallInput;
 use crate::eth::primitives::EvmExecution;
 use crate::eth::primitives::EvmExecutionMetrics;
+use crate::eth::primitives::EvmTrace;
+use crate::eth::primitives::ExecutionChanges;
+use crate::eth::primitives::ExecutionResult;
 use crate::eth::primitives::ExternalBlock;
 use crate::eth::primitives::ExternalReceipt;
 use crate::eth::primitives::ExternalReceipts;
 use crate::eth::primitives::ExternalTransaction;
 use crate::eth::primitives::ExternalTransactionExecution;
+use crate::eth::primitives::FeeMarket;
+use crate::eth::primitives::Gas;
+use crate::eth::primitives::Hash;
+use crate::eth::primitives::PendingTransactionEvent;
+use crate::eth::primitives::StateOverride;
 use crate::eth::primitives::StratusError;
+use crate::eth::primitives::TracerOptions;
 use crate::eth::primitives::TransactionExecution;
 use crate::eth::primitives::TransactionInput;
+use crate::eth::primitives::TransactionStage;
 use crate::eth::primitives::UnixTime;
 use crate::eth::storage::StoragePointInTime;
 use crate::eth::storage::StratusStorage;
@@ -41,6 +60,9 @@ use crate::infra::tracing::warn_task_tx_closed;
 use crate::infra::tracing::SpanExt;
 use crate::GlobalState;
 
+/// Max number of attempts of executions that are retried until they do not conflict.
+const INFINITE_ATTEMPTS: usize = usize::MAX;
+
 // -----------------------------------------------------------------------------
 // Evm task
 // -----------------------------------------------------------------------------
@@ -49,7 +71,22 @@ use crate::GlobalState;
 pub struct EvmTask {
     pub span: Span,
     pub input: EvmInput,
-    pub response_tx: oneshot::Sender<Result<EvmExecutionResult, StratusError>>,
+    pub kind: EvmTaskKind,
+}
+
+/// What the EVM should do with the task input and where to send the result.
+#[derive(Debug)]
+pub enum EvmTaskKind {
+    /// Executes the input and returns the execution result.
+    Execute {
+        response_tx: oneshot::Sender<Result<EvmExecutionResult, StratusError>>,
+    },
+
+    /// Executes the input with a tracer and returns the trace.
+    Trace {
+        options: TracerOptions,
+        response_tx: oneshot::Sender<Result<EvmTrace, StratusError>>,
+    },
 }
 
 impl EvmTask {
@@ -57,7 +94,15 @@ impl EvmTask {
         Self {
             span: Span::current(),
             input,
-            response_tx,
+            kind: EvmTaskKind::Execute { response_tx },
+        }
+    }
+
+    pub fn new_trace(input: EvmInput, options: TracerOptions, response_tx: oneshot::Sender<Result<EvmTrace, StratusError>>) -> Self {
+        Self {
+            span: Span::current(),
+            input,
+            kind: EvmTaskKind::Trace { options, response_tx },
         }
     }
 }
@@ -99,9 +144,19 @@ impl Evms {
 
                 // execute
                 let _enter = task.span.enter();
-                let result = evm.execute(task.input);
-                if let Err(e) = task.response_tx.send(result) {
-                    tracing::error!(reason = ?e, "failed to send evm task execution result");
+                match task.kind {
+                    EvmTaskKind::Execute { response_tx } => {
+                        let result = evm.execute(task.input);
+                        if let Err(e) = response_tx.send(result) {
+                            tracing::error!(reason = ?e, "failed to send evm task execution result");
+                        }
+                    }
+                    EvmTaskKind::Trace { options, response_tx } => {
+                        let result = evm.trace(task.input, options);
+                        if let Err(e) = response_tx.send(result) {
+                            tracing::error!(reason = ?e, "failed to send evm task trace result");
+                        }
+                    }
                 }
             }
 
@@ -148,6 +203,29 @@ impl Evms {
         let (execution_tx, execution_rx) = oneshot::channel::<Result<EvmExecutionResult, StratusError>>();
 
         let task = EvmTask::new(evm_input, execution_tx);
+        self.send(task, route);
+
+        match execution_rx.recv() {
+            Ok(result) => result,
+            Err(_) => Err(StratusError::UnexpectedChannelClosed { channel: "evm" }),
+        }
+    }
+
+    /// Executes a transaction with a tracer in the specified route.
+    fn trace(&self, evm_input: EvmInput, options: TracerOptions, route: EvmRoute) -> Result<EvmTrace, StratusError> {
+        let (trace_tx, trace_rx) = oneshot::channel::<Result<EvmTrace, StratusError>>();
+
+        let task = EvmTask::new_trace(evm_input, options, trace_tx);
+        self.send(task, route);
+
+        match trace_rx.recv() {
+            Ok(result) => result,
+            Err(_) => Err(StratusError::UnexpectedChannelClosed { channel: "evm" }),
+        }
+    }
+
+    /// Sends a task to the pool of the specified route.
+    fn send(&self, task: EvmTask, route: EvmRoute) {
         let _ = match route {
             EvmRoute::Parallel => self.tx_parallel.send(task),
             EvmRoute::Serial => self.tx_serial.send(task),
@@ -155,11 +233,6 @@ impl Evms {
             EvmRoute::CallPresent => self.call_present.send(task),
             EvmRoute::CallPast => self.call_past.send(task),
         };
-
-        match execution_rx.recv() {
-            Ok(result) => result,
-            Err(_) => Err(StratusError::UnexpectedChannelClosed { channel: "evm" }),
-        }
     }
 }
 
@@ -185,6 +258,9 @@ pub enum EvmRoute {
 // Executor
 // -----------------------------------------------------------------------------
 
+/// Max relative distance between the bounds of a gas estimation before the search stops.
+const ESTIMATE_GAS_ERROR_RATIO: f64 = 0.015;
+
 /// Locks used for local execution.
 #[derive(Default)]
 pub struct ExecutorLocks {
@@ -206,21 +282,41 @@ pub struct Executor {
 
     /// Shared storage backend for persisting blockchain state.
     storage: Arc<StratusStorage>,
+
+    /// Local transactions waiting for the previous nonces of their senders.
+    txpool: TransactionPool,
 }
 
 impl Executor {
     pub fn new(storage: Arc<StratusStorage>, miner: Arc<Miner>, config: ExecutorConfig) -> Self {
         tracing::info!(?config, "creating executor");
         let evms = Evms::spawn(Arc::clone(&storage), &config);
+        let replacement_policy = match miner.fee_market() {
+            FeeMarket::Eip1559 => ReplacementPolicy::PriceBump(config.executor_txpool_price_bump),
+            FeeMarket::Zero if config.executor_txpool_zero_gas_replacement => ReplacementPolicy::Always,
+            FeeMarket::Zero => ReplacementPolicy::Never,
+        };
+        let queue_limits = QueueLimits {
+            max_queued: config.executor_txpool_max_queued,
+            max_queued_per_sender: config.executor_txpool_max_queued_per_sender,
+            max_nonce_gap: config.executor_txpool_max_nonce_gap,
+        };
+        let txpool = TransactionPool::new(config.executor_txpool_ttl, replacement_policy, queue_limits);
         Self {
             locks: ExecutorLocks::default(),
             config,
             evms,
             miner,
             storage,
+            txpool,
         }
     }
 
+    /// Local transactions waiting for the previous nonces of their senders.
+    pub fn transaction_pool(&self) -> &TransactionPool {
+        &self.txpool
+    }
+
     // -------------------------------------------------------------------------
     // External transactions
     // -------------------------------------------------------------------------
@@ -364,6 +460,102 @@ impl Executor {
     // Local transactions
     // -------------------------------------------------------------------------
 
+    /// Executes a transaction persisting state changes, or queues it if its nonce is ahead of the sender nonce.
+    ///
+    /// Transactions with the same sender and nonce of a queued or pending transaction replace it according to the replacement policy.
+    /// After the execution, queued transactions of the sender that became executable are executed too.
+    pub fn submit_local_transaction(&self, tx: TransactionInput) -> Result<(), StratusError> {
+        let sender = tx.signer;
+        match self.execute_local_transaction(tx.clone()) {
+            Ok(_) => {}
+            Err(StratusError::TransactionNonce { transaction, account }) if transaction > account => {
+                tracing::info!(tx_hash = %tx.hash, %transaction, %account, "queueing local transaction with nonce ahead of sender nonce");
+                let tx_hash = tx.hash;
+                if let Some(replaced) = self.txpool.queue(tx, account)? {
+                    self.notify_dropped_transaction(replaced.hash, Some(tx_hash));
+                }
+            }
+            Err(e @ StratusError::TransactionNonce { .. }) => self.replace_pending_transaction(tx, e)?,
+            Err(e) => return Err(e),
+        }
+        self.promote_queued_transactions(&sender);
+        Ok(())
+    }
+
+    /// Replaces a local transaction of the pending block with a transaction with the same sender and nonce.
+    ///
+    /// Returns the nonce error if there is no pending transaction to replace, because the transaction with the nonce was already mined.
+    fn replace_pending_transaction(&self, tx: TransactionInput, nonce_error: StratusError) -> Result<(), StratusError> {
+        let pending_tx = self
+            .storage
+            .pending_transactions()
+            .into_iter()
+            .filter_map(TransactionExecution::as_local)
+            .find(|pending_tx| pending_tx.input.signer == tx.signer && pending_tx.input.nonce == tx.nonce);
+        let Some(pending_tx) = pending_tx.filter(|pending_tx| pending_tx.input.hash != tx.hash) else {
+            return Err(nonce_error);
+        };
+        self.txpool.replacement_policy().check(&pending_tx.input, &tx)?;
+
+        // hold the serial execution lock until the replacement is executed or the replaced transaction is restored,
+        // so serial executions cannot take its nonce or spend the balance it depends on in the meantime
+        let _serial_locks = self.lock_serial_execution();
+
+        // remove replaced transaction from the pending block
+        let (pending_tx_hash, tx_hash) = (pending_tx.input.hash, tx.hash);
+        tracing::info!(%tx_hash, %pending_tx_hash, "replacing pending local transaction");
+        let Some(removed) = self.storage.remove_execution(&pending_tx_hash)? else {
+            // mined after it was read
+            return Err(nonce_error);
+        };
+
+        // execute replacement, restoring the replaced transaction if the replacement fails
+        match self.execute_local_transaction_attempts(tx, EvmRoute::Serial, INFINITE_ATTEMPTS) {
+            Ok(_) => {
+                self.notify_dropped_transaction(pending_tx_hash, Some(tx_hash));
+                Ok(())
+            }
+            Err(e) => {
+                if let Err(restore_error) = self.storage.save_execution(removed, true) {
+                    tracing::error!(reason = ?restore_error, %pending_tx_hash, "failed to restore pending local transaction after replacement failed");
+                    self.notify_dropped_transaction(pending_tx_hash, None);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Notifies a queued or pending transaction was discarded, replaced by another transaction or not.
+    fn notify_dropped_transaction(&self, hash: Hash, replaced_by: Option<Hash>) {
+        tracing::info!(tx_hash = %hash, replaced_by = ?replaced_by, "dropped local transaction");
+        let _ = self.miner.notifier_pending_txs.send(PendingTransactionEvent::Dropped { hash, replaced_by });
+    }
+
+    /// Executes queued transactions of the sender while there is one with the sender nonce.
+    ///
+    /// The nonce is read again after queueing because the missing transaction may have been executed concurrently.
+    fn promote_queued_transactions(&self, sender: &Address) {
+        loop {
+            let account = match self.storage.read_account(sender, &StoragePointInTime::Pending) {
+                Ok(account) => account,
+                Err(e) => {
+                    tracing::error!(reason = ?e, %sender, "failed to read sender nonce when promoting queued transactions");
+                    return;
+                }
+            };
+            let Some(tx) = self.txpool.take(sender, account.nonce) else {
+                return;
+            };
+
+            let tx_hash = tx.hash;
+            tracing::info!(%tx_hash, %sender, "executing queued local transaction");
+            if let Err(e) = self.execute_local_transaction(tx) {
+                tracing::warn!(reason = ?e, %tx_hash, %sender, "failed to execute queued local transaction");
+                return;
+            }
+        }
+    }
+
     /// Executes a transaction persisting state changes.
     #[tracing::instrument(name = "executor::local_transaction", skip_all, fields(tx_hash, tx_from, tx_to, tx_nonce))]
     pub fn execute_local_transaction(&self, tx: TransactionInput) -> Result<TransactionExecution, StratusError> {
@@ -381,8 +573,6 @@ impl Executor {
         });
 
         // execute according to the strategy
-        const INFINITE_ATTEMPTS: usize = usize::MAX;
-
         let tx_execution = match self.config.executor_strategy {
             // Executes transactions in serial mode:
             // * Uses a Mutex, so a new transactions starts executing only after the previous one is executed and persisted.
@@ -390,18 +580,7 @@ impl Executor {
             // * Conflict detection runs, but it should never trigger because of the Mutex.
             ExecutorStrategy::Serial => {
                 // acquire serial execution lock
-                let _serial_lock = self.locks.serial.lock_or_clear("executor serial lock was poisoned");
-
-                // WORKAROUND: prevents interval miner mining blocks while a transaction is being executed.
-                // this can be removed when we implement conflict detection for block number
-                let _miner_lock = {
-                    if self.miner.mode().is_interval() {
-                        let miner_lock = Some(self.miner.locks.mine_and_commit.lock_or_clear("miner mine_and_commit lock was poisoned"));
-                        miner_lock
-                    } else {
-                        None
-                    }
-                };
+                let _serial_locks = self.lock_serial_execution();
 
                 // execute transaction
                 self.execute_local_transaction_attempts(tx.clone(), EvmRoute::Serial, INFINITE_ATTEMPTS)
@@ -443,6 +622,21 @@ impl Executor {
         tx_execution
     }
 
+    /// Locks the execution of transactions by the serial strategy.
+    fn lock_serial_execution(&self) -> (MutexGuard<'_, ()>, Option<MutexGuard<'_, ()>>) {
+        let serial_lock = self.locks.serial.lock_or_clear("executor serial lock was poisoned");
+
+        // WORKAROUND: prevents interval miner mining blocks while a transaction is being executed.
+        // this can be removed when we implement conflict detection for block number
+        let miner_lock = if self.miner.mode().is_interval() {
+            Some(self.miner.locks.mine_and_commit.lock_or_clear("miner mine_and_commit lock was poisoned"))
+        } else {
+            None
+        };
+
+        (serial_lock, miner_lock)
+    }
+
     /// Executes a transaction until it reaches the max number of attempts.
     fn execute_local_transaction_attempts(
         &self,
@@ -475,8 +669,8 @@ impl Executor {
             });
 
             // prepare evm input
-            let pending_header = self.storage.read_pending_block_header()?.unwrap_or_default();
-            let evm_input = EvmInput::from_eth_transaction(tx_input.clone(), pending_header.number);
+            let (pending_header, pending_base_fee) = self.miner.read_pending_block_header()?;
+            let evm_input = EvmInput::from_eth_transaction(tx_input.clone(), pending_header.number, pending_base_fee);
 
             // execute transaction in evm (retry only in case of conflict, but do not retry on other failures)
             tracing::info!(
@@ -519,8 +713,16 @@ impl Executor {
     }
 
     /// Executes a transaction without persisting state changes.
+    ///
+    /// Accounts and block values can be overridden by the caller without changing the storage.
     #[tracing::instrument(name = "executor::local_call", skip_all, fields(from, to))]
-    pub fn execute_local_call(&self, call_input: CallInput, point_in_time: StoragePointInTime) -> Result<EvmExecution, StratusError> {
+    pub fn execute_local_call(
+        &self,
+        call_input: CallInput,
+        point_in_time: StoragePointInTime,
+        state_override: StateOverride,
+        block_override: BlockOverride,
+    ) -> Result<EvmExecution, StratusError> {
         #[cfg(feature = "metrics")]
         let start = metrics::now();
 
@@ -534,28 +736,12 @@ impl Executor {
             data_len = call_input.data.len(),
             data = %call_input.data,
             %point_in_time,
+            overrides = state_override.len(),
             "executing read-only local transaction"
         );
 
-        // retrieve block info
-        let pending_header = self.storage.read_pending_block_header()?.unwrap_or_default();
-        let mined_block = match point_in_time {
-            StoragePointInTime::MinedPast(number) => {
-                let Some(block) = self.storage.read_block(&BlockFilter::Number(number))? else {
-                    let filter = BlockFilter::Number(number);
-                    return Err(StratusError::RpcBlockFilterInvalid { filter });
-                };
-                Some(block)
-            }
-            _ => None,
-        };
-
         // execute
-        let evm_input = EvmInput::from_eth_call(call_input.clone(), point_in_time, pending_header.number, mined_block)?;
-        let evm_route = match point_in_time {
-            StoragePointInTime::Mined | StoragePointInTime::Pending => EvmRoute::CallPresent,
-            StoragePointInTime::MinedPast(_) => EvmRoute::CallPast,
-        };
+        let (evm_input, evm_route) = self.create_call_input(call_input.clone(), point_in_time, state_override, block_override)?;
         let evm_result = self.evms.execute(evm_input, evm_route);
 
         // track metrics
@@ -578,6 +764,235 @@ impl Executor {
         let execution = evm_result?.execution;
         Ok(execution)
     }
+
+    /// Executes a transaction without persisting state changes and returns the accounts and slots it accessed.
+    #[tracing::instrument(name = "executor::create_access_list", skip_all, fields(from, to))]
+    pub fn create_access_list(&self, call_input: CallInput, point_in_time: StoragePointInTime) -> Result<AccessListWithGasUsed, StratusError> {
+        Span::with(|s| {
+            s.rec_opt("from", &call_input.from);
+            s.rec_opt("to", &call_input.to);
+        });
+        tracing::info!(
+            from = ?call_input.from,
+            to = ?call_input.to,
+            data_len = call_input.data.len(),
+            %point_in_time,
+            "creating access list of local transaction"
+        );
+
+        // execute
+        let (evm_input, evm_route) = self.create_call_input(call_input, point_in_time, StateOverride::default(), BlockOverride::default())?;
+        let (from, to) = (evm_input.from, evm_input.to);
+        let execution = self.evms.execute(evm_input, evm_route)?.execution;
+
+        // sender, recipient and deployed contract are always warm, so they are listed only if their slots were accessed
+        let excluded = [Some(from), to, execution.deployed_contract_address].into_iter().flatten().collect_vec();
+        Ok(AccessListWithGasUsed::new(&execution, &excluded))
+    }
+
+    /// Executes a sequence of transactions without persisting state changes.
+    ///
+    /// Each transaction is executed over the state changes made by the previous ones, so dependent transactions can be simulated together.
+    #[tracing::instrument(name = "executor::simulate_bundle", skip_all, fields(calls))]
+    pub fn simulate_bundle(&self, calls: Vec<CallInput>, point_in_time: StoragePointInTime) -> Result<Vec<EvmExecution>, StratusError> {
+        Span::with(|s| s.rec_str("calls", &calls.len()));
+        tracing::info!(calls = calls.len(), %point_in_time, "simulating bundle of local transactions");
+
+        let mut prior_changes = ExecutionChanges::default();
+        let mut executions = Vec::with_capacity(calls.len());
+        for call_input in calls {
+            // execute over the changes made by previous transactions
+            let (mut evm_input, evm_route) = self.create_call_input(call_input, point_in_time, StateOverride::default(), BlockOverride::default())?;
+            evm_input.prior_changes = prior_changes.clone();
+            let execution = self.evms.execute(evm_input, evm_route)?.execution;
+
+            // chain changes to the next transactions
+            for account_changes in execution.changes.values() {
+                match prior_changes.get_mut(&account_changes.address) {
+                    Some(prior_account_changes) => prior_account_changes.merge(account_changes),
+                    None => {
+                        prior_changes.insert(account_changes.address, account_changes.clone());
+                    }
+                }
+            }
+            executions.push(execution);
+        }
+
+        Ok(executions)
+    }
+
+    /// Estimates the gas required by a transaction by searching for the lowest gas limit it succeeds with.
+    ///
+    /// Fails with the reversion output if the transaction does not succeed even with the max allowed gas.
+    #[tracing::instrument(name = "executor::estimate_gas", skip_all, fields(from, to))]
+    pub fn estimate_gas(&self, call_input: CallInput, point_in_time: StoragePointInTime) -> Result<Gas, StratusError> {
+        Span::with(|s| {
+            s.rec_opt("from", &call_input.from);
+            s.rec_opt("to", &call_input.to);
+        });
+        tracing::info!(
+            from = ?call_input.from,
+            to = ?call_input.to,
+            data_len = call_input.data.len(),
+            gas = ?call_input.gas,
+            %point_in_time,
+            "estimating gas of local transaction"
+        );
+
+        // upper bound is the gas sent by the caller limited by the block gas limit and the configured cap
+        let mut hi = min(call_input.gas.unwrap_or(Gas::BLOCK_LIMIT).as_u64(), Gas::BLOCK_LIMIT.as_u64());
+        if let Some(cap) = self.config.executor_estimate_gas_cap {
+            hi = min(hi, cap);
+        }
+        let (evm_input, evm_route) = self.create_call_input(call_input, point_in_time, StateOverride::default(), BlockOverride::default())?;
+
+        // execute with the upper bound to check if the transaction succeeds at all
+        let execution = self.estimate_gas_execute(&evm_input, evm_route, hi)?;
+        match execution.result {
+            ExecutionResult::Success => {}
+            ExecutionResult::Reverted => return Err(StratusError::TransactionReverted { output: execution.output }),
+            ExecutionResult::Halted { out_of_gas: true, .. } => return Err(StratusError::TransactionGasAllowanceExceeded { gas: hi.into() }),
+            ExecutionResult::Halted { reason, .. } => return Err(StratusError::TransactionHalted { reason }),
+        }
+
+        // search the lowest gas it succeeds with
+        let gas = search_lowest_gas(execution.gas.as_u64(), hi, |gas_limit| {
+            Ok(self.estimate_gas_execute(&evm_input, evm_route, gas_limit)?.is_success())
+        })?;
+        tracing::info!(%gas, "estimated gas of local transaction");
+        Ok(gas.into())
+    }
+
+    /// Executes a gas estimation attempt with the specified gas limit.
+    fn estimate_gas_execute(&self, evm_input: &EvmInput, evm_route: EvmRoute, gas_limit: u64) -> Result<EvmExecution, StratusError> {
+        let mut evm_input = evm_input.clone();
+        evm_input.gas_limit = gas_limit.into();
+        Ok(self.evms.execute(evm_input, evm_route)?.execution)
+    }
+
+    /// Creates the EVM input for a call and selects the pool that should execute it according to the point-in-time.
+    fn create_call_input(
+        &self,
+        call_input: CallInput,
+        point_in_time: StoragePointInTime,
+        state_override: StateOverride,
+        block_override: BlockOverride,
+    ) -> Result<(EvmInput, EvmRoute), StratusError> {
+        // retrieve block info
+        let pending_header = self.storage.read_pending_block_header()?.unwrap_or_default();
+        let mined_block = match point_in_time {
+            StoragePointInTime::MinedPast(number) => {
+                let Some(block) = self.storage.read_block(&BlockFilter::Number(number))? else {
+                    let filter = BlockFilter::Number(number);
+                    return Err(StratusError::RpcBlockFilterInvalid { filter });
+                };
+                Some(block)
+            }
+            _ => None,
+        };
+
+        let evm_input = EvmInput::from_eth_call(call_input, point_in_time, pending_header.number, mined_block, state_override, block_override)?;
+        let evm_route = match point_in_time {
+            StoragePointInTime::Mined | StoragePointInTime::Pending => EvmRoute::CallPresent,
+            StoragePointInTime::MinedPast(_) => EvmRoute::CallPast,
+        };
+        Ok((evm_input, evm_route))
+    }
+
+    // -------------------------------------------------------------------------
+    // Traces
+    // -------------------------------------------------------------------------
+
+    /// Re-executes a mined transaction with a tracer using the same state it was originally executed.
+    #[tracing::instrument(name = "executor::trace_transaction", skip_all, fields(tx_hash, tracer))]
+    pub fn trace_transaction(&self, tx_hash: Hash, options: TracerOptions) -> Result<EvmTrace, StratusError> {
+        Span::with(|s| {
+            s.rec_str("tx_hash", &tx_hash);
+            s.rec_str("tracer", &options.tracer);
+        });
+        tracing::info!(%tx_hash, tracer = %options.tracer, "tracing mined transaction");
+
+        // retrieve transaction and the block it was mined
+        let Some(TransactionStage::Mined(tx)) = self.storage.read_transaction(&tx_hash)? else {
+            return Err(StratusError::RpcTransactionNotFound { hash: tx_hash });
+        };
+        let Some(block) = self.storage.read_block(&BlockFilter::Number(tx.block_number))? else {
+            let filter = BlockFilter::Number(tx.block_number);
+            return Err(StratusError::RpcBlockFilterInvalid { filter });
+        };
+
+        // execute over the state produced by the previous transactions of the same block
+        let prior_changes = block.compact_account_changes_before(tx.transaction_index);
+        let gas_limit = tx.input.gas_limit;
+        let evm_input = EvmInput::from_mined_transaction(tx, block.header.timestamp, block.header.base_fee_per_gas, prior_changes);
+        let mut trace = self.evms.trace(evm_input, options, EvmRoute::CallPast)?;
+
+        // top-level frame reports the gas limit signed in the transaction instead of the one it was re-executed with
+        if let EvmTrace::Call(ref mut root) = trace {
+            root.gas = gas_limit;
+        }
+        Ok(trace)
+    }
+
+    /// Executes a transaction with a tracer without persisting state changes.
+    #[tracing::instrument(name = "executor::trace_local_call", skip_all, fields(from, to, tracer))]
+    pub fn trace_local_call(&self, call_input: CallInput, point_in_time: StoragePointInTime, options: TracerOptions) -> Result<EvmTrace, StratusError> {
+        Span::with(|s| {
+            s.rec_opt("from", &call_input.from);
+            s.rec_opt("to", &call_input.to);
+            s.rec_str("tracer", &options.tracer);
+        });
+        tracing::info!(
+            from = ?call_input.from,
+            to = ?call_input.to,
+            data_len = call_input.data.len(),
+            %point_in_time,
+            tracer = %options.tracer,
+            "tracing read-only local transaction"
+        );
+
+        let (evm_input, evm_route) = self.create_call_input(call_input, point_in_time, StateOverride::default(), BlockOverride::default())?;
+        self.evms.trace(evm_input, options, evm_route)
+    }
+}
+
+/// Searches the lowest gas limit a transaction succeeds with, given it succeeded with the upper bound `hi` consuming `used` gas.
+///
+/// The search stops when the distance between the bounds is small enough, so the result may be slightly above the lowest gas limit.
+fn search_lowest_gas<F>(used: u64, mut hi: u64, mut succeeds: F) -> Result<u64, StratusError>
+where
+    F: FnMut(u64) -> Result<bool, StratusError>,
+{
+    // a transaction never succeeds with less gas than it used, but may need more because of refunds and the 63/64 rule
+    if used >= hi {
+        return Ok(hi);
+    }
+    if succeeds(used)? {
+        return Ok(used);
+    }
+    let mut lo = used;
+
+    // optimistically try the used gas plus what the 63/64 rule retains from nested calls, which is usually enough
+    let optimistic = min(used.saturating_mul(64) / 63, hi);
+    if optimistic < hi {
+        if succeeds(optimistic)? {
+            hi = optimistic;
+        } else {
+            lo = optimistic;
+        }
+    }
+
+    // binary search until the distance between bounds is small enough
+    while lo + 1 < hi && (hi - lo) as f64 / hi as f64 > ESTIMATE_GAS_ERROR_RATIO {
+        let mid = lo + (hi - lo) / 2;
+        if succeeds(mid)? {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+
+    Ok(hi)
 }
 
 #[derive(Clone, Copy, serde::Serialize)]
@@ -600,3 +1015,59 @@ impl FromStr for ExecutorStrategy {
         }
     }
 }
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Searches with a transaction that succeeds only with at least `required` gas, returning the result and the attempted gas limits.
+    fn search(used: u64, hi: u64, required: u64) -> (u64, Vec<u64>) {
+        let mut attempts = vec![];
+        let gas = search_lowest_gas(used, hi, |gas_limit| {
+            attempts.push(gas_limit);
+            Ok(gas_limit >= required)
+        })
+        .unwrap();
+        (gas, attempts)
+    }
+
+    #[test]
+    fn search_lowest_gas_returns_used_gas_when_enough() {
+        assert_eq!(search(21_000, 30_000_000, 21_000), (21_000, vec![21_000]));
+    }
+
+    #[test]
+    fn search_lowest_gas_returns_upper_bound_when_all_gas_was_used() {
+        assert_eq!(search(30_000_000, 30_000_000, 30_000_000), (30_000_000, vec![]));
+    }
+
+    #[test]
+    fn search_lowest_gas_tries_optimistic_guess_before_binary_search() {
+        let (gas, attempts) = search(63_000, 30_000_000, 63_990);
+        assert_eq!(gas, 64_000);
+        assert_eq!(attempts, vec![63_000, 64_000, 63_500]);
+    }
+
+    #[test]
+    fn search_lowest_gas_converges_above_required_gas() {
+        let (used, hi, required) = (100_000, 30_000_000, 2_000_000);
+        let (gas, attempts) = search(used, hi, required);
+
+        assert!(gas >= required);
+        assert!((gas - required) as f64 / gas as f64 <= ESTIMATE_GAS_ERROR_RATIO);
+
+        // the used gas already failed, so it is the lower bound and is never attempted again
+        assert_eq!(attempts.iter().filter(|gas_limit| **gas_limit == used).count(), 1);
+        assert!(attempts.iter().all(|gas_limit| *gas_limit >= used && *gas_limit < hi));
+    }
+
+    #[test]
+    fn search_lowest_gas_propagates_errors() {
+        let result = search_lowest_gas(21_000, 30_000_000, |_| Err(StratusError::TransactionFromZeroAddress));
+        assert!(matches!(result, Err(StratusError::TransactionFromZeroAddress)));
+    }
+}
@@ -1,5 +1,6 @@
 use std::cmp::max;
 use std::sync::Arc;
+use std::time::Duration;
 
 use clap::Parser;
 use display_json::DebugAsJson;
@@ -7,7 +8,9 @@ use display_json::DebugAsJson;
 use crate::eth::executor::Executor;
 use crate::eth::executor::ExecutorStrategy;
 use crate::eth::miner::Miner;
+use crate::eth::primitives::HardforkSchedule;
 use crate::eth::storage::StratusStorage;
+use crate::ext::parse_duration;
 
 #[derive(Parser, DebugAsJson, Clone, serde::Serialize)]
 pub struct ExecutorConfig {
@@ -33,6 +36,81 @@ pub struct ExecutorConfig {
         default_value = "true"
     )]
     pub executor_reject_not_contract: bool,
+
+    /// Hardforks activated at each block, like `london:0,shanghai:1000,cancun:2000`.
+    ///
+    /// Blocks before the first activation are executed with London rules. External blocks are re-executed with the hardfork
+    /// active at their block number, so followers and importers must use the same schedule the blocks were produced with.
+    #[arg(long = "executor-hardforks", alias = "hardforks", env = "EXECUTOR_HARDFORKS", default_value = "london:0")]
+    pub executor_hardforks: HardforkSchedule,
+
+    /// Max gas `eth_estimateGas` searches for. When not set, estimations are limited by the block gas limit.
+    #[arg(long = "executor-estimate-gas-cap", alias = "estimate-gas-cap", env = "EXECUTOR_ESTIMATE_GAS_CAP")]
+    pub executor_estimate_gas_cap: Option<u64>,
+
+    /// Should record calls, contract creations and self-destructs executed by contracts in transactions?
+    ///
+    /// Recorded operations are persisted with the mined transactions, but executing with them enabled is slower.
+    #[arg(
+        long = "executor-internal-transactions",
+        alias = "internal-transactions",
+        env = "EXECUTOR_INTERNAL_TRANSACTIONS",
+        default_value = "false"
+    )]
+    pub executor_internal_transactions: bool,
+
+    /// Time a transaction with a nonce ahead of the sender nonce stays queued waiting for the previous transactions of the sender.
+    #[arg(long = "executor-txpool-ttl", alias = "txpool-ttl", value_parser=parse_duration, env = "EXECUTOR_TXPOOL_TTL", default_value = "5m")]
+    pub executor_txpool_ttl: Duration,
+
+    /// Max number of transactions queued waiting for the previous transactions of their senders.
+    #[arg(
+        long = "executor-txpool-max-queued",
+        alias = "txpool-max-queued",
+        env = "EXECUTOR_TXPOOL_MAX_QUEUED",
+        default_value = "1024"
+    )]
+    pub executor_txpool_max_queued: usize,
+
+    /// Max number of transactions of a single sender queued waiting for its previous transactions.
+    #[arg(
+        long = "executor-txpool-max-queued-per-sender",
+        alias = "txpool-max-queued-per-sender",
+        env = "EXECUTOR_TXPOOL_MAX_QUEUED_PER_SENDER",
+        default_value = "64"
+    )]
+    pub executor_txpool_max_queued_per_sender: usize,
+
+    /// Max number of nonces a transaction can be ahead of the sender nonce to be queued.
+    #[arg(
+        long = "executor-txpool-max-nonce-gap",
+        alias = "txpool-max-nonce-gap",
+        env = "EXECUTOR_TXPOOL_MAX_NONCE_GAP",
+        default_value = "64"
+    )]
+    pub executor_txpool_max_nonce_gap: u64,
+
+    /// Minimum percentage the fees of a transaction must be increased by to replace a queued or pending transaction with the same sender and nonce.
+    ///
+    /// Only used when the fee market is enabled.
+    #[arg(
+        long = "executor-txpool-price-bump",
+        alias = "txpool-price-bump",
+        env = "EXECUTOR_TXPOOL_PRICE_BUMP",
+        default_value = "10"
+    )]
+    pub executor_txpool_price_bump: u64,
+
+    /// Should transactions replace queued or pending transactions with the same sender and nonce when the fee market is disabled?
+    ///
+    /// Without fee market all transactions have zero gas price, so replacements cannot be priced and must be explicitly enabled.
+    #[arg(
+        long = "executor-txpool-zero-gas-replacement",
+        alias = "txpool-zero-gas-replacement",
+        env = "EXECUTOR_TXPOOL_ZERO_GAS_REPLACEMENT",
+        default_value = "false"
+    )]
+    pub executor_txpool_zero_gas_replacement: bool,
 }
 
 impl ExecutorConfig {
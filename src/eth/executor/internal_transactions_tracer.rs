@@ -0,0 +1,207 @@
+use revm::interpreter::CallInputs;
+use revm::interpreter::CallOutcome;
+use revm::interpreter::CallScheme;
+use revm::interpreter::CreateInputs;
+use revm::interpreter::CreateOutcome;
+use revm::interpreter::InterpreterResult;
+use revm::primitives::CreateScheme;
+use revm::Database;
+use revm::EvmContext;
+use revm::Inspector;
+
+use crate::alias::RevmAddress;
+use crate::alias::RevmU256;
+use crate::eth::primitives::CallKind;
+use crate::eth::primitives::InternalTransaction;
+use crate::ext::not;
+use crate::if_else;
+
+/// Inspector that records the calls, contract creations and self-destructs executed by contracts.
+///
+/// Operations of reverted call frames are discarded together with the frame.
+#[derive(Default)]
+pub struct InternalTransactionsTracer {
+    /// Number of operations recorded when each call frame that did not finish yet started executing.
+    stack: Vec<usize>,
+
+    /// Operations recorded during the execution.
+    transactions: Vec<InternalTransaction>,
+}
+
+impl InternalTransactionsTracer {
+    /// Takes the operations recorded during the execution.
+    pub fn take_transactions(&mut self) -> Vec<InternalTransaction> {
+        self.stack.clear();
+        std::mem::take(&mut self.transactions)
+    }
+
+    /// Enters a call frame, recording it unless it is the top-level frame executed by the transaction itself.
+    fn enter(&mut self, kind: CallKind, from: RevmAddress, to: RevmAddress, value: RevmU256, input: &[u8]) {
+        let depth = self.stack.len();
+        self.stack.push(self.transactions.len());
+        if depth > 0 {
+            self.transactions
+                .push(InternalTransaction::new(kind, from.into(), to.into(), value.into(), input, depth));
+        }
+    }
+
+    fn exit(&mut self, result: &InterpreterResult, created_address: Option<RevmAddress>) {
+        let Some(start) = self.stack.pop() else {
+            tracing::error!("internal transactions tracer exited a call frame that was never entered");
+            return;
+        };
+
+        // discard the frame and everything executed inside it
+        if not(result.is_ok()) {
+            self.transactions.truncate(start);
+            return;
+        }
+
+        // contract addresses are known only after the creation finishes (the top-level frame is not recorded)
+        if let Some(created_address) = created_address {
+            if not(self.stack.is_empty()) {
+                self.transactions[start].to = created_address.into();
+            }
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for InternalTransactionsTracer {
+    fn call(&mut self, _: &mut EvmContext<DB>, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        let kind = match inputs.scheme {
+            CallScheme::Call => CallKind::Call,
+            CallScheme::CallCode => CallKind::CallCode,
+            CallScheme::DelegateCall => CallKind::DelegateCall,
+            CallScheme::StaticCall => CallKind::StaticCall,
+        };
+
+        // delegated calls execute the code of another contract without leaving the current one
+        let to = if_else!(
+            matches!(kind, CallKind::DelegateCall | CallKind::CallCode),
+            inputs.bytecode_address,
+            inputs.target_address
+        );
+        let value = inputs.transfer_value().unwrap_or_default();
+
+        self.enter(kind, inputs.caller, to, value, &inputs.input);
+        None
+    }
+
+    fn call_end(&mut self, _: &mut EvmContext<DB>, _: &CallInputs, outcome: CallOutcome) -> CallOutcome {
+        self.exit(&outcome.result, None);
+        outcome
+    }
+
+    fn create(&mut self, _: &mut EvmContext<DB>, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        let kind = match inputs.scheme {
+            CreateScheme::Create => CallKind::Create,
+            CreateScheme::Create2 { .. } => CallKind::Create2,
+        };
+
+        self.enter(kind, inputs.caller, RevmAddress::ZERO, inputs.value, &[]);
+        None
+    }
+
+    fn create_end(&mut self, _: &mut EvmContext<DB>, _: &CreateInputs, outcome: CreateOutcome) -> CreateOutcome {
+        self.exit(&outcome.result, outcome.address);
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: RevmAddress, target: RevmAddress, value: RevmU256) {
+        let depth = self.stack.len();
+        self.transactions.push(InternalTransaction::new(
+            CallKind::SelfDestruct,
+            contract.into(),
+            target.into(),
+            value.into(),
+            &[],
+            depth,
+        ));
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use revm::db::InMemoryDB;
+    use revm::inspector_handle_register;
+    use revm::primitives::TransactTo;
+    use revm::Evm;
+
+    use super::*;
+    use crate::alias::RevmAccountInfo;
+    use crate::alias::RevmBytecode;
+    use crate::eth::primitives::Wei;
+
+    const STOP: [u8; 1] = [0x00];
+    const REVERT: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xfd];
+    const RETURN_EMPTY: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xf3];
+
+    /// Assembles a CALL without value and input to the contract, discarding its result.
+    fn call(to: RevmAddress) -> Vec<u8> {
+        let mut code = vec![0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x73];
+        code.extend_from_slice(to.as_slice());
+        code.extend_from_slice(&[0x5a, 0xf1, 0x50]); // GAS CALL POP
+        code
+    }
+
+    /// Assembles a CREATE2 without value and with salt zero of the init code, discarding its result.
+    fn create2(init_code: [u8; 5]) -> Vec<u8> {
+        let mut code = vec![0x64];
+        code.extend_from_slice(&init_code);
+        code.extend_from_slice(&[0x60, 0x00, 0x52]); // MSTORE the init code at the end of the first word
+        code.extend_from_slice(&[0x60, 0x00, 0x60, 0x05, 0x60, 0x1b, 0x60, 0x00, 0xf5, 0x50]); // CREATE2(value, offset, size, salt) POP
+        code
+    }
+
+    #[test]
+    fn tracer_discards_operations_of_reverted_frames() {
+        let [a, b, c, d, e] = [0x0a, 0x0b, 0x0c, 0x0d, 0x0e].map(RevmAddress::with_last_byte);
+        let contracts = [
+            // succeeds calling B, calling D and creating a contract
+            (a, [call(b), call(d), create2(RETURN_EMPTY)].concat()),
+            // succeeds after a CALL and a CREATE2 that revert
+            (b, [call(c), create2(REVERT), STOP.to_vec()].concat()),
+            (c, REVERT.to_vec()),
+            // reverts after a CALL that succeeds
+            (d, [call(e), REVERT.to_vec()].concat()),
+            (e, STOP.to_vec()),
+        ];
+
+        let mut db = InMemoryDB::default();
+        for (address, code) in contracts {
+            let bytecode = RevmBytecode::new_raw(code.into());
+            let info = RevmAccountInfo {
+                code_hash: bytecode.hash_slow(),
+                code: Some(bytecode),
+                ..Default::default()
+            };
+            db.insert_account_info(address, info);
+        }
+
+        let mut evm = Evm::builder()
+            .with_db(db)
+            .with_external_context(InternalTransactionsTracer::default())
+            .append_handler_register(inspector_handle_register)
+            .modify_tx_env(|tx| {
+                tx.caller = RevmAddress::with_last_byte(0x01);
+                tx.transact_to = TransactTo::Call(a);
+                tx.gas_limit = 1_000_000;
+            })
+            .build();
+        let result = evm.transact().unwrap();
+        assert!(result.result.is_success());
+
+        let created = a.create2_from_code([0u8; 32], RETURN_EMPTY);
+        assert_eq!(
+            evm.context.external.take_transactions(),
+            vec![
+                InternalTransaction::new(CallKind::Call, a.into(), b.into(), Wei::ZERO, &[], 1),
+                InternalTransaction::new(CallKind::Create2, a.into(), created.into(), Wei::ZERO, &[], 1),
+            ]
+        );
+    }
+}
@@ -1,13 +1,21 @@
+mod call_tracer;
 mod evm;
 mod evm_input;
 mod evm_result;
 #[allow(clippy::module_inception)]
 mod executor;
 mod executor_config;
+mod internal_transactions_tracer;
+mod transaction_pool;
 
+pub use call_tracer::CallTracer;
 pub use evm::Evm;
 pub use evm_input::EvmInput;
 pub use evm_result::EvmExecutionResult;
 pub use executor::Executor;
 pub use executor::ExecutorStrategy;
 pub use executor_config::ExecutorConfig;
+pub use internal_transactions_tracer::InternalTransactionsTracer;
+pub use transaction_pool::QueueLimits;
+pub use transaction_pool::ReplacementPolicy;
+pub use transaction_pool::TransactionPool;
@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::time::Duration;
+use std::time::Instant;
+
+use ethereum_types::U256;
+
+use crate::eth::primitives::Address;
+use crate::eth::primitives::Nonce;
+use crate::eth::primitives::StratusError;
+use crate::eth::primitives::TransactionInput;
+use crate::eth::primitives::Wei;
+use crate::ext::not;
+use crate::ext::MutexExt;
+
+/// Local transactions received with a nonce ahead of the sender nonce.
+///
+/// Transactions stay queued until the transactions with the previous nonces of the sender are executed, or until they expire.
+pub struct TransactionPool {
+    /// Time a transaction can stay queued before being discarded.
+    ttl: Duration,
+
+    /// Rules for replacing queued and pending transactions.
+    replacement_policy: ReplacementPolicy,
+
+    /// Limits of the queued transactions.
+    limits: QueueLimits,
+
+    /// Queued transactions by sender and nonce.
+    queued: Mutex<HashMap<Address, BTreeMap<Nonce, QueuedTransaction>>>,
+}
+
+struct QueuedTransaction {
+    tx: TransactionInput,
+    queued_at: Instant,
+}
+
+/// Limits of the queued transactions, so senders cannot grow the pool without bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueLimits {
+    /// Max number of queued transactions of all senders.
+    pub max_queued: usize,
+
+    /// Max number of queued transactions of a single sender.
+    pub max_queued_per_sender: usize,
+
+    /// Max number of nonces a queued transaction can be ahead of the sender nonce.
+    pub max_nonce_gap: u64,
+}
+
+/// Rules for replacing a queued or pending transaction with another transaction with the same sender and nonce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplacementPolicy {
+    /// Replacement must increase the max fee and the max priority fee by at least the percentage.
+    PriceBump(u64),
+
+    /// Replacement is always accepted.
+    ///
+    /// Used when gas is free, because fees cannot be increased to signal which transaction should be kept.
+    Always,
+
+    /// Replacement is always rejected.
+    Never,
+}
+
+impl ReplacementPolicy {
+    /// Checks if the replacement can replace the current transaction.
+    pub fn check(&self, current: &TransactionInput, replacement: &TransactionInput) -> Result<(), StratusError> {
+        match self {
+            Self::PriceBump(price_bump) => {
+                // legacy transactions pay their gas price as both the max fee and the max priority fee
+                let max_fees = |tx: &TransactionInput| (tx.max_fee_per_gas.unwrap_or(tx.gas_price), tx.max_priority_fee_per_gas.unwrap_or(tx.gas_price));
+                let bumped = |fee: Wei| Wei(fee.0.saturating_mul(U256::from(100 + price_bump)) / 100);
+                let (current_max_fee, current_priority_fee) = max_fees(current);
+                let (replacement_max_fee, replacement_priority_fee) = max_fees(replacement);
+                if replacement_max_fee < bumped(current_max_fee) || replacement_priority_fee < bumped(current_priority_fee) {
+                    return Err(StratusError::TransactionReplacementUnderpriced {
+                        hash: current.hash,
+                        price_bump: *price_bump,
+                    });
+                }
+                Ok(())
+            }
+            Self::Always => Ok(()),
+            Self::Never => Err(StratusError::TransactionReplacementNotAllowed { hash: current.hash }),
+        }
+    }
+}
+
+impl TransactionPool {
+    pub fn new(ttl: Duration, replacement_policy: ReplacementPolicy, limits: QueueLimits) -> Self {
+        Self {
+            ttl,
+            replacement_policy,
+            limits,
+            queued: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Rules for replacing queued and pending transactions.
+    pub fn replacement_policy(&self) -> ReplacementPolicy {
+        self.replacement_policy
+    }
+
+    /// Queues a transaction until its sender reaches its nonce.
+    ///
+    /// If there is a queued transaction with the same sender and nonce, it is replaced according to the replacement policy and returned.
+    /// Otherwise the transaction is rejected if it exceeds one of the queue limits.
+    pub fn queue(&self, tx: TransactionInput, sender_nonce: Nonce) -> Result<Option<TransactionInput>, StratusError> {
+        let max_gap = self.limits.max_nonce_gap;
+        if tx.nonce.as_u64().saturating_sub(sender_nonce.as_u64()) > max_gap {
+            return Err(StratusError::TransactionNonceGapExceeded {
+                transaction: tx.nonce,
+                account: sender_nonce,
+                max_gap,
+            });
+        }
+
+        let mut queued = self.lock();
+        let queued_len: usize = queued.values().map(BTreeMap::len).sum();
+        let sender_txs = queued.entry(tx.signer).or_default();
+        match sender_txs.get(&tx.nonce) {
+            Some(current) if current.tx.hash == tx.hash => return Ok(None),
+            Some(current) => self.replacement_policy.check(&current.tx, &tx)?,
+            None if sender_txs.len() >= self.limits.max_queued_per_sender =>
+                return Err(StratusError::TransactionPoolSenderLimit {
+                    address: tx.signer,
+                    max: self.limits.max_queued_per_sender,
+                }),
+            None if queued_len >= self.limits.max_queued => return Err(StratusError::TransactionPoolLimit { max: self.limits.max_queued }),
+            None => {}
+        }
+        let replaced = sender_txs.insert(tx.nonce, QueuedTransaction { tx, queued_at: Instant::now() });
+        Ok(replaced.map(|queued_tx| queued_tx.tx))
+    }
+
+    /// Removes the queued transaction of the sender with the nonce, if there is one that did not expire.
+    pub fn take(&self, sender: &Address, nonce: Nonce) -> Option<TransactionInput> {
+        let mut queued = self.lock();
+        let sender_txs = queued.get_mut(sender)?;
+        let tx = sender_txs.remove(&nonce).map(|queued_tx| queued_tx.tx);
+        if sender_txs.is_empty() {
+            queued.remove(sender);
+        }
+        tx
+    }
+
+    /// Returns all queued transactions ordered by nonce for each sender.
+    pub fn queued(&self) -> Vec<TransactionInput> {
+        let queued = self.lock();
+        queued
+            .values()
+            .flat_map(|sender_txs| sender_txs.values().map(|queued_tx| queued_tx.tx.clone()))
+            .collect()
+    }
+
+    /// Returns the number of queued transactions.
+    pub fn len(&self) -> usize {
+        self.lock().values().map(BTreeMap::len).sum()
+    }
+
+    /// Checks if there are no queued transactions.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Locks the queued transactions, discarding the expired ones.
+    fn lock(&self) -> MutexGuard<'_, HashMap<Address, BTreeMap<Nonce, QueuedTransaction>>> {
+        let mut queued = self.queued.lock_or_clear("transaction pool lock was poisoned");
+        queued.retain(|_, sender_txs| {
+            sender_txs.retain(|_, queued_tx| {
+                let expired = queued_tx.queued_at.elapsed() >= self.ttl;
+                if expired {
+                    tracing::info!(tx_hash = %queued_tx.tx.hash, "discarding expired queued transaction");
+                }
+                not(expired)
+            });
+            not(sender_txs.is_empty())
+        });
+        queued
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use fake::Fake;
+    use fake::Faker;
+
+    use super::*;
+
+    const LIMITS: QueueLimits = QueueLimits {
+        max_queued: 1024,
+        max_queued_per_sender: 64,
+        max_nonce_gap: 64,
+    };
+
+    fn tx(sender: Address, nonce: u64) -> TransactionInput {
+        TransactionInput {
+            signer: sender,
+            nonce: nonce.into(),
+            ..Faker.fake()
+        }
+    }
+
+    #[test]
+    fn transaction_pool_takes_queued_transactions_by_sender_and_nonce() {
+        let pool = TransactionPool::new(Duration::from_secs(60), ReplacementPolicy::Never, LIMITS);
+        let sender: Address = Faker.fake();
+        pool.queue(tx(sender, 2), Nonce::ZERO).unwrap();
+        pool.queue(tx(sender, 3), Nonce::ZERO).unwrap();
+        assert_eq!(pool.len(), 2);
+
+        assert!(pool.take(&sender, 1.into()).is_none());
+        assert!(pool.take(&Faker.fake(), 2.into()).is_none());
+        assert_eq!(pool.take(&sender, 2.into()).unwrap().nonce, 2.into());
+        assert_eq!(pool.queued().len(), 1);
+    }
+
+    #[test]
+    fn transaction_pool_discards_expired_transactions() {
+        let pool = TransactionPool::new(Duration::ZERO, ReplacementPolicy::Never, LIMITS);
+        let sender: Address = Faker.fake();
+        pool.queue(tx(sender, 1), Nonce::ZERO).unwrap();
+        assert!(pool.is_empty());
+        assert!(pool.take(&sender, 1.into()).is_none());
+    }
+
+    #[test]
+    fn transaction_pool_replaces_queued_transactions_according_to_policy() {
+        let sender: Address = Faker.fake();
+        let priced_tx = |gas_price: u64| TransactionInput {
+            gas_price: gas_price.into(),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            ..tx(sender, 1)
+        };
+
+        let pool = TransactionPool::new(Duration::from_secs(60), ReplacementPolicy::PriceBump(10), LIMITS);
+        let current = priced_tx(100);
+        pool.queue(current.clone(), Nonce::ZERO).unwrap();
+        assert!(matches!(
+            pool.queue(priced_tx(109), Nonce::ZERO),
+            Err(StratusError::TransactionReplacementUnderpriced { price_bump: 10, .. })
+        ));
+        assert_eq!(pool.queue(priced_tx(110), Nonce::ZERO).unwrap().unwrap().hash, current.hash);
+
+        let pool = TransactionPool::new(Duration::from_secs(60), ReplacementPolicy::Never, LIMITS);
+        pool.queue(priced_tx(0), Nonce::ZERO).unwrap();
+        assert!(matches!(
+            pool.queue(priced_tx(0), Nonce::ZERO),
+            Err(StratusError::TransactionReplacementNotAllowed { .. })
+        ));
+
+        let pool = TransactionPool::new(Duration::from_secs(60), ReplacementPolicy::Always, LIMITS);
+        pool.queue(priced_tx(0), Nonce::ZERO).unwrap();
+        assert!(pool.queue(priced_tx(0), Nonce::ZERO).unwrap().is_some());
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn transaction_pool_rejects_transactions_beyond_limits() {
+        let limits = QueueLimits {
+            max_queued: 3,
+            max_queued_per_sender: 2,
+            max_nonce_gap: 5,
+        };
+        let pool = TransactionPool::new(Duration::from_secs(60), ReplacementPolicy::Always, limits);
+        let (alice, bob): (Address, Address) = (Faker.fake(), Faker.fake());
+
+        assert!(matches!(
+            pool.queue(tx(alice, 6), Nonce::ZERO),
+            Err(StratusError::TransactionNonceGapExceeded { max_gap: 5, .. })
+        ));
+        pool.queue(tx(alice, 6), 1.into()).unwrap();
+        pool.queue(tx(alice, 2), Nonce::ZERO).unwrap();
+        assert!(matches!(
+            pool.queue(tx(alice, 3), Nonce::ZERO),
+            Err(StratusError::TransactionPoolSenderLimit { max: 2, .. })
+        ));
+        assert!(pool.queue(tx(alice, 2), Nonce::ZERO).unwrap().is_some());
+
+        pool.queue(tx(bob, 1), Nonce::ZERO).unwrap();
+        assert!(matches!(
+            pool.queue(tx(bob, 2), Nonce::ZERO),
+            Err(StratusError::TransactionPoolLimit { max: 3 })
+        ));
+        assert_eq!(pool.len(), 3);
+    }
+}
@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 
+use crate::eth::primitives::BlockNumber;
 use crate::eth::primitives::Bytes;
 use crate::eth::primitives::Hash;
 use crate::eth::primitives::StratusError;
@@ -50,4 +51,7 @@ pub trait Consensus: Send + Sync {
 
     /// Get the lag between this node and the leader.
     async fn lag(&self) -> anyhow::Result<u64>;
+
+    /// Get the block number the import started from and the current block number of the leader.
+    fn import_progress(&self) -> (BlockNumber, BlockNumber);
 }
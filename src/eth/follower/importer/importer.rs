@@ -47,6 +47,9 @@ use crate::GlobalState;
 /// Current block number of the external RPC blockchain.
 static EXTERNAL_RPC_CURRENT_BLOCK: AtomicU64 = AtomicU64::new(0);
 
+/// Block number the importer started importing from.
+static IMPORTER_STARTING_BLOCK: AtomicU64 = AtomicU64::new(0);
+
 /// Only sets the external RPC current block number if it is equals or greater than the current one.
 fn set_external_rpc_current_block(new_number: BlockNumber) {
     let new_number_u64 = new_number.as_u64();
@@ -112,6 +115,7 @@ impl Importer {
 
         let storage = &self.storage;
         let number = storage.read_block_number_to_resume_import()?;
+        IMPORTER_STARTING_BLOCK.store(number.as_u64(), Ordering::Relaxed);
 
         let (backlog_tx, backlog_rx) = mpsc::unbounded_channel();
 
@@ -460,6 +464,12 @@ impl Consensus for Importer {
         Ok(EXTERNAL_RPC_CURRENT_BLOCK.load(Ordering::SeqCst) - self.storage.read_mined_block_number()?.as_u64())
     }
 
+    fn import_progress(&self) -> (BlockNumber, BlockNumber) {
+        let starting_block = IMPORTER_STARTING_BLOCK.load(Ordering::Relaxed);
+        let highest_block = EXTERNAL_RPC_CURRENT_BLOCK.load(Ordering::SeqCst);
+        (starting_block.into(), highest_block.into())
+    }
+
     fn get_chain(&self) -> anyhow::Result<&Arc<BlockchainClient>> {
         Ok(&self.chain)
     }
@@ -4,6 +4,8 @@ use std::sync::mpsc;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::RwLock;
+use std::sync::RwLockReadGuard;
+use std::sync::RwLockWriteGuard;
 use std::time::Duration;
 
 use anyhow::anyhow;
@@ -17,19 +19,27 @@ use tracing::Span;
 
 use crate::eth::miner::MinerMode;
 use crate::eth::primitives::Block;
+use crate::eth::primitives::BlockFilter;
 use crate::eth::primitives::BlockHeader;
 use crate::eth::primitives::BlockNumber;
 use crate::eth::primitives::ExternalBlock;
 use crate::eth::primitives::ExternalTransactionExecution;
+use crate::eth::primitives::FeeMarket;
+use crate::eth::primitives::Gas;
 use crate::eth::primitives::Hash;
 use crate::eth::primitives::Index;
 use crate::eth::primitives::LocalTransactionExecution;
 use crate::eth::primitives::LogMined;
+use crate::eth::primitives::LogsBloom;
+use crate::eth::primitives::PendingBlock;
+use crate::eth::primitives::PendingBlockHeader;
+use crate::eth::primitives::PendingTransactionEvent;
 use crate::eth::primitives::Size;
 use crate::eth::primitives::StratusError;
 use crate::eth::primitives::TransactionExecution;
 use crate::eth::primitives::TransactionMined;
 use crate::eth::primitives::UnixTime;
+use crate::eth::primitives::Wei;
 use crate::eth::storage::StratusStorage;
 use crate::ext::not;
 use crate::ext::DisplayExt;
@@ -57,8 +67,14 @@ pub struct Miner {
     /// Mode the block miner is running.
     mode: RwLock<MinerMode>,
 
+    /// How the gas consumed by transactions is priced.
+    fee_market: FeeMarket,
+
+    /// Base fee of the pending block.
+    pending_base_fee: RwLock<Wei>,
+
     /// Broadcasts pending transactions events.
-    pub notifier_pending_txs: broadcast::Sender<Hash>,
+    pub notifier_pending_txs: broadcast::Sender<PendingTransactionEvent>,
 
     /// Broadcasts new mined blocks events.
     pub notifier_blocks: broadcast::Sender<BlockHeader>,
@@ -86,13 +102,15 @@ pub struct MinerLocks {
 }
 
 impl Miner {
-    pub fn new(storage: Arc<StratusStorage>, mode: MinerMode) -> Self {
-        tracing::info!(?mode, "creating block miner");
+    pub fn new(storage: Arc<StratusStorage>, mode: MinerMode, fee_market: FeeMarket) -> Self {
+        tracing::info!(?mode, ?fee_market, "creating block miner");
         Self {
             locks: MinerLocks::default(),
             storage,
             is_paused: AtomicBool::new(false),
             mode: mode.into(),
+            fee_market,
+            pending_base_fee: RwLock::new(Wei::ZERO),
             notifier_pending_txs: broadcast::channel(u16::MAX as usize).0,
             notifier_blocks: broadcast::channel(u16::MAX as usize).0,
             notifier_logs: broadcast::channel(u16::MAX as usize).0,
@@ -173,6 +191,53 @@ impl Miner {
         }) = new_mode;
     }
 
+    pub fn fee_market(&self) -> FeeMarket {
+        self.fee_market
+    }
+
+    /// Base fee that will be charged from transactions included in the pending block.
+    pub fn pending_base_fee(&self) -> Wei {
+        *self.read_pending_base_fee()
+    }
+
+    /// Header and base fee of the pending block.
+    ///
+    /// Both are read while the base fee is locked, so a transaction never executes in a pending block with the base fee of another block.
+    pub fn read_pending_block_header(&self) -> Result<(PendingBlockHeader, Wei), StratusError> {
+        let base_fee = self.read_pending_base_fee();
+        let header = self.storage.read_pending_block_header()?.unwrap_or_default();
+        Ok((header, *base_fee))
+    }
+
+    fn read_pending_base_fee(&self) -> RwLockReadGuard<'_, Wei> {
+        self.pending_base_fee.read().unwrap_or_else(|poison_error| {
+            tracing::error!("miner pending base fee read lock was poisoned");
+            self.pending_base_fee.clear_poison();
+            poison_error.into_inner()
+        })
+    }
+
+    fn write_pending_base_fee(&self) -> RwLockWriteGuard<'_, Wei> {
+        self.pending_base_fee.write().unwrap_or_else(|poison_error| {
+            tracing::error!("miner pending base fee write lock was poisoned");
+            self.pending_base_fee.clear_poison();
+            poison_error.into_inner()
+        })
+    }
+
+    fn set_pending_base_fee(&self, base_fee: Wei) {
+        *self.write_pending_base_fee() = base_fee;
+    }
+
+    /// Recalculates the base fee of the pending block from the last mined block.
+    pub fn reset_pending_base_fee(&self) -> anyhow::Result<()> {
+        let last_block = self.storage.read_block(&BlockFilter::Latest)?;
+        let base_fee = self.fee_market.next_base_fee(last_block.as_ref().map(|block| &block.header));
+        tracing::info!(%base_fee, "setting pending block base fee");
+        self.set_pending_base_fee(base_fee);
+        Ok(())
+    }
+
     pub fn is_interval_miner_running(&self) -> bool {
         match self.interval_joinset.try_lock() {
             // check if the joinset of tasks has futures running
@@ -226,7 +291,7 @@ impl Miner {
         self.storage.save_execution(tx_execution, check_conflicts)?;
 
         // notify
-        let _ = self.notifier_pending_txs.send(tx_hash);
+        let _ = self.notifier_pending_txs.send(PendingTransactionEvent::Added { hash: tx_hash });
 
         // if automine is enabled, automatically mines a block
         if is_automine {
@@ -256,9 +321,20 @@ impl Miner {
         let _mine_lock = self.locks.mine.lock().map_lock_error("mine_external")?;
 
         // mine block
+        // the base fee stays locked until the next pending block has its own base fee
+        let mut pending_base_fee = self.write_pending_base_fee();
         let block = self.storage.finish_pending_block()?;
         Span::with(|s| s.rec_str("block_number", &block.header.number));
 
+        let block = self.restore_finished_block_on_error(self.build_external_block(external_block, block))?;
+        *pending_base_fee = self.fee_market.next_base_fee(Some(&block.header));
+        drop(pending_base_fee);
+
+        Ok(block)
+    }
+
+    /// Builds an external block from a finished pending block, validating it against the previous committed block.
+    fn build_external_block(&self, external_block: ExternalBlock, block: PendingBlock) -> anyhow::Result<Block> {
         // mine transactions
         let mut external_txs = Vec::with_capacity(block.transactions.len());
         for tx in block.transactions.into_values() {
@@ -270,7 +346,58 @@ impl Miner {
         }
         let mined_external_txs = mine_external_transactions(block.header.number, external_txs)?;
 
-        block_from_external(external_block, mined_external_txs)
+        // validate link to the previous committed block
+        let parent_hash = self.read_parent_hash(block.header.number)?;
+        if parent_hash != Hash::ZERO && parent_hash != external_block.parent_hash() {
+            return log_and_err!(
+                payload = (parent_hash, external_block.parent_hash()),
+                "failed to mine external block because its parent hash does not match the hash of the previous block"
+            );
+        }
+
+        let block = block_from_external(external_block, mined_external_txs)?;
+        self.validate_state_root(&block)?;
+        Ok(block)
+    }
+
+    /// Validates the state root of an external block against the state root calculated locally.
+    ///
+    /// Skipped when the local state trie does not match the previous block, which happens when the blocks were not imported since the genesis.
+    fn validate_state_root(&self, block: &Block) -> anyhow::Result<()> {
+        let Some(parent_number) = block.number().prev() else { return Ok(()) };
+        let Some(parent) = self.storage.read_block(&BlockFilter::Number(parent_number))? else {
+            return Ok(());
+        };
+        if parent.header.state_root != self.storage.compute_state_root(&[])? {
+            tracing::debug!(block_number = %block.number(), "skipping state root validation because the local state trie differs from the previous block");
+            return Ok(());
+        }
+
+        let state_root = self.storage.compute_state_root(&block.compact_account_changes())?;
+        if state_root != block.header.state_root {
+            return log_and_err!(
+                payload = (state_root, block.header.state_root),
+                "failed to mine external block because its state root does not match the state root calculated locally"
+            );
+        }
+        Ok(())
+    }
+
+    /// Reads the hash of the block that precedes the specified block.
+    ///
+    /// Returns zero if the block is the genesis or the previous block was not imported.
+    fn read_parent_hash(&self, number: BlockNumber) -> anyhow::Result<Hash> {
+        let Some(parent_number) = number.prev() else { return Ok(Hash::ZERO) };
+        Ok(self.storage.read_block_hash(parent_number)?.unwrap_or(Hash::ZERO))
+    }
+
+    /// Restores the finished pending block if it could not be mined, so its transactions are mined again in the next attempt.
+    fn restore_finished_block_on_error(&self, result: anyhow::Result<Block>) -> anyhow::Result<Block> {
+        if result.is_err() {
+            // failures are already logged by the storage and the mining error is more relevant to the caller
+            let _ = self.storage.restore_finished_block();
+        }
+        result
     }
 
     /// Same as [`Self::mine_local`], but automatically commits the block instead of returning it.
@@ -293,9 +420,22 @@ impl Miner {
         let _mine_lock = self.locks.mine.lock().map_lock_error("mine_local")?;
 
         // mine block
+        // the base fee stays locked until the next pending block has its own base fee, so transactions executed in the next block
+        // are not charged the base fee of this block
+        let mut pending_base_fee = self.write_pending_base_fee();
         let block = self.storage.finish_pending_block()?;
         Span::with(|s| s.rec_str("block_number", &block.header.number));
 
+        // charge the same base fee used to execute the transactions and calculate the base fee of the next block
+        let block = self.restore_finished_block_on_error(self.build_local_block(block, *pending_base_fee))?;
+        *pending_base_fee = self.fee_market.next_base_fee(Some(&block.header));
+        drop(pending_base_fee);
+
+        Ok(block)
+    }
+
+    /// Builds a local block from a finished pending block, linking it to the previous committed block.
+    fn build_local_block(&self, block: PendingBlock, base_fee: Wei) -> anyhow::Result<Block> {
         // mine transactions
         let mut local_txs = Vec::with_capacity(block.transactions.len());
         for tx in block.transactions.into_values() {
@@ -305,8 +445,16 @@ impl Miner {
                 return log_and_err!("failed to mine local block because one of the transactions is not a local transaction");
             }
         }
+        let mut block = block_from_local(block.header.number, base_fee, local_txs)?;
+
+        // calculate state root
+        block.header.state_root = self.storage.compute_state_root(&block.compact_account_changes())?;
 
-        block_from_local(block.header.number, local_txs)
+        // link to the previous committed block and calculate final block hash
+        block.header.parent_hash = self.read_parent_hash(block.header.number)?;
+        block.update_hash();
+
+        Ok(block)
     }
 
     /// Persists a mined block to permanent storage and prepares new block.
@@ -369,13 +517,15 @@ fn mine_external_transactions(block_number: BlockNumber, txs: Vec<ExternalTransa
 }
 
 fn block_from_external(external_block: ExternalBlock, mined_txs: Vec<TransactionMined>) -> anyhow::Result<Block> {
-    Ok(Block {
+    let mut block = Block {
         header: BlockHeader::try_from(&external_block)?,
         transactions: mined_txs,
-    })
+    };
+    block.fill_receipts();
+    Ok(block)
 }
 
-pub fn block_from_local(number: BlockNumber, txs: Vec<LocalTransactionExecution>) -> anyhow::Result<Block> {
+pub fn block_from_local(number: BlockNumber, base_fee: Wei, txs: Vec<LocalTransactionExecution>) -> anyhow::Result<Block> {
     // TODO: block timestamp should be set in the PendingBlock instead of being retrieved from the execution
     let block_timestamp = match txs.first() {
         Some(tx) => tx.result.execution.block_timestamp,
@@ -383,8 +533,10 @@ pub fn block_from_local(number: BlockNumber, txs: Vec<LocalTransactionExecution>
     };
 
     let mut block = Block::new(number, block_timestamp);
+    block.header.base_fee_per_gas = base_fee;
     block.transactions.reserve(txs.len());
     block.header.size = Size::from(txs.len() as u64);
+    block.header.gas_used = Gas::from(txs.iter().map(|tx| tx.result.execution.gas.as_u64()).sum::<u64>());
 
     // mine transactions and logs
     let mut log_index = Index::ZERO;
@@ -419,11 +571,15 @@ pub fn block_from_local(number: BlockNumber, txs: Vec<LocalTransactionExecution>
             block_number: block.header.number,
             block_hash: block.header.hash,
             logs: mined_logs,
+            cumulative_gas_used: Gas::ZERO,
+            effective_gas_price: Wei::ZERO,
+            logs_bloom: LogsBloom::default(),
         };
 
         // add transaction to block
         block.transactions.push(mined_transaction);
     }
+    block.fill_receipts();
 
     // calculate transactions hash
     if not(block.transactions.is_empty()) {
@@ -431,17 +587,10 @@ pub fn block_from_local(number: BlockNumber, txs: Vec<LocalTransactionExecution>
         block.header.transactions_root = triehash::ordered_trie_root::<KeccakHasher, _>(transactions_hashes).into();
     }
 
-    // calculate final block hash
-
-    // replicate calculated block hash from header to transactions and logs
-    for transaction in block.transactions.iter_mut() {
-        transaction.block_hash = block.header.hash;
-        for log in transaction.logs.iter_mut() {
-            log.block_hash = block.header.hash;
-        }
-    }
+    // calculate receipts hash
+    block.header.receipts_root = block.compute_receipts_root();
 
-    // TODO: calculate state_root, receipts_root and parent_hash
+    // block hash is calculated by the caller after the state root and parent hash are defined
     Ok(block)
 }
 
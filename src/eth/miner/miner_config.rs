@@ -6,6 +6,7 @@ use clap::Parser;
 use display_json::DebugAsJson;
 
 use crate::eth::miner::Miner;
+use crate::eth::primitives::FeeMarket;
 use crate::eth::storage::StratusStorage;
 use crate::ext::not;
 use crate::ext::parse_duration;
@@ -21,6 +22,10 @@ pub struct MinerConfig {
     /// Target block time.
     #[arg(long = "block-mode", env = "BLOCK_MODE", default_value = "automine")]
     pub block_mode: MinerMode,
+
+    /// How the gas consumed by transactions is priced.
+    #[arg(long = "fee-market", env = "FEE_MARKET", default_value = "zero")]
+    pub fee_market: FeeMarket,
 }
 
 impl MinerConfig {
@@ -46,7 +51,8 @@ impl MinerConfig {
         tracing::info!(config = ?self, mode = ?mode, "creating block miner with specific mode");
 
         // create miner
-        let miner = Miner::new(Arc::clone(&storage), mode);
+        let miner = Miner::new(Arc::clone(&storage), mode, self.fee_market);
+        miner.reset_pending_base_fee()?;
         let miner = Arc::new(miner);
 
         if let MinerMode::Interval(block_time) = mode {
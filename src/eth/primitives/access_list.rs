@@ -0,0 +1,148 @@
+use display_json::DebugAsJson;
+use itertools::Itertools;
+
+use crate::eth::primitives::Address;
+use crate::eth::primitives::EvmExecution;
+use crate::eth::primitives::Gas;
+use crate::eth::primitives::Hash;
+use crate::ext::not;
+
+/// Account and storage slots accessed by a transaction.
+#[derive(DebugAsJson, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListItem {
+    pub address: Address,
+    pub storage_keys: Vec<Hash>,
+}
+
+/// EIP-2930 access list of a transaction and the gas it consumed.
+///
+/// Returned by `eth_createAccessList` in the same format used by Geth.
+#[derive(DebugAsJson, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListWithGasUsed {
+    pub access_list: Vec<AccessListItem>,
+
+    /// Gas consumed by the transaction without the access list.
+    ///
+    /// It is an upper bound of the gas consumed with the access list, because accessing a warm account or slot is always
+    /// cheaper than declaring it in the access list and accessing it cold.
+    pub gas_used: Gas,
+
+    /// Reason the transaction failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AccessListWithGasUsed {
+    /// Creates the access list from all accounts and slots read or modified during an execution.
+    ///
+    /// Precompiles are never included because they are always warm and have no storage. Other accounts that are always warm
+    /// (`excluded`) are not included because declaring them only increases the transaction cost, unless their slots were accessed.
+    pub fn new(execution: &EvmExecution, excluded: &[Address]) -> Self {
+        let access_list = execution
+            .changes
+            .values()
+            .filter(|changes| not(changes.address.is_precompile()))
+            .filter(|changes| not(changes.slots.is_empty()) || not(excluded.contains(&changes.address)))
+            .sorted_by_key(|changes| changes.address.0)
+            .map(|changes| AccessListItem {
+                address: changes.address,
+                storage_keys: changes.slots.keys().sorted().map(|index| Hash::new((*index).into())).collect(),
+            })
+            .collect();
+
+        Self {
+            access_list,
+            gas_used: execution.gas,
+            error: execution.failure_reason(),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use fake::Fake;
+    use fake::Faker;
+    use hex_literal::hex;
+
+    use super::*;
+    use crate::eth::primitives::Account;
+    use crate::eth::primitives::ExecutionAccountChanges;
+    use crate::eth::primitives::ExecutionResult;
+    use crate::eth::primitives::ExecutionValueChange;
+    use crate::eth::primitives::Slot;
+    use crate::eth::primitives::SlotIndex;
+
+    #[test]
+    fn access_list_includes_accessed_slots_and_cold_accounts() {
+        let sender = Address::new([1; 20]);
+        let contract = Address::new([2; 20]);
+        let other = Address::new([3; 20]);
+        let precompile = Address::new(hex!("0000000000000000000000000000000000000001"));
+
+        let mut contract_changes = ExecutionAccountChanges::from_original_values(Account::new_empty(contract));
+        for index in [SlotIndex::ONE, SlotIndex::ZERO] {
+            contract_changes
+                .slots
+                .insert(index, ExecutionValueChange::from_original(Slot::new(index, Default::default())));
+        }
+
+        let mut execution: EvmExecution = Faker.fake();
+        execution.result = ExecutionResult::Success;
+        execution.changes = [sender, precompile, other]
+            .into_iter()
+            .map(|address| (address, ExecutionAccountChanges::from_original_values(Account::new_empty(address))))
+            .chain([(contract, contract_changes)])
+            .collect();
+
+        let access_list = AccessListWithGasUsed::new(&execution, &[sender, contract]);
+        assert_eq!(
+            access_list.access_list,
+            vec![
+                AccessListItem {
+                    address: contract,
+                    storage_keys: vec![Hash::ZERO, Hash::new(SlotIndex::ONE.into())],
+                },
+                AccessListItem {
+                    address: other,
+                    storage_keys: vec![],
+                }
+            ]
+        );
+        assert_eq!(access_list.gas_used, execution.gas);
+        assert!(access_list.error.is_none());
+    }
+
+    #[test]
+    fn access_list_excludes_precompiles() {
+        let sender = Address::new([1; 20]);
+        let contract = Address::new([2; 20]);
+        let precompile = |byte: u8| {
+            let mut bytes = [0; 20];
+            bytes[19] = byte;
+            Address::new(bytes)
+        };
+        let precompiles = [0x01, 0x02, 0x04, 0x09, 0x0a].map(precompile);
+
+        let mut execution: EvmExecution = Faker.fake();
+        execution.result = ExecutionResult::Success;
+        execution.changes = precompiles
+            .into_iter()
+            .chain([sender, contract])
+            .map(|address| (address, ExecutionAccountChanges::from_original_values(Account::new_empty(address))))
+            .collect();
+
+        let access_list = AccessListWithGasUsed::new(&execution, &[sender]);
+        assert_eq!(
+            access_list.access_list,
+            vec![AccessListItem {
+                address: contract,
+                storage_keys: vec![],
+            }]
+        );
+    }
+}
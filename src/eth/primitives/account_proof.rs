@@ -0,0 +1,44 @@
+use display_json::DebugAsJson;
+
+use crate::eth::primitives::Address;
+use crate::eth::primitives::Bytes;
+use crate::eth::primitives::CodeHash;
+use crate::eth::primitives::Hash;
+use crate::eth::primitives::Nonce;
+use crate::eth::primitives::SlotIndex;
+use crate::eth::primitives::SlotValue;
+use crate::eth::primitives::Wei;
+
+/// Merkle-Patricia proof of an account and some of its slots against a state root.
+///
+/// Follows the format defined in <https://eips.ethereum.org/EIPS/eip-1186>.
+#[derive(DebugAsJson, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProof {
+    pub address: Address,
+
+    /// Encoded trie nodes from the state root to the account leaf.
+    pub account_proof: Vec<Bytes>,
+
+    pub balance: Wei,
+
+    pub code_hash: CodeHash,
+
+    pub nonce: Nonce,
+
+    /// Root of the account storage trie.
+    pub storage_hash: Hash,
+
+    pub storage_proof: Vec<StorageProof>,
+}
+
+/// Merkle-Patricia proof of a slot against the account storage root.
+#[derive(DebugAsJson, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct StorageProof {
+    pub key: SlotIndex,
+
+    pub value: SlotValue,
+
+    /// Encoded trie nodes from the storage root to the slot leaf.
+    pub proof: Vec<Bytes>,
+}
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use display_json::DebugAsJson;
 use ethereum_types::H256;
 use itertools::Itertools;
+use keccak_hasher::KeccakHasher;
 use serde::Deserialize;
 
 use super::LogMined;
@@ -12,11 +13,14 @@ use crate::alias::EthersBlockH256;
 use crate::alias::EthersTransaction;
 use crate::alias::JsonValue;
 use crate::eth::executor::EvmExecutionResult;
-use crate::eth::primitives::Address;
 use crate::eth::primitives::BlockHeader;
 use crate::eth::primitives::BlockNumber;
 use crate::eth::primitives::ExecutionAccountChanges;
+use crate::eth::primitives::ExecutionChanges;
+use crate::eth::primitives::Gas;
 use crate::eth::primitives::Hash;
+use crate::eth::primitives::Index;
+use crate::eth::primitives::LogsBloom;
 use crate::eth::primitives::TransactionMined;
 use crate::eth::primitives::UnixTime;
 use crate::ext::to_json_value;
@@ -45,7 +49,8 @@ impl Block {
     /// Pushes a single transaction execution to the blocks transactions.
     pub fn push_execution(&mut self, input: TransactionInput, evm_result: EvmExecutionResult) {
         let transaction_index = (self.transactions.len() as u64).into();
-        self.transactions.push(TransactionMined {
+        let cumulative_gas_used = self.transactions.last().map(|tx| tx.cumulative_gas_used).unwrap_or(Gas::ZERO);
+        let mut tx = TransactionMined {
             logs: evm_result
                 .execution
                 .logs
@@ -61,12 +66,57 @@ impl Block {
                     block_hash: self.header.hash,
                 })
                 .collect(),
+            cumulative_gas_used: cumulative_gas_used.as_u64().saturating_add(evm_result.execution.gas.as_u64()).into(),
+            effective_gas_price: input.effective_gas_price(self.header.base_fee_per_gas),
+            logs_bloom: LogsBloom::default(),
             input,
             execution: evm_result.execution,
             transaction_index,
             block_number: self.header.number,
             block_hash: self.header.hash,
-        }); // TODO: update logs bloom
+        };
+        tx.logs_bloom = tx.compute_bloom();
+        self.transactions.push(tx);
+    }
+
+    /// Calculates the receipt fields of the transactions that depend on the block or on the preceding transactions.
+    ///
+    /// Must be called after all transactions are added to the block and the base fee is defined.
+    pub fn fill_receipts(&mut self) {
+        let mut cumulative_gas_used = 0u64;
+        for tx in &mut self.transactions {
+            cumulative_gas_used = cumulative_gas_used.saturating_add(tx.execution.gas.as_u64());
+            tx.cumulative_gas_used = cumulative_gas_used.into();
+            tx.effective_gas_price = tx.input.effective_gas_price(self.header.base_fee_per_gas);
+            tx.logs_bloom = tx.compute_bloom();
+        }
+    }
+
+    /// Checks if the receipt fields of the transactions are missing because the block was stored before they existed.
+    pub fn is_missing_receipts(&self) -> bool {
+        self.transactions.iter().any(|tx| tx.cumulative_gas_used == Gas::ZERO)
+    }
+
+    /// Calculates the root of the trie of the transactions receipts.
+    pub fn compute_receipts_root(&self) -> Hash {
+        if self.transactions.is_empty() {
+            return Hash::EMPTY_TRIE;
+        }
+        let receipts = self.transactions.iter().map(TransactionMined::encode_receipt);
+        triehash::ordered_trie_root::<KeccakHasher, _>(receipts).into()
+    }
+
+    /// Calculates the block hash from its header and replicates it to the transactions and logs.
+    ///
+    /// Must be called after all header fields are defined.
+    pub fn update_hash(&mut self) {
+        self.header.hash = self.header.compute_hash();
+        for transaction in self.transactions.iter_mut() {
+            transaction.block_hash = self.header.hash;
+            for log in transaction.logs.iter_mut() {
+                log.block_hash = self.header.hash;
+            }
+        }
     }
 
     /// Calculates block size label by the number of transactions.
@@ -123,36 +173,30 @@ impl Block {
 
     /// Compact accounts changes removing intermediate values, keeping only the last modified nonce, balance, bytecode and slots.
     pub fn compact_account_changes(&self) -> Vec<ExecutionAccountChanges> {
-        let mut block_compacted_changes: HashMap<Address, ExecutionAccountChanges> = HashMap::new();
-        for transaction in &self.transactions {
-            for transaction_changes in transaction.execution.changes.values() {
-                let account_compacted_changes = block_compacted_changes
-                    .entry(transaction_changes.address)
-                    .or_insert(transaction_changes.clone());
-
-                if let Some(nonce) = transaction_changes.nonce.take_modified_ref() {
-                    account_compacted_changes.nonce.set_modified(*nonce);
-                }
-
-                if let Some(balance) = transaction_changes.balance.take_modified_ref() {
-                    account_compacted_changes.balance.set_modified(*balance);
-                }
+        compact_transactions_changes(&self.transactions).into_values().collect_vec()
+    }
 
-                if let Some(bytecode) = transaction_changes.bytecode.take_modified_ref() {
-                    account_compacted_changes.bytecode.set_modified(bytecode.clone());
-                }
+    /// Compact accounts changes of the transactions that precede the specified transaction index in the block.
+    pub fn compact_account_changes_before(&self, transaction_index: Index) -> ExecutionChanges {
+        compact_transactions_changes(self.transactions.iter().take_while(|tx| tx.transaction_index < transaction_index))
+    }
+}
 
-                for (slot_index, slot) in &transaction_changes.slots {
-                    let slot_compacted_changes = account_compacted_changes.slots.entry(*slot_index).or_insert(slot.clone());
-                    if let Some(slot_value) = slot.take_modified_ref() {
-                        slot_compacted_changes.set_modified(*slot_value);
-                    }
+/// Compact accounts changes of the transactions removing intermediate values, keeping only the last modified nonce, balance, bytecode and slots.
+fn compact_transactions_changes<'a>(transactions: impl IntoIterator<Item = &'a TransactionMined>) -> ExecutionChanges {
+    let mut compacted_changes: ExecutionChanges = HashMap::new();
+    for transaction in transactions {
+        for transaction_changes in transaction.execution.changes.values() {
+            match compacted_changes.get_mut(&transaction_changes.address) {
+                Some(account_compacted_changes) => account_compacted_changes.merge(transaction_changes),
+                None => {
+                    compacted_changes.insert(transaction_changes.address, transaction_changes.clone());
                 }
             }
         }
-
-        block_compacted_changes.into_values().collect_vec()
     }
+
+    compacted_changes
 }
 
 // -----------------------------------------------------------------------------
@@ -190,3 +234,66 @@ impl TryFrom<JsonValue> for Block {
         }
     }
 }
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use fake::Fake;
+    use fake::Faker;
+
+    use super::*;
+    use crate::eth::primitives::Wei;
+    use crate::ext::from_json_str;
+
+    #[test]
+    fn fill_receipts_accumulates_gas_and_prices_by_base_fee() {
+        let mut block = Block::new(BlockNumber::ONE, UnixTime::now());
+        block.header.base_fee_per_gas = Wei::ONE;
+        for gas in [21_000u64, 50_000] {
+            let mut tx: TransactionMined = Faker.fake();
+            tx.execution.gas = gas.into();
+            tx.input.gas_price = 10u64.into();
+            tx.input.max_fee_per_gas = None;
+            tx.input.max_priority_fee_per_gas = None;
+            block.transactions.push(tx);
+        }
+
+        block.fill_receipts();
+
+        assert_eq!(block.transactions[0].cumulative_gas_used, Gas::from(21_000u64));
+        assert_eq!(block.transactions[1].cumulative_gas_used, Gas::from(71_000u64));
+        for tx in &block.transactions {
+            assert_eq!(tx.effective_gas_price, Wei::from(10u64));
+            assert_eq!(tx.logs_bloom, tx.compute_bloom());
+        }
+    }
+
+    #[test]
+    fn block_stored_without_receipts_is_filled() {
+        let mut block = Block::new(BlockNumber::ONE, UnixTime::now());
+        let mut tx: TransactionMined = Faker.fake();
+        tx.execution.gas = 21_000u64.into();
+        tx.input.gas_price = 10u64.into();
+        tx.input.max_fee_per_gas = None;
+        tx.input.max_priority_fee_per_gas = None;
+        block.transactions.push(tx);
+        block.fill_receipts();
+
+        // json of a block stored before the receipt fields existed
+        let mut json = to_json_value(&block);
+        for tx in json["transactions"].as_array_mut().unwrap() {
+            let tx = tx.as_object_mut().unwrap();
+            tx.remove("cumulative_gas_used");
+            tx.remove("effective_gas_price");
+            tx.remove("logs_bloom");
+        }
+
+        let mut stored: Block = from_json_str(&json.to_string());
+        assert!(stored.is_missing_receipts());
+        stored.fill_receipts();
+        assert!(!stored.is_missing_receipts());
+        assert_eq!(stored, block);
+    }
+}
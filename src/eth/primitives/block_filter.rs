@@ -5,6 +5,7 @@ use display_json::DebugAsJson;
 use crate::alias::JsonValue;
 use crate::eth::primitives::BlockNumber;
 use crate::eth::primitives::Hash;
+use crate::ext::not;
 
 #[derive(DebugAsJson, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, Hash)]
 #[cfg_attr(test, derive(fake::Dummy))]
@@ -19,6 +20,16 @@ pub enum BlockFilter {
     /// Information from the first block.
     Earliest,
 
+    /// Information from the last block considered safe from reorgs.
+    ///
+    /// Stratus blocks are final when mined, so it is the same as the last mined block.
+    Safe,
+
+    /// Information from the last finalized block.
+    ///
+    /// Stratus blocks are final when mined, so it is the same as the last mined block.
+    Finalized,
+
     /// Retrieve a block by its hash.
     Hash(Hash),
 
@@ -32,6 +43,8 @@ impl Display for BlockFilter {
             BlockFilter::Latest => write!(f, "latest"),
             BlockFilter::Pending => write!(f, "pending"),
             BlockFilter::Earliest => write!(f, "earliest"),
+            BlockFilter::Safe => write!(f, "safe"),
+            BlockFilter::Finalized => write!(f, "finalized"),
             BlockFilter::Hash(block_hash) => write!(f, "{}", block_hash),
             BlockFilter::Number(block_number) => write!(f, "{}", block_number),
         }
@@ -65,6 +78,8 @@ impl<'de> serde::Deserialize<'de> for BlockFilter {
                     "latest" | "Latest" => Ok(Self::Latest),
                     "pending" | "Pending" => Ok(Self::Pending),
                     "earliest" | "Earliest" => Ok(Self::Earliest),
+                    "safe" | "Safe" => Ok(Self::Safe),
+                    "finalized" | "Finalized" => Ok(Self::Finalized),
 
                     // parse hash (64: H256 without 0x prefix; 66: H256 with 0x prefix)
                     s if s.len() == 64 || s.len() == 66 => {
@@ -79,6 +94,30 @@ impl<'de> serde::Deserialize<'de> for BlockFilter {
                 }
             }
 
+            // EIP-1898 (requireCanonical is ignored because Stratus blocks are never reorganized)
+            serde_json::Value::Object(map) if map.contains_key("blockHash") || map.contains_key("blockNumber") => {
+                let unexpected_field = map
+                    .keys()
+                    .find(|key| not(matches!(key.as_str(), "blockHash" | "blockNumber" | "requireCanonical")));
+                if let Some(key) = unexpected_field {
+                    return Err(serde::de::Error::custom(format!("block parameter object has unexpected field \"{}\"", key)));
+                }
+                if map.get("requireCanonical").is_some_and(|value| not(value.is_boolean())) {
+                    return Err(serde::de::Error::custom("requireCanonical must be a boolean"));
+                }
+                match (map.get("blockHash"), map.get("blockNumber")) {
+                    (Some(hash), None) => {
+                        let hash: Hash = serde_json::from_value(hash.clone()).map_err(serde::de::Error::custom)?;
+                        Ok(Self::Hash(hash))
+                    }
+                    (None, Some(number)) => {
+                        let number: BlockNumber = serde_json::from_value(number.clone()).map_err(serde::de::Error::custom)?;
+                        Ok(Self::Number(number))
+                    }
+                    _ => Err(serde::de::Error::custom("block parameter object must have either blockHash or blockNumber")),
+                }
+            }
+
             serde_json::Value::Object(map) => {
                 if map.len() != 1 {
                     return Err(serde::de::Error::custom("value was an object with an unexpected number of fields"));
@@ -131,4 +170,26 @@ mod tests {
         let json = json!("0x2");
         assert_eq!(serde_json::from_value::<BlockFilter>(json).unwrap(), BlockFilter::Number(2usize.into()));
     }
+
+    #[test]
+    fn serde_block_number_with_safe_and_finalized() {
+        assert_eq!(serde_json::from_value::<BlockFilter>(json!("safe")).unwrap(), BlockFilter::Safe);
+        assert_eq!(serde_json::from_value::<BlockFilter>(json!("finalized")).unwrap(), BlockFilter::Finalized);
+    }
+
+    #[test]
+    fn serde_block_number_with_eip1898_object() {
+        let hash = Hash::new([1; 32]);
+        let json = json!({ "blockHash": hash, "requireCanonical": true });
+        assert_eq!(serde_json::from_value::<BlockFilter>(json).unwrap(), BlockFilter::Hash(hash));
+
+        let json = json!({ "blockNumber": "0x2" });
+        assert_eq!(serde_json::from_value::<BlockFilter>(json).unwrap(), BlockFilter::Number(2usize.into()));
+
+        let json = json!({ "blockHash": hash, "blockNumber": "0x2" });
+        assert!(serde_json::from_value::<BlockFilter>(json).is_err());
+
+        let json = json!({ "blockNumber": "0x2", "requireCanonical": "yes" });
+        assert!(serde_json::from_value::<BlockFilter>(json).is_err());
+    }
 }
@@ -4,14 +4,15 @@ use ethereum_types::H64;
 use ethereum_types::U256;
 use ethers_core::types::Block as EthersBlock;
 use ethers_core::types::OtherFields;
+use ethers_core::utils::keccak256;
 use fake::Dummy;
 use fake::Fake;
 use fake::Faker;
 use hex_literal::hex;
 use jsonrpsee::SubscriptionMessage;
+use rlp::RlpStream;
 
 use crate::alias::EthersBlockVoid;
-use crate::alias::EthersBytes;
 use crate::eth::primitives::logs_bloom::LogsBloom;
 use crate::eth::primitives::Address;
 use crate::eth::primitives::BlockNumber;
@@ -23,14 +24,13 @@ use crate::eth::primitives::Hash;
 use crate::eth::primitives::MinerNonce;
 use crate::eth::primitives::Size;
 use crate::eth::primitives::UnixTime;
+use crate::eth::primitives::Wei;
 use crate::ext::InfallibleExt;
+use crate::if_else;
 
 /// Special hash used in block mining to indicate no uncle blocks.
 const HASH_EMPTY_UNCLES: Hash = Hash::new(hex!("1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347"));
 
-/// Special hash used in block mining to indicate no transaction root and no receipts root.
-const HASH_EMPTY_TRIE: Hash = Hash::new(hex!("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"));
-
 #[derive(DebugAsJson, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct BlockHeader {
     pub number: BlockNumber,
@@ -51,31 +51,83 @@ pub struct BlockHeader {
     pub state_root: Hash,
     pub total_difficulty: Difficulty, // is always 0x0
     pub nonce: MinerNonce,            // is always 0x0000000000000000
+
+    /// Zero when the block was mined without a fee market.
+    #[serde(default)]
+    pub base_fee_per_gas: Wei,
 }
 
 impl BlockHeader {
     /// Creates a new block header with the given number.
+    ///
+    /// The parent hash is not known and must be set by the miner before the block hash is computed again.
     pub fn new(number: BlockNumber, timestamp: UnixTime) -> Self {
-        Self {
+        let mut header = Self {
             number,
-            hash: number.hash(),
-            transactions_root: HASH_EMPTY_TRIE,
+            hash: Hash::ZERO,
+            transactions_root: Hash::EMPTY_TRIE,
             gas_used: Gas::ZERO,
-            gas_limit: Gas::ZERO,
+            gas_limit: Gas::BLOCK_LIMIT,
             bloom: LogsBloom::default(),
             timestamp,
-            parent_hash: number.prev().map(|n| n.hash()).unwrap_or(Hash::ZERO),
-            author: Address::default(),
+            parent_hash: Hash::ZERO,
+            author: Address::COINBASE,
             extra_data: Bytes::default(),
-            miner: Address::default(),
+            miner: Address::COINBASE,
             difficulty: Difficulty::default(),
-            receipts_root: HASH_EMPTY_TRIE,
+            receipts_root: Hash::EMPTY_TRIE,
             uncle_hash: HASH_EMPTY_UNCLES,
             size: Size::default(),
-            state_root: HASH_EMPTY_TRIE,
+            state_root: Hash::EMPTY_TRIE,
             total_difficulty: Difficulty::default(),
             nonce: MinerNonce::default(),
-        }
+            base_fee_per_gas: Wei::ZERO,
+        };
+        header.hash = header.compute_hash();
+        header
+    }
+
+    /// Calculates the block hash as the Keccak-256 of the RLP-encoded header.
+    ///
+    /// Uses the same fields and values returned by the JSON-RPC, so clients can verify the hash of the blocks.
+    pub fn compute_hash(&self) -> Hash {
+        let mut stream = RlpStream::new_list(16);
+        self.append_legacy_fields(&mut stream);
+        stream.append(&U256::from(self.base_fee_per_gas));
+        Hash::new(keccak256(stream.out()))
+    }
+
+    /// Appends to the RLP stream the header fields that exist since the first Ethereum block, before the London hardfork added the base fee.
+    fn append_legacy_fields(&self, stream: &mut RlpStream) {
+        stream.append(&self.parent_hash.0);
+        stream.append(&self.uncle_hash.0);
+        stream.append(&self.miner.0);
+        stream.append(&self.state_root.0);
+        stream.append(&self.transactions_root.0);
+        stream.append(&self.receipts_root.0);
+        stream.append(&self.bloom.0);
+        stream.append(&U256::from(self.difficulty));
+        stream.append(&self.number.as_u64());
+        stream.append(&self.gas_limit.as_u64());
+        stream.append(&self.gas_used.as_u64());
+        stream.append(&*self.timestamp);
+        stream.append(&self.extra_data.0);
+        stream.append(&H256::zero()); // mix hash
+        stream.append(&H64::from(self.nonce));
+    }
+
+    /// Returns the miner reported to clients.
+    ///
+    /// Blocks mined locally before the miner was stored have a zero miner, but they were always reported as mined by the coinbase.
+    pub fn reported_miner(&self) -> Address {
+        if_else!(self.miner.is_zero(), Address::COINBASE, self.miner)
+    }
+
+    /// Returns the gas limit reported to clients.
+    ///
+    /// Blocks mined locally before the gas limit was stored have a zero gas limit, but they were always limited by the block gas limit.
+    pub fn reported_gas_limit(&self) -> Gas {
+        if_else!(self.gas_limit == Gas::ZERO, Gas::BLOCK_LIMIT, self.gas_limit)
     }
 }
 
@@ -100,6 +152,7 @@ impl Dummy<Faker> for BlockHeader {
             state_root: faker.fake_with_rng(rng),
             total_difficulty: faker.fake_with_rng(rng),
             nonce: faker.fake_with_rng(rng),
+            base_fee_per_gas: faker.fake_with_rng(rng),
         }
     }
 }
@@ -119,38 +172,38 @@ where
             mix_hash: Some(H256::default()),
 
             // block: relation with other blocks
-            uncles_hash: HASH_EMPTY_UNCLES.into(),
+            uncles_hash: header.uncle_hash.into(),
             uncles: Vec::new(),
             parent_beacon_block_root: None,
             parent_hash: header.parent_hash.into(),
 
             // mining: identifiers
             timestamp: (*header.timestamp).into(),
-            author: Some(Address::COINBASE.into()),
+            author: Some(header.reported_miner().into()),
 
             // minining: difficulty
-            difficulty: U256::zero(),
+            difficulty: header.difficulty.into(),
             total_difficulty: Some(U256::zero()),
-            nonce: Some(H64::zero()),
+            nonce: Some(header.nonce.into()),
 
             // mining: gas
-            gas_limit: Gas::from(100_000_000u64).into(),
+            gas_limit: header.reported_gas_limit().into(),
             gas_used: header.gas_used.into(),
-            base_fee_per_gas: Some(U256::zero()),
+            base_fee_per_gas: Some(header.base_fee_per_gas.into()),
             blob_gas_used: None,
             excess_blob_gas: None,
 
             // transactions
             transactions: vec![], // can't fill transactions from header, must be modified afterward
             transactions_root: header.transactions_root.into(),
-            receipts_root: HASH_EMPTY_TRIE.into(),
+            receipts_root: header.receipts_root.into(),
             withdrawals_root: None,
             withdrawals: None,
 
             // data
             size: Some(u64::from(header.size).into()),
             logs_bloom: Some(*header.bloom),
-            extra_data: EthersBytes::default(),
+            extra_data: header.extra_data.into(),
             state_root: header.state_root.into(),
             seal_fields: Vec::default(),
             other: OtherFields::default(),
@@ -184,6 +237,7 @@ impl TryFrom<&ExternalBlock> for BlockHeader {
             state_root: value.state_root.into(),
             total_difficulty: value.total_difficulty.unwrap_or_default().into(),
             nonce: value.nonce.unwrap_or_default().into(),
+            base_fee_per_gas: value.base_fee_per_gas.unwrap_or_default().into(),
         })
     }
 }
@@ -200,23 +254,37 @@ impl From<BlockHeader> for SubscriptionMessage {
 // -----------------------------------------------------------------------------
 #[cfg(test)]
 mod tests {
+    use hex_literal::hex;
+    use rlp::RlpStream;
+
+    use crate::eth::primitives::Address;
     use crate::eth::primitives::BlockHeader;
     use crate::eth::primitives::BlockNumber;
+    use crate::eth::primitives::Bytes;
+    use crate::eth::primitives::Difficulty;
+    use crate::eth::primitives::Gas;
     use crate::eth::primitives::Hash;
+    use crate::eth::primitives::MinerNonce;
     use crate::eth::primitives::UnixTime;
 
     #[test]
     fn block_header_hash_calculation() {
         let header = BlockHeader::new(BlockNumber::ZERO, UnixTime::from(1234567890));
-        assert_eq!(header.hash.to_string(), "0x011b4d03dd8c01f1049143cf9c4c817e4b167f1d1b83e5c6f0f10d89ba1e7bce");
+        assert_eq!(header.hash.to_string(), "0xb0f884cc65dc3594e988176665f142bdb16443797c52f98fc9cdd9b36b6efa7b");
+        assert_eq!(header.hash, header.compute_hash());
     }
 
     #[test]
-    fn block_header_parent_hash() {
-        let header = BlockHeader::new(BlockNumber::ONE, UnixTime::from(1234567891));
+    fn block_header_hash_depends_on_parent_hash() {
+        let parent = BlockHeader::new(BlockNumber::ZERO, UnixTime::from(1234567890));
+        let mut header = BlockHeader::new(BlockNumber::ONE, UnixTime::from(1234567891));
+        assert_eq!(header.parent_hash, Hash::ZERO);
+
+        header.parent_hash = parent.hash;
+        assert_ne!(header.compute_hash(), header.hash);
         assert_eq!(
-            header.parent_hash.to_string(),
-            "0x011b4d03dd8c01f1049143cf9c4c817e4b167f1d1b83e5c6f0f10d89ba1e7bce"
+            header.compute_hash().to_string(),
+            "0x1d25cb416e5b404a6af59e52563aad30a1912dbfe48f019e77b1ab01dcca6f69"
         );
     }
 
@@ -225,4 +293,42 @@ mod tests {
         let header = BlockHeader::new(BlockNumber::ZERO, UnixTime::from(1234567890));
         assert_eq!(header.parent_hash, Hash::ZERO);
     }
+
+    #[test]
+    fn block_header_hash_matches_ethereum_mainnet_genesis() {
+        // the mainnet genesis predates the base fee, so only the legacy fields are hashed
+        let mut header = BlockHeader::new(BlockNumber::ZERO, UnixTime::from(0u64));
+        header.miner = Address::ZERO;
+        header.state_root = Hash::new(hex!("d7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544"));
+        header.difficulty = Difficulty::from(0x400000000u64);
+        header.gas_limit = Gas::from(5000u64);
+        header.extra_data = Bytes(hex!("11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa").to_vec());
+        header.nonce = MinerNonce::from(hex!("0000000000000042"));
+
+        let mut stream = RlpStream::new_list(15);
+        header.append_legacy_fields(&mut stream);
+        let hash = Hash::new(ethers_core::utils::keccak256(stream.out()));
+        assert_eq!(hash.to_string(), "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3");
+    }
+
+    #[test]
+    fn block_header_reports_coinbase_for_blocks_stored_without_miner() {
+        let mut header = BlockHeader::new(BlockNumber::ZERO, UnixTime::from(1234567890));
+        header.miner = Address::ZERO;
+        assert_eq!(header.reported_miner(), Address::COINBASE);
+
+        let miner: Address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse().unwrap();
+        header.miner = miner;
+        assert_eq!(header.reported_miner(), miner);
+    }
+
+    #[test]
+    fn block_header_reports_block_gas_limit_for_blocks_stored_without_gas_limit() {
+        let mut header = BlockHeader::new(BlockNumber::ZERO, UnixTime::from(1234567890));
+        header.gas_limit = Gas::ZERO;
+        assert_eq!(header.reported_gas_limit(), Gas::BLOCK_LIMIT);
+
+        header.gas_limit = Gas::from(5000u64);
+        assert_eq!(header.reported_gas_limit(), Gas::from(5000u64));
+    }
 }
@@ -0,0 +1,26 @@
+use display_json::DebugAsJson;
+use ethereum_types::U64;
+
+use crate::eth::primitives::BlockNumber;
+use crate::eth::primitives::UnixTime;
+
+/// Block values overridden by the caller while executing a call.
+///
+/// Fields that are not specified are derived from the block the call is executed at.
+#[derive(DebugAsJson, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BlockOverride {
+    /// Replaces the block number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number: Option<BlockNumber>,
+
+    /// Replaces the block timestamp.
+    #[serde(alias = "timestamp", skip_serializing_if = "Option::is_none")]
+    pub time: Option<U64>,
+}
+
+impl BlockOverride {
+    /// Returns the overridden block timestamp.
+    pub fn timestamp(&self) -> Option<UnixTime> {
+        self.time.map(|time| time.as_u64().into())
+    }
+}
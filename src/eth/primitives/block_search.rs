@@ -0,0 +1,23 @@
+use display_json::DebugAsJson;
+
+use crate::eth::primitives::BlockNumber;
+
+/// Blocks visited by a paginated search, in the order they are visited.
+#[derive(DebugAsJson, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum BlockSearch {
+    /// Blocks before the block, from the most recent to the oldest.
+    Before(BlockNumber),
+
+    /// Blocks after the block, from the oldest to the most recent.
+    After(BlockNumber),
+}
+
+impl BlockSearch {
+    /// Checks if a block is visited by the search.
+    pub fn contains(&self, number: BlockNumber) -> bool {
+        match self {
+            Self::Before(before) => number < *before,
+            Self::After(after) => number > *after,
+        }
+    }
+}
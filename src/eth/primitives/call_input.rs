@@ -2,6 +2,7 @@ use display_json::DebugAsJson;
 
 use crate::eth::primitives::Address;
 use crate::eth::primitives::Bytes;
+use crate::eth::primitives::Gas;
 use crate::eth::primitives::Wei;
 
 #[derive(DebugAsJson, Clone, PartialEq, Eq, fake::Dummy, serde::Serialize, serde::Deserialize)]
@@ -17,4 +18,8 @@ pub struct CallInput {
 
     #[serde(rename = "data", alias = "input", default)]
     pub data: Bytes,
+
+    /// Max gas the call can consume. Used only as the upper bound of gas estimations.
+    #[serde(rename = "gas", default, skip_serializing_if = "Option::is_none")]
+    pub gas: Option<Gas>,
 }
@@ -0,0 +1,426 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use display_json::DebugAsJson;
+use ethereum_types::H256;
+
+use crate::eth::primitives::Address;
+use crate::eth::primitives::Bytes;
+use crate::eth::primitives::ExecutionChanges;
+use crate::eth::primitives::Gas;
+use crate::eth::primitives::LogTopic;
+use crate::eth::primitives::Wei;
+use crate::ext::not;
+use crate::if_else;
+
+/// Result of an execution inspected by one of the supported tracers.
+///
+/// Serialized in the same format produced by Geth native tracers.
+#[derive(DebugAsJson, Clone, serde::Serialize)]
+#[serde(untagged)]
+pub enum EvmTrace {
+    /// Result of the `callTracer`.
+    Call(CallFrame),
+
+    /// Result of the `prestateTracer`.
+    Prestate(HashMap<Address, PrestateAccount>),
+
+    /// Result of the `prestateTracer` in diff mode.
+    PrestateDiff {
+        pre: HashMap<Address, PrestateAccount>,
+        post: HashMap<Address, PrestateAccount>,
+    },
+}
+
+impl EvmTrace {
+    /// Creates a `prestateTracer` result from the changes tracked during the execution.
+    ///
+    /// In default mode, returns the original state of all accounts and slots touched by the execution.
+    ///
+    /// In diff mode, returns the original and the modified state of all accounts and slots modified by the execution.
+    pub fn from_changes(changes: &ExecutionChanges, diff_mode: bool) -> Self {
+        if not(diff_mode) {
+            let pre = changes
+                .values()
+                .filter(|account| not(account.new_account))
+                .map(|account| {
+                    let mut pre = PrestateAccount::new(
+                        account.balance.take_original_ref().copied(),
+                        account.nonce.take_original_ref().map(|nonce| nonce.as_u64()),
+                        account.bytecode.take_original_ref().cloned().flatten(),
+                    );
+                    for (index, slot) in &account.slots {
+                        if let Some(original) = slot.take_original_ref() {
+                            pre.storage.insert(H256((*index).into()), H256(original.value.into()));
+                        }
+                    }
+                    (account.address, pre)
+                })
+                .collect();
+            return Self::Prestate(pre);
+        }
+
+        let mut pre = HashMap::new();
+        let mut post = HashMap::new();
+        for account in changes.values() {
+            let modified_slots = account.slots.iter().filter(|(_, slot)| slot.is_modified()).collect::<Vec<_>>();
+            if not(account.is_account_modified()) && modified_slots.is_empty() {
+                continue;
+            }
+
+            // original state of modified accounts that existed before the execution
+            if not(account.new_account) {
+                let mut account_pre = PrestateAccount::new(
+                    account.balance.take_original_ref().copied(),
+                    account.nonce.take_original_ref().map(|nonce| nonce.as_u64()),
+                    account.bytecode.take_original_ref().cloned().flatten(),
+                );
+                for (index, slot) in &modified_slots {
+                    if let Some(original) = slot.take_original_ref() {
+                        account_pre.storage.insert(H256((**index).into()), H256(original.value.into()));
+                    }
+                }
+                pre.insert(account.address, account_pre);
+            }
+
+            // only modified values after the execution
+            let mut account_post = PrestateAccount::new(
+                if_else!(account.balance.is_modified(), account.balance.take_modified_ref().copied(), None),
+                if_else!(account.nonce.is_modified(), account.nonce.take_modified_ref().map(|nonce| nonce.as_u64()), None),
+                if_else!(account.bytecode.is_modified(), account.bytecode.take_modified_ref().cloned().flatten(), None),
+            );
+            for (index, slot) in &modified_slots {
+                if let Some(modified) = slot.take_modified_ref() {
+                    account_post.storage.insert(H256((**index).into()), H256(modified.value.into()));
+                }
+            }
+            post.insert(account.address, account_post);
+        }
+        Self::PrestateDiff { pre, post }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Call tracer
+// -----------------------------------------------------------------------------
+
+/// Call frame tracked by the `callTracer`.
+#[derive(DebugAsJson, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    #[serde(rename = "type")]
+    pub kind: CallKind,
+
+    pub from: Address,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Wei>,
+
+    pub gas: Gas,
+
+    pub gas_used: Gas,
+
+    pub input: Bytes,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<Bytes>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<CallFrame>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub logs: Vec<CallFrameLog>,
+}
+
+impl CallFrame {
+    /// Creates a call frame that started executing, but did not finish yet.
+    pub fn new(kind: CallKind, from: Address, to: Option<Address>, value: Option<Wei>, gas: Gas, input: Bytes) -> Self {
+        Self {
+            kind,
+            from,
+            to,
+            value,
+            gas,
+            gas_used: Gas::ZERO,
+            input,
+            output: None,
+            error: None,
+            revert_reason: None,
+            calls: Vec::new(),
+            logs: Vec::new(),
+        }
+    }
+
+    /// Checks if the call frame finished with an error.
+    pub fn is_failure(&self) -> bool {
+        self.error.is_some()
+    }
+
+    /// Removes logs emitted by failed frames and their children because they are discarded by the EVM.
+    pub fn clear_failed_logs(&mut self) {
+        if self.is_failure() {
+            self.clear_logs();
+            return;
+        }
+        for call in &mut self.calls {
+            call.clear_failed_logs();
+        }
+    }
+
+    fn clear_logs(&mut self) {
+        self.logs.clear();
+        for call in &mut self.calls {
+            call.clear_logs();
+        }
+    }
+}
+
+/// Kind of the call frame.
+#[derive(DebugAsJson, Clone, Copy, PartialEq, Eq, fake::Dummy, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum CallKind {
+    Call,
+    CallCode,
+    DelegateCall,
+    StaticCall,
+    Create,
+    Create2,
+    SelfDestruct,
+}
+
+/// Log emitted inside a call frame tracked by the `callTracer`.
+#[derive(DebugAsJson, Clone, serde::Serialize)]
+pub struct CallFrameLog {
+    pub address: Address,
+
+    pub topics: Vec<LogTopic>,
+
+    pub data: Bytes,
+
+    /// Number of child calls executed before the log was emitted.
+    #[serde(serialize_with = "serialize_position")]
+    pub position: usize,
+}
+
+fn serialize_position<S>(position: &usize, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&format!("{:#x}", position))
+}
+
+// -----------------------------------------------------------------------------
+// Prestate tracer
+// -----------------------------------------------------------------------------
+
+/// Account state tracked by the `prestateTracer`.
+#[derive(DebugAsJson, Clone, Default, serde::Serialize)]
+pub struct PrestateAccount {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<Wei>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<H256, H256>,
+}
+
+impl PrestateAccount {
+    /// Creates an account state omitting zero nonce and empty bytecode like Geth does.
+    fn new(balance: Option<Wei>, nonce: Option<u64>, code: Option<Bytes>) -> Self {
+        Self {
+            balance,
+            nonce: nonce.filter(|nonce| *nonce != 0),
+            code: code.filter(|code| not(code.is_empty())),
+            storage: BTreeMap::new(),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::eth::primitives::Account;
+    use crate::eth::primitives::ExecutionAccountChanges;
+    use crate::eth::primitives::ExecutionValueChange;
+    use crate::eth::primitives::Nonce;
+    use crate::eth::primitives::Slot;
+    use crate::eth::primitives::SlotIndex;
+    use crate::eth::primitives::SlotValue;
+
+    const ALICE: Address = Address::new([1; 20]);
+    const CONTRACT: Address = Address::new([2; 20]);
+
+    fn log(position: usize) -> CallFrameLog {
+        CallFrameLog {
+            address: CONTRACT,
+            topics: vec![LogTopic::from([3; 32])],
+            data: Bytes(vec![4]),
+            position,
+        }
+    }
+
+    #[test]
+    fn call_frame_serializes_like_geth() {
+        let mut root = CallFrame::new(CallKind::Call, ALICE, Some(CONTRACT), Some(Wei::ZERO), Gas::from(50_000u64), Bytes(vec![0xab]));
+        root.gas_used = Gas::from(21_000u64);
+        root.output = Some(Bytes(vec![]));
+        root.logs.push(log(1));
+        let mut child = CallFrame::new(CallKind::Create2, CONTRACT, Some(ALICE), None, Gas::from(1_000u64), Bytes(vec![]));
+        child.error = Some("execution reverted".to_owned());
+        child.revert_reason = Some("denied".to_owned());
+        root.calls.push(child);
+
+        let expected = json!({
+            "type": "CALL",
+            "from": "0x0101010101010101010101010101010101010101",
+            "to": "0x0202020202020202020202020202020202020202",
+            "value": "0x0",
+            "gas": "0xc350",
+            "gasUsed": "0x5208",
+            "input": "0xab",
+            "output": "0x",
+            "calls": [{
+                "type": "CREATE2",
+                "from": "0x0202020202020202020202020202020202020202",
+                "to": "0x0101010101010101010101010101010101010101",
+                "gas": "0x3e8",
+                "gasUsed": "0x0",
+                "input": "0x",
+                "error": "execution reverted",
+                "revertReason": "denied"
+            }],
+            "logs": [{
+                "address": "0x0202020202020202020202020202020202020202",
+                "topics": ["0x0303030303030303030303030303030303030303030303030303030303030303"],
+                "data": "0x04",
+                "position": "0x1"
+            }]
+        });
+        assert_eq!(serde_json::to_value(EvmTrace::Call(root)).unwrap(), expected);
+    }
+
+    #[test]
+    fn call_frame_clears_logs_of_failed_frames() {
+        let mut root = CallFrame::new(CallKind::Call, ALICE, Some(CONTRACT), None, Gas::ZERO, Bytes(vec![]));
+        root.logs.push(log(0));
+        let mut failed = CallFrame::new(CallKind::Call, CONTRACT, Some(ALICE), None, Gas::ZERO, Bytes(vec![]));
+        failed.error = Some("execution reverted".to_owned());
+        failed.logs.push(log(0));
+        let mut nested = CallFrame::new(CallKind::Call, ALICE, Some(CONTRACT), None, Gas::ZERO, Bytes(vec![]));
+        nested.logs.push(log(0));
+        failed.calls.push(nested);
+        root.calls.push(failed);
+
+        root.clear_failed_logs();
+        assert_eq!(root.logs.len(), 1);
+        assert!(root.calls[0].logs.is_empty());
+        assert!(root.calls[0].calls[0].logs.is_empty());
+    }
+
+    fn changes() -> ExecutionChanges {
+        // existing contract with a modified slot and an untouched read slot
+        let mut contract = ExecutionAccountChanges::from_original_values(Account {
+            address: CONTRACT,
+            nonce: Nonce::from(1u64),
+            balance: Wei::from(10u64),
+            bytecode: Some(Bytes(vec![0x60, 0x00])),
+            ..Account::new_empty(CONTRACT)
+        });
+        let original_slot = Slot::new(SlotIndex::ZERO, SlotValue::from(1u64));
+        let modified_slot = Slot::new(SlotIndex::ZERO, SlotValue::from(5u64));
+        let read_slot = Slot::new(SlotIndex::ONE, SlotValue::from(7u64));
+        contract.slots.insert(SlotIndex::ZERO, ExecutionValueChange::from_original(original_slot));
+        contract.slots.insert(SlotIndex::ONE, ExecutionValueChange::from_original(read_slot));
+        contract.apply_modifications(contract.to_account(), vec![modified_slot]);
+
+        // existing sender whose nonce and balance changed
+        let mut alice = ExecutionAccountChanges::from_original_values(Account::new_with_balance(ALICE, Wei::from(100u64)));
+        alice.apply_modifications(
+            Account {
+                nonce: Nonce::from(1u64),
+                balance: Wei::from(90u64),
+                ..Account::new_empty(ALICE)
+            },
+            vec![],
+        );
+
+        // account created by the execution
+        let created = Address::new([9; 20]);
+        let created = ExecutionAccountChanges::from_modified_values(Account::new_with_balance(created, Wei::ONE), vec![]);
+
+        [contract, alice, created].into_iter().map(|account| (account.address, account)).collect()
+    }
+
+    #[test]
+    fn prestate_returns_original_state_of_touched_accounts() {
+        let expected = json!({
+            "0x0101010101010101010101010101010101010101": {
+                "balance": "0x64"
+            },
+            "0x0202020202020202020202020202020202020202": {
+                "balance": "0xa",
+                "nonce": 1,
+                "code": "0x6000",
+                "storage": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000000": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                    "0x0000000000000000000000000000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000007"
+                }
+            }
+        });
+        assert_eq!(serde_json::to_value(EvmTrace::from_changes(&changes(), false)).unwrap(), expected);
+    }
+
+    #[test]
+    fn prestate_diff_returns_only_modified_values() {
+        let expected = json!({
+            "pre": {
+                "0x0101010101010101010101010101010101010101": {
+                    "balance": "0x64"
+                },
+                "0x0202020202020202020202020202020202020202": {
+                    "balance": "0xa",
+                    "nonce": 1,
+                    "code": "0x6000",
+                    "storage": {
+                        "0x0000000000000000000000000000000000000000000000000000000000000000": "0x0000000000000000000000000000000000000000000000000000000000000001"
+                    }
+                }
+            },
+            "post": {
+                "0x0101010101010101010101010101010101010101": {
+                    "balance": "0x5a",
+                    "nonce": 1
+                },
+                "0x0202020202020202020202020202020202020202": {
+                    "storage": {
+                        "0x0000000000000000000000000000000000000000000000000000000000000000": "0x0000000000000000000000000000000000000000000000000000000000000005"
+                    }
+                },
+                "0x0909090909090909090909090909090909090909": {
+                    "balance": "0x1"
+                }
+            }
+        });
+        assert_eq!(serde_json::to_value(EvmTrace::from_changes(&changes(), true)).unwrap(), expected);
+    }
+}
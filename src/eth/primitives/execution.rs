@@ -12,6 +12,7 @@ use crate::eth::primitives::ExecutionAccountChanges;
 use crate::eth::primitives::ExecutionResult;
 use crate::eth::primitives::ExternalReceipt;
 use crate::eth::primitives::Gas;
+use crate::eth::primitives::InternalTransaction;
 use crate::eth::primitives::Log;
 use crate::eth::primitives::UnixTime;
 use crate::eth::primitives::Wei;
@@ -49,6 +50,10 @@ pub struct EvmExecution {
 
     /// The contract address if the executed transaction deploys a contract.
     pub deployed_contract_address: Option<Address>,
+
+    /// Calls, contract creations and self-destructs executed by contracts, recorded only if enabled in the executor.
+    #[serde(default)]
+    pub internal_transactions: Vec<InternalTransaction>,
 }
 
 impl EvmExecution {
@@ -80,6 +85,7 @@ impl EvmExecution {
             gas: receipt.gas_used.unwrap_or_default().try_into()?,
             changes: HashMap::from([(sender_changes.address, sender_changes)]),
             deployed_contract_address: None,
+            internal_transactions: Vec::new(),
         };
         execution.apply_receipt(receipt)?;
         Ok(execution)
@@ -95,6 +101,15 @@ impl EvmExecution {
         not(self.is_success())
     }
 
+    /// Returns the reason the transaction failed in the format reported by Geth in call results.
+    pub fn failure_reason(&self) -> Option<String> {
+        match self.result {
+            ExecutionResult::Success => None,
+            ExecutionResult::Reverted => Some("execution reverted".to_owned()),
+            ExecutionResult::Halted { ref reason, .. } => Some(reason.clone()),
+        }
+    }
+
     /// Returns the address of the deployed contract if the transaction is a deployment.
     pub fn contract_address(&self) -> Option<Address> {
         if let Some(contract_address) = &self.deployed_contract_address {
@@ -100,8 +100,91 @@ impl ExecutionAccountChanges {
         }
     }
 
+    /// Updates itself with the values modified by a subsequent execution, keeping the original values of the first one.
+    pub fn merge(&mut self, subsequent: &ExecutionAccountChanges) {
+        if let Some(nonce) = subsequent.nonce.take_modified_ref() {
+            self.nonce.set_modified(*nonce);
+        }
+
+        if let Some(balance) = subsequent.balance.take_modified_ref() {
+            self.balance.set_modified(*balance);
+        }
+
+        if let Some(bytecode) = subsequent.bytecode.take_modified_ref() {
+            self.bytecode.set_modified(bytecode.clone());
+            self.code_hash = subsequent.code_hash;
+        }
+
+        for (slot_index, slot) in &subsequent.slots {
+            let merged_slot = self.slots.entry(*slot_index).or_insert(slot.clone());
+            if let Some(slot_value) = slot.take_modified_ref() {
+                merged_slot.set_modified(*slot_value);
+            }
+        }
+    }
+
+    /// Returns the account with its most recent values, giving preference to modified values over original values.
+    pub fn to_account(&self) -> Account {
+        Account {
+            address: self.address,
+            nonce: self.nonce.take_ref().copied().unwrap_or_default(),
+            balance: self.balance.take_ref().copied().unwrap_or_default(),
+            bytecode: self.bytecode.take_ref().cloned().flatten(),
+            code_hash: self.code_hash,
+        }
+    }
+
     /// Checks if account nonce, balance or bytecode were modified.
     pub fn is_account_modified(&self) -> bool {
         self.nonce.is_modified() || self.balance.is_modified() || self.bytecode.is_modified()
     }
 }
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::primitives::SlotValue;
+
+    #[test]
+    fn merge_keeps_original_values_and_takes_subsequent_modifications() {
+        let address = Address::new([1; 20]);
+        let slot = |index: u64, value: u64| Slot::new(SlotIndex::from(index), SlotValue::from(value));
+
+        // first execution read the account and modified its balance and a slot
+        let mut first = ExecutionAccountChanges::from_original_values(Account::new_with_balance(address, Wei::from(100u64)));
+        first.slots.insert(SlotIndex::from(1u64), ExecutionValueChange::from_original(slot(1, 10)));
+        first.apply_modifications(Account::new_with_balance(address, Wei::from(90u64)), vec![slot(1, 11)]);
+
+        // subsequent execution modified the nonce, the same slot and a new slot, and deployed bytecode
+        let mut subsequent = ExecutionAccountChanges::from_original_values(Account::new_with_balance(address, Wei::from(90u64)));
+        subsequent.apply_modifications(
+            Account {
+                nonce: Nonce::from(1u64),
+                ..Account::new_with_balance(address, Wei::from(90u64))
+            },
+            vec![slot(1, 12), slot(2, 20)],
+        );
+        let bytecode = Some(Bytes(vec![0x60, 0x00]));
+        subsequent.bytecode.set_modified(bytecode.clone());
+        subsequent.code_hash = CodeHash::from_bytecode(bytecode.clone());
+
+        first.merge(&subsequent);
+
+        // original values come from the first execution
+        assert_eq!(first.balance.take_original_ref(), Some(&Wei::from(100u64)));
+        assert_eq!(first.nonce.take_original_ref(), Some(&Nonce::ZERO));
+        assert_eq!(first.slots[&SlotIndex::from(1u64)].take_original_ref(), Some(&slot(1, 10)));
+
+        // modified values are the latest ones
+        assert_eq!(first.balance.take_modified_ref(), Some(&Wei::from(90u64)));
+        assert_eq!(first.nonce.take_modified_ref(), Some(&Nonce::from(1u64)));
+        assert_eq!(first.slots[&SlotIndex::from(1u64)].take_modified_ref(), Some(&slot(1, 12)));
+        assert_eq!(first.slots[&SlotIndex::from(2u64)].take_modified_ref(), Some(&slot(2, 20)));
+        assert_eq!(first.bytecode.take_modified_ref(), Some(&bytecode));
+        assert_eq!(first.code_hash, CodeHash::from_bytecode(bytecode));
+    }
+}
@@ -14,5 +14,13 @@ pub enum ExecutionResult {
 
     /// Transaction execution did not finish.
     #[strum(to_string = "halted")]
-    Halted { reason: String },
+    Halted {
+        reason: String,
+
+        /// Indicates the execution halted because it ran out of gas.
+        ///
+        /// Not persisted by all storages, so it is only reliable for executions that were not read from storage.
+        #[serde(default)]
+        out_of_gas: bool,
+    },
 }
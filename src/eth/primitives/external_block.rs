@@ -22,6 +22,11 @@ impl ExternalBlock {
         self.0.hash.expect("external block must have hash").into()
     }
 
+    /// Returns the hash of the previous block.
+    pub fn parent_hash(&self) -> Hash {
+        self.0.parent_hash.into()
+    }
+
     /// Returns the block number.
     pub fn number(&self) -> BlockNumber {
         self.0.number.expect("external block must have number").into()
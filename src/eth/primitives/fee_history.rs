@@ -0,0 +1,82 @@
+use display_json::DebugAsJson;
+
+use crate::eth::primitives::Block;
+use crate::eth::primitives::BlockNumber;
+use crate::eth::primitives::Wei;
+
+/// Base fees, gas usage and priority fees of a range of blocks.
+///
+/// Returned by `eth_feeHistory` in the same format used by Geth.
+#[derive(DebugAsJson, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeHistory {
+    /// Lowest block number in the range.
+    pub oldest_block: BlockNumber,
+
+    /// Base fee of each block in the range, including the base fee of the block that follows the newest one.
+    pub base_fee_per_gas: Vec<Wei>,
+
+    /// Ratio between gas used and gas limit of each block in the range.
+    pub gas_used_ratio: Vec<f64>,
+
+    /// Priority fees paid at the requested percentiles of gas used in each block in the range.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reward: Option<Vec<Vec<Wei>>>,
+}
+
+impl FeeHistory {
+    /// Creates the fee history of the specified blocks ordered from the oldest to the newest.
+    ///
+    /// Rewards are calculated only if `reward_percentiles` are specified.
+    pub fn new(oldest_block: BlockNumber, blocks: &[Block], next_base_fee: Wei, reward_percentiles: Option<&[f64]>) -> Self {
+        let mut base_fee_per_gas: Vec<Wei> = blocks.iter().map(|block| block.header.base_fee_per_gas).collect();
+        base_fee_per_gas.push(next_base_fee);
+
+        let gas_used_ratio = blocks
+            .iter()
+            .map(|block| match block.header.reported_gas_limit().as_u64() {
+                0 => 0.0,
+                gas_limit => block.header.gas_used.as_u64() as f64 / gas_limit as f64,
+            })
+            .collect();
+
+        let reward = reward_percentiles.map(|percentiles| blocks.iter().map(|block| block_rewards(block, percentiles)).collect());
+
+        Self {
+            oldest_block,
+            base_fee_per_gas,
+            gas_used_ratio,
+            reward,
+        }
+    }
+}
+
+/// Calculates the priority fees paid at the specified percentiles of gas used in the block.
+///
+/// Transactions are sorted by priority fee and weighted by the gas they used.
+fn block_rewards(block: &Block, percentiles: &[f64]) -> Vec<Wei> {
+    let base_fee = block.header.base_fee_per_gas;
+    let mut txs: Vec<(Wei, u64)> = block
+        .transactions
+        .iter()
+        .map(|tx| (tx.input.effective_priority_fee(base_fee), tx.execution.gas.as_u64()))
+        .collect();
+    if txs.is_empty() {
+        return vec![Wei::ZERO; percentiles.len()];
+    }
+    txs.sort_by_key(|(priority_fee, _)| *priority_fee);
+
+    let block_gas_used: u64 = txs.iter().map(|(_, gas_used)| gas_used).sum();
+    let mut rewards = Vec::with_capacity(percentiles.len());
+    let mut tx_index = 0;
+    let mut sum_gas_used = txs[0].1;
+    for percentile in percentiles {
+        let threshold_gas_used = (block_gas_used as f64 * percentile / 100.0) as u64;
+        while sum_gas_used < threshold_gas_used && tx_index < txs.len() - 1 {
+            tx_index += 1;
+            sum_gas_used += txs[tx_index].1;
+        }
+        rewards.push(txs[tx_index].0);
+    }
+    rewards
+}
@@ -0,0 +1,121 @@
+use std::cmp::max;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use ethereum_types::U256;
+
+use crate::eth::primitives::BlockHeader;
+use crate::eth::primitives::Wei;
+use crate::ext::not;
+
+/// Base fee of the first block mined after the fee market is enabled (1 gwei).
+const INITIAL_BASE_FEE: Wei = Wei(U256([1_000_000_000, 0, 0, 0]));
+
+/// Priority fee suggested to clients when the fee market is enabled (1 gwei).
+const SUGGESTED_PRIORITY_FEE: Wei = Wei(U256([1_000_000_000, 0, 0, 0]));
+
+/// Bounds the amount the base fee can change between blocks (12.5%).
+const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
+
+/// Bounds the maximum gas limit a block may have relative to its gas target.
+const ELASTICITY_MULTIPLIER: u64 = 2;
+
+/// Defines how the gas consumed by transactions is priced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::EnumIs, serde::Serialize)]
+pub enum FeeMarket {
+    /// Gas is free: blocks have zero base fee and transactions execute without paying for gas.
+    #[serde(rename = "zero")]
+    Zero,
+
+    /// Base fee is adjusted on every block according to EIP-1559 and transactions pay the base fee plus a priority fee.
+    ///
+    /// See <https://eips.ethereum.org/EIPS/eip-1559>.
+    #[serde(rename = "eip1559")]
+    Eip1559,
+}
+
+impl FeeMarket {
+    /// Calculates the base fee of the block that follows the specified parent block.
+    ///
+    /// The initial base fee is used when there is no parent or the parent was mined without the fee market.
+    pub fn next_base_fee(&self, parent: Option<&BlockHeader>) -> Wei {
+        let parent = match self {
+            Self::Zero => return Wei::ZERO,
+            Self::Eip1559 => match parent {
+                Some(parent) if not(parent.base_fee_per_gas.is_zero()) => parent,
+                _ => return INITIAL_BASE_FEE,
+            },
+        };
+
+        let parent_base_fee = parent.base_fee_per_gas.0;
+        let parent_gas_used = U256::from(parent.gas_used.as_u64());
+        let parent_gas_target = U256::from(max(parent.gas_limit.as_u64() / ELASTICITY_MULTIPLIER, 1));
+
+        if parent_gas_used > parent_gas_target {
+            let delta = parent_base_fee * (parent_gas_used - parent_gas_target) / parent_gas_target / BASE_FEE_MAX_CHANGE_DENOMINATOR;
+            Wei(parent_base_fee + max(delta, U256::one()))
+        } else {
+            let delta = parent_base_fee * (parent_gas_target - parent_gas_used) / parent_gas_target / BASE_FEE_MAX_CHANGE_DENOMINATOR;
+            Wei(parent_base_fee.saturating_sub(delta))
+        }
+    }
+
+    /// Priority fee per gas that clients should pay to have their transactions included in a block.
+    pub fn suggested_priority_fee(&self) -> Wei {
+        match self {
+            Self::Zero => Wei::ZERO,
+            Self::Eip1559 => SUGGESTED_PRIORITY_FEE,
+        }
+    }
+}
+
+impl FromStr for FeeMarket {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "zero" => Ok(Self::Zero),
+            "eip1559" | "eip-1559" => Ok(Self::Eip1559),
+            s => Err(anyhow!("unknown fee market: {}", s)),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::primitives::BlockNumber;
+    use crate::eth::primitives::UnixTime;
+
+    fn parent(base_fee: u64, gas_used: u64) -> BlockHeader {
+        let mut header = BlockHeader::new(BlockNumber::ONE, UnixTime::from(1234567890));
+        header.base_fee_per_gas = base_fee.into();
+        header.gas_used = gas_used.into();
+        header.gas_limit = 100_000u64.into();
+        header
+    }
+
+    #[test]
+    fn fee_market_zero_never_charges() {
+        assert_eq!(FeeMarket::Zero.next_base_fee(Some(&parent(1_000, 100_000))), Wei::ZERO);
+        assert_eq!(FeeMarket::Zero.suggested_priority_fee(), Wei::ZERO);
+    }
+
+    #[test]
+    fn fee_market_eip1559_starts_from_initial_base_fee() {
+        assert_eq!(FeeMarket::Eip1559.next_base_fee(None), INITIAL_BASE_FEE);
+        assert_eq!(FeeMarket::Eip1559.next_base_fee(Some(&parent(0, 100_000))), INITIAL_BASE_FEE);
+    }
+
+    #[test]
+    fn fee_market_eip1559_adjusts_to_gas_target() {
+        let market = FeeMarket::Eip1559;
+        assert_eq!(market.next_base_fee(Some(&parent(1_000, 50_000))), Wei::from(1_000u64));
+        assert_eq!(market.next_base_fee(Some(&parent(1_000, 100_000))), Wei::from(1_125u64));
+        assert_eq!(market.next_base_fee(Some(&parent(1_000, 0))), Wei::from(875u64));
+        assert_eq!(market.next_base_fee(Some(&parent(1, 50_001))), Wei::from(2u64));
+    }
+}
@@ -16,6 +16,9 @@ impl Gas {
     pub const ZERO: Gas = Gas(U64::zero());
     pub const MAX: Gas = Gas(U64::MAX);
 
+    /// Gas limit of blocks mined by Stratus.
+    pub const BLOCK_LIMIT: Gas = Gas(U64([100_000_000]));
+
     pub fn as_u64(&self) -> u64 {
         self.0.as_u64()
     }
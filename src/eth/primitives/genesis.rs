@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use anyhow::Context;
+use display_json::DebugAsJson;
+use ethereum_types::U256;
+use serde::Deserialize;
+
+use crate::eth::primitives::Account;
+use crate::eth::primitives::Address;
+use crate::eth::primitives::Block;
+use crate::eth::primitives::BlockNumber;
+use crate::eth::primitives::Bytes;
+use crate::eth::primitives::CodeHash;
+use crate::eth::primitives::ExecutionAccountChanges;
+use crate::eth::primitives::Hash;
+use crate::eth::primitives::Nonce;
+use crate::eth::primitives::Slot;
+use crate::eth::primitives::SlotIndex;
+use crate::eth::primitives::SlotValue;
+use crate::eth::primitives::UnixTime;
+use crate::eth::primitives::Wei;
+use crate::eth::storage::update_state_trie;
+use crate::ext::not;
+
+/// Initial state of the chain in the geth genesis file format.
+///
+/// Only the fields used to build the genesis block and accounts are read. Other fields, like the chain config, are ignored.
+#[derive(DebugAsJson, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Genesis {
+    /// Timestamp of the genesis block.
+    #[serde(default, deserialize_with = "deserialize_quantity")]
+    pub timestamp: UnixTime,
+
+    /// Extra data of the genesis block.
+    #[serde(default)]
+    pub extra_data: Bytes,
+
+    /// Accounts that exist at the genesis block.
+    #[serde(default)]
+    pub alloc: HashMap<Address, GenesisAccount>,
+}
+
+/// Account that exists at the genesis block.
+#[derive(DebugAsJson, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct GenesisAccount {
+    #[serde(default, deserialize_with = "deserialize_quantity")]
+    pub balance: Wei,
+
+    #[serde(default, deserialize_with = "deserialize_quantity")]
+    pub nonce: Nonce,
+
+    #[serde(default)]
+    pub code: Option<Bytes>,
+
+    #[serde(default)]
+    pub storage: HashMap<SlotIndex, SlotValue>,
+}
+
+impl Genesis {
+    /// Reads a genesis file.
+    pub fn from_file(path: &str) -> anyhow::Result<Self> {
+        tracing::info!(%path, "reading genesis file");
+        let json = std::fs::read_to_string(path).with_context(|| format!("failed to read genesis file {}", path))?;
+        serde_json::from_str(&json).with_context(|| format!("failed to parse genesis file {}", path))
+    }
+
+    /// Creates the genesis block, including the state root of the genesis accounts.
+    pub fn to_block(&self) -> anyhow::Result<Block> {
+        let state_trie = update_state_trie(&HashMap::<Hash, Vec<u8>>::new(), Hash::EMPTY_TRIE, &self.account_changes())?;
+
+        let mut block = Block::new(BlockNumber::ZERO, self.timestamp);
+        block.header.extra_data = self.extra_data.clone();
+        block.header.state_root = state_trie.root;
+        block.header.hash = block.header.compute_hash();
+        Ok(block)
+    }
+
+    /// Creates the changes that create the genesis accounts with their slots.
+    pub fn account_changes(&self) -> Vec<ExecutionAccountChanges> {
+        self.alloc
+            .iter()
+            .map(|(address, genesis_account)| {
+                // empty bytecode is an account without bytecode
+                let bytecode = genesis_account.code.clone().filter(|code| not(code.is_empty()));
+                let account = Account {
+                    address: *address,
+                    nonce: genesis_account.nonce,
+                    balance: genesis_account.balance,
+                    code_hash: CodeHash::from_bytecode(bytecode.clone()),
+                    bytecode,
+                };
+                let slots = genesis_account.storage.iter().map(|(index, value)| Slot::new(*index, *value)).collect();
+                ExecutionAccountChanges::from_modified_values(account, slots)
+            })
+            .collect()
+    }
+}
+
+/// Deserializes a quantity as a hexadecimal string prefixed with `0x`, a decimal string or a number, like geth does.
+fn deserialize_quantity<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: TryFrom<U256>,
+    T::Error: Display,
+{
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum Quantity {
+        String(String),
+        Number(u64),
+    }
+
+    let value = match Quantity::deserialize(deserializer)? {
+        Quantity::Number(number) => U256::from(number),
+        Quantity::String(s) => match s.strip_prefix("0x") {
+            Some(hex) => U256::from_str_radix(hex, 16).map_err(serde::de::Error::custom)?,
+            None => U256::from_dec_str(&s).map_err(serde::de::Error::custom)?,
+        },
+    };
+    T::try_from(value).map_err(serde::de::Error::custom)
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GENESIS: &str = r#"{
+        "config": { "chainId": 2008 },
+        "timestamp": "0x10",
+        "extraData": "0x0102",
+        "gasLimit": "0x1c9c380",
+        "alloc": {
+            "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266": { "balance": "1000" },
+            "0xca11bde05977b3631167028862be2a173976ca11": {
+                "balance": "0x0",
+                "nonce": "0x1",
+                "code": "0x6000",
+                "storage": { "0x1": "0x02" }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn genesis_parses_geth_format() {
+        let genesis: Genesis = serde_json::from_str(GENESIS).unwrap();
+        assert_eq!(genesis.timestamp, UnixTime::from(16u64));
+        assert_eq!(genesis.extra_data, Bytes(vec![1, 2]));
+
+        let alice: Address = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266".parse().unwrap();
+        assert_eq!(genesis.alloc[&alice].balance, Wei::from(U256::from(1000u64)));
+
+        let multicall: Address = "0xca11bde05977b3631167028862be2a173976ca11".parse().unwrap();
+        let multicall = &genesis.alloc[&multicall];
+        assert_eq!(multicall.nonce, Nonce::from(1u64));
+        assert_eq!(multicall.code, Some(Bytes(vec![0x60, 0x00])));
+        assert_eq!(multicall.storage[&SlotIndex::ONE], SlotValue::from(2u64));
+    }
+
+    #[test]
+    fn genesis_block_commits_to_accounts() {
+        let genesis: Genesis = serde_json::from_str(GENESIS).unwrap();
+        let block = genesis.to_block().unwrap();
+        assert_eq!(block.number(), BlockNumber::ZERO);
+        assert_eq!(block.header.timestamp, genesis.timestamp);
+        assert_ne!(block.header.state_root, Hash::EMPTY_TRIE);
+        assert_eq!(block.hash(), block.header.compute_hash());
+
+        let mut changed = genesis.clone();
+        changed.alloc.values_mut().for_each(|account| account.balance = Wei::ONE);
+        assert_ne!(changed.to_block().unwrap().hash(), block.hash());
+    }
+}
@@ -0,0 +1,155 @@
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use revm::primitives::SpecId;
+
+use crate::eth::primitives::BlockNumber;
+use crate::ext::not;
+
+/// Set of EVM rules (opcodes, gas costs and validations) a block is executed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, strum::Display, serde::Serialize)]
+pub enum Hardfork {
+    /// Rules used by Stratus before the hardfork schedule was configurable.
+    #[serde(rename = "london")]
+    #[strum(to_string = "london")]
+    London,
+
+    /// Adds `PUSH0` and limits initcode size.
+    #[serde(rename = "shanghai")]
+    #[strum(to_string = "shanghai")]
+    Shanghai,
+
+    /// Adds `TLOAD`, `TSTORE`, `MCOPY` and `BLOBBASEFEE`.
+    #[serde(rename = "cancun")]
+    #[strum(to_string = "cancun")]
+    Cancun,
+}
+
+impl FromStr for Hardfork {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "london" => Ok(Self::London),
+            "shanghai" => Ok(Self::Shanghai),
+            "cancun" => Ok(Self::Cancun),
+            s => Err(anyhow!("unknown hardfork: {}", s)),
+        }
+    }
+}
+
+impl From<Hardfork> for SpecId {
+    fn from(value: Hardfork) -> Self {
+        match value {
+            Hardfork::London => SpecId::LONDON,
+            Hardfork::Shanghai => SpecId::SHANGHAI,
+            Hardfork::Cancun => SpecId::CANCUN,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Schedule
+// -----------------------------------------------------------------------------
+
+/// Block numbers where each hardfork is activated.
+///
+/// Blocks before the first activation are executed with [`Hardfork::London`], so historical blocks keep being
+/// re-executed with the rules they were originally produced with.
+///
+/// Parsed from a comma-separated list of `hardfork:block` entries, like `london:0,shanghai:1000,cancun:2000`.
+/// A hardfork without block is activated at genesis.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct HardforkSchedule(Vec<(BlockNumber, Hardfork)>);
+
+impl HardforkSchedule {
+    /// Returns the hardfork active at the specified block.
+    pub fn hardfork_at(&self, number: BlockNumber) -> Hardfork {
+        self.0
+            .iter()
+            .rev()
+            .find(|(activation, _)| *activation <= number)
+            .map(|(_, hardfork)| *hardfork)
+            .unwrap_or(Hardfork::London)
+    }
+
+    /// Returns the revm specification active at the specified block.
+    pub fn spec_at(&self, number: BlockNumber) -> SpecId {
+        self.hardfork_at(number).into()
+    }
+}
+
+impl Default for HardforkSchedule {
+    fn default() -> Self {
+        Self(vec![(BlockNumber::ZERO, Hardfork::London)])
+    }
+}
+
+impl FromStr for HardforkSchedule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut schedule = Vec::new();
+        for entry in s.split(',').map(str::trim).filter(|entry| not(entry.is_empty())) {
+            let (hardfork, activation) = match entry.split_once(':') {
+                Some((hardfork, activation)) => (hardfork.parse::<Hardfork>()?, BlockNumber::from(activation.trim().parse::<u64>()?)),
+                None => (entry.parse::<Hardfork>()?, BlockNumber::ZERO),
+            };
+            schedule.push((activation, hardfork));
+        }
+        if schedule.is_empty() {
+            return Err(anyhow!("hardfork schedule must have at least one hardfork"));
+        }
+
+        // activations must follow the order hardforks were released
+        for window in schedule.windows(2) {
+            let ((prev_activation, prev_hardfork), (activation, hardfork)) = (window[0], window[1]);
+            if activation <= prev_activation || hardfork <= prev_hardfork {
+                return Err(anyhow!(
+                    "hardfork {} at block {} must come after hardfork {} at block {}",
+                    hardfork,
+                    activation,
+                    prev_hardfork,
+                    prev_activation
+                ));
+            }
+        }
+
+        Ok(Self(schedule))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hardfork_schedule_selects_active_hardfork() {
+        let schedule: HardforkSchedule = "shanghai:10,cancun:20".parse().unwrap();
+        assert_eq!(schedule.hardfork_at(BlockNumber::ZERO), Hardfork::London);
+        assert_eq!(schedule.hardfork_at(9u64.into()), Hardfork::London);
+        assert_eq!(schedule.hardfork_at(10u64.into()), Hardfork::Shanghai);
+        assert_eq!(schedule.hardfork_at(19u64.into()), Hardfork::Shanghai);
+        assert_eq!(schedule.hardfork_at(20u64.into()), Hardfork::Cancun);
+        assert_eq!(schedule.spec_at(1_000u64.into()), SpecId::CANCUN);
+    }
+
+    #[test]
+    fn hardfork_schedule_without_block_activates_at_genesis() {
+        let schedule: HardforkSchedule = "cancun".parse().unwrap();
+        assert_eq!(schedule.hardfork_at(BlockNumber::ZERO), Hardfork::Cancun);
+        assert_eq!(HardforkSchedule::default().hardfork_at(1_000u64.into()), Hardfork::London);
+    }
+
+    #[test]
+    fn hardfork_schedule_rejects_invalid_order() {
+        assert!("cancun:10,shanghai:20".parse::<HardforkSchedule>().is_err());
+        assert!("shanghai:20,cancun:10".parse::<HardforkSchedule>().is_err());
+        assert!("shanghai:10,cancun:10".parse::<HardforkSchedule>().is_err());
+        assert!("".parse::<HardforkSchedule>().is_err());
+        assert!("prague:0".parse::<HardforkSchedule>().is_err());
+    }
+}
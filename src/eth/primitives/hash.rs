@@ -6,6 +6,7 @@ use display_json::DebugAsJson;
 use ethereum_types::H256;
 use fake::Dummy;
 use fake::Faker;
+use hex_literal::hex;
 use sqlx::encode::IsNull;
 use sqlx::postgres::PgHasArrayType;
 
@@ -18,6 +19,9 @@ pub struct Hash(pub H256);
 impl Hash {
     pub const ZERO: Hash = Hash(H256::zero());
 
+    /// Root of an empty Merkle-Patricia trie.
+    pub const EMPTY_TRIE: Hash = Hash::new(hex!("56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"));
+
     /// Creates a hash from the given bytes.
     pub const fn new(bytes: [u8; 32]) -> Self {
         Self(H256(bytes))
@@ -0,0 +1,110 @@
+use display_json::DebugAsJson;
+use serde_json::json;
+
+use crate::alias::JsonValue;
+use crate::eth::primitives::Address;
+use crate::eth::primitives::Bytes;
+use crate::eth::primitives::CallKind;
+use crate::eth::primitives::Wei;
+use crate::ext::not;
+
+/// Call, contract creation or self-destruct executed by a contract during the execution of a transaction.
+///
+/// Only operations that were not reverted are kept, because their state changes and value transfers were discarded.
+#[derive(DebugAsJson, Clone, PartialEq, Eq, fake::Dummy, serde::Serialize, serde::Deserialize)]
+pub struct InternalTransaction {
+    /// Kind of the operation.
+    #[serde(rename = "type")]
+    pub kind: CallKind,
+
+    /// Contract that executed the operation.
+    pub from: Address,
+
+    /// Called contract, created contract or self-destruct beneficiary.
+    pub to: Address,
+
+    /// Native value transferred from `from` to `to`.
+    pub value: Wei,
+
+    /// First four bytes of the call input identifying the called function, or empty if the input is shorter.
+    pub selector: Bytes,
+
+    /// Depth of the call frame where the operation was executed, starting at 1 for operations of the contract called by the transaction.
+    pub depth: usize,
+}
+
+impl InternalTransaction {
+    /// Creates a call with the function selector extracted from the call input.
+    pub fn new(kind: CallKind, from: Address, to: Address, value: Wei, input: &[u8], depth: usize) -> Self {
+        let selector = match input.get(..4) {
+            Some(selector) => Bytes(selector.to_vec()),
+            None => Bytes::default(),
+        };
+        Self {
+            kind,
+            from,
+            to,
+            value,
+            selector,
+            depth,
+        }
+    }
+
+    /// Serializes the operation in the format returned by Otterscan `ots_getInternalOperations`.
+    ///
+    /// Returns `None` for calls that did not transfer value because Otterscan only displays value transfers.
+    pub fn to_ots_operation(&self) -> Option<JsonValue> {
+        let kind = match self.kind {
+            CallKind::Call | CallKind::CallCode | CallKind::DelegateCall | CallKind::StaticCall if not(self.value.is_zero()) => 0,
+            CallKind::SelfDestruct => 1,
+            CallKind::Create => 2,
+            CallKind::Create2 => 3,
+            _ => return None,
+        };
+        Some(json!({
+            "type": kind,
+            "from": self.from,
+            "to": self.to,
+            "value": self.value,
+        }))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use fake::Fake;
+    use fake::Faker;
+
+    use super::*;
+
+    #[test]
+    fn internal_transaction_selector() {
+        let call = InternalTransaction::new(CallKind::Call, Faker.fake(), Faker.fake(), Wei::ZERO, &[1, 2, 3, 4, 5], 1);
+        assert_eq!(call.selector, Bytes(vec![1, 2, 3, 4]));
+
+        let transfer = InternalTransaction::new(CallKind::Call, Faker.fake(), Faker.fake(), Wei::ONE, &[], 1);
+        assert_eq!(transfer.selector, Bytes::default());
+    }
+
+    #[test]
+    fn internal_transaction_ots_operation() {
+        let call = InternalTransaction::new(CallKind::Call, Faker.fake(), Faker.fake(), Wei::ZERO, &[1, 2, 3, 4], 1);
+        assert!(call.to_ots_operation().is_none());
+
+        let transfer = InternalTransaction {
+            value: Wei::ONE,
+            ..call.clone()
+        };
+        assert_eq!(transfer.to_ots_operation().unwrap()["type"], 0);
+
+        let create = InternalTransaction {
+            kind: CallKind::Create2,
+            ..call
+        };
+        assert_eq!(create.to_ots_operation().unwrap()["type"], 3);
+    }
+}
@@ -1,4 +1,5 @@
 use display_json::DebugAsJson;
+use itertools::Itertools;
 
 use crate::alias::EthersLog;
 use crate::alias::RevmLog;
@@ -112,3 +113,17 @@ impl From<EthersLog> for Log {
         log
     }
 }
+
+// -----------------------------------------------------------------------------
+// Conversions: Self -> Other
+// -----------------------------------------------------------------------------
+impl From<Log> for EthersLog {
+    fn from(value: Log) -> Self {
+        Self {
+            address: value.address.into(),
+            topics: value.topics_non_empty().into_iter().map_into().collect_vec(),
+            data: value.data.into(),
+            ..Default::default()
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use display_json::DebugAsJson;
+use ethereum_types::BloomInput;
 
+use crate::eth::primitives::logs_bloom::LogsBloom;
 use crate::eth::primitives::Address;
 use crate::eth::primitives::BlockNumber;
 use crate::eth::primitives::LogFilterInput;
 use crate::eth::primitives::LogMined;
+use crate::eth::primitives::LogTopic;
 use crate::ext::not;
 
 #[derive(Clone, DebugAsJson, serde::Serialize, Eq, Hash, PartialEq)]
@@ -20,6 +23,36 @@ pub struct LogFilter {
 }
 
 impl LogFilter {
+    /// Returns the topics accepted in each position, or `None` for positions that accept anything.
+    fn topics_by_position(&self) -> impl Iterator<Item = Option<Vec<LogTopic>>> + '_ {
+        self.original_input.topics.iter().map(|filter_topic| {
+            if filter_topic.is_empty() || filter_topic.contains(&None) {
+                None
+            } else {
+                Some(filter_topic.iter().flatten().copied().collect())
+            }
+        })
+    }
+
+    /// Returns the topics accepted in the first position, or `None` if the first position accepts anything.
+    pub fn first_topics(&self) -> Option<Vec<LogTopic>> {
+        self.topics_by_position().next().flatten()
+    }
+
+    /// Checks if a block with the given bloom can contain logs matching the filter.
+    ///
+    /// Blooms have false positives, so matching blocks still need to have their logs checked.
+    pub fn matches_bloom(&self, bloom: &LogsBloom) -> bool {
+        let has_addresses = not(self.addresses.is_empty());
+        if has_addresses && not(self.addresses.iter().any(|address| bloom.contains_input(BloomInput::Raw(address.as_ref())))) {
+            return false;
+        }
+
+        self.topics_by_position()
+            .flatten()
+            .all(|topics| topics.iter().any(|topic| bloom.contains_input(BloomInput::Raw(topic.as_ref()))))
+    }
+
     /// Checks if a log matches the filter.
     pub fn matches(&self, log: &LogMined) -> bool {
         // filter block range
@@ -184,6 +217,44 @@ mod tests {
         assert!(not(filter.matches(&log_with_topics([None, None, None, None]))));
     }
 
+    #[test]
+    fn log_filtering_by_bloom() {
+        let addresses = fake_list::<Address>(2);
+        let topics = fake_list::<LogTopic>(3);
+
+        let mut bloom = LogsBloom::default();
+        bloom.accrue_log(&Log {
+            address: addresses[0],
+            topic0: Some(topics[0]),
+            topic1: Some(topics[1]),
+            topic2: None,
+            topic3: None,
+            ..fake_first::<LogMined>().log
+        });
+
+        assert!(build_filter(vec![], vec![]).matches_bloom(&bloom));
+        assert!(build_filter(vec![addresses[1], addresses[0]], vec![]).matches_bloom(&bloom));
+        assert!(build_filter(vec![addresses[0]], vec![vec![Some(topics[0])], vec![None]]).matches_bloom(&bloom));
+        assert!(build_filter(vec![], vec![vec![Some(topics[2]), Some(topics[1])]]).matches_bloom(&bloom));
+
+        assert!(not(build_filter(vec![addresses[1]], vec![]).matches_bloom(&bloom)));
+        assert!(not(
+            build_filter(vec![addresses[0]], vec![vec![None], vec![Some(topics[2])]]).matches_bloom(&bloom)
+        ));
+    }
+
+    #[test]
+    fn log_filtering_first_topics() {
+        let topics = fake_list::<LogTopic>(2);
+
+        let filter = build_filter(vec![], vec![vec![Some(topics[0]), Some(topics[1])], vec![None]]);
+        assert_eq!(filter.first_topics(), Some(topics.clone()));
+
+        assert_eq!(build_filter(vec![], vec![]).first_topics(), None);
+        assert_eq!(build_filter(vec![], vec![vec![None], vec![Some(topics[0])]]).first_topics(), None);
+        assert_eq!(build_filter(vec![], vec![vec![Some(topics[0]), None]]).first_topics(), None);
+    }
+
     #[test]
     fn log_filtering_by_address() {
         let addresses = fake_list::<Address>(4);
@@ -2,6 +2,8 @@ use std::ops::Deref;
 use std::ops::DerefMut;
 
 use ethereum_types::Bloom;
+use fake::Dummy;
+use fake::Faker;
 
 use crate::eth::primitives::Log;
 use crate::gen_newtype_from;
@@ -41,6 +43,12 @@ impl DerefMut for LogsBloom {
     }
 }
 
+impl Dummy<Faker> for LogsBloom {
+    fn dummy_with_rng<R: ethers_core::rand::prelude::Rng + ?Sized>(faker: &Faker, rng: &mut R) -> Self {
+        <[u8; 256]>::dummy_with_rng(faker, rng).into()
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Conversions: Other -> Self
 // -----------------------------------------------------------------------------
@@ -1,9 +1,13 @@
+mod access_list;
 mod account;
+mod account_proof;
 mod address;
 mod block;
 mod block_filter;
 mod block_header;
 mod block_number;
+mod block_override;
+mod block_search;
 pub mod bytes;
 mod call_input;
 mod chain_id;
@@ -11,6 +15,7 @@ mod code_hash;
 mod difficulty;
 mod ecdsa_rs;
 mod ecdsa_v;
+mod evm_trace;
 mod execution;
 mod execution_account_changes;
 mod execution_conflict;
@@ -21,9 +26,14 @@ mod external_block;
 mod external_receipt;
 mod external_receipts;
 mod external_transaction;
+mod fee_history;
+mod fee_market;
 mod gas;
+mod genesis;
+mod hardfork;
 mod hash;
 mod index;
+mod internal_transaction;
 mod log;
 mod log_filter;
 mod log_filter_input;
@@ -35,11 +45,17 @@ mod nonce;
 mod now;
 mod pending_block;
 mod pending_block_header;
+mod pending_transaction_event;
+mod revert_reason;
+mod simulated_call;
 mod size;
 mod slot;
 mod slot_index;
 mod slot_value;
+mod state_diff;
+mod state_override;
 mod stratus_error;
+mod tracer_options;
 mod transaction_execution;
 mod transaction_input;
 mod transaction_mined;
@@ -47,13 +63,19 @@ mod transaction_stage;
 mod unix_time;
 mod wei;
 
+pub use access_list::AccessListItem;
+pub use access_list::AccessListWithGasUsed;
 pub use account::test_accounts;
 pub use account::Account;
+pub use account_proof::AccountProof;
+pub use account_proof::StorageProof;
 pub use address::Address;
 pub use block::Block;
 pub use block_filter::BlockFilter;
 pub use block_header::BlockHeader;
 pub use block_number::BlockNumber;
+pub use block_override::BlockOverride;
+pub use block_search::BlockSearch;
 pub use bytes::Bytes;
 pub use call_input::CallInput;
 pub use chain_id::ChainId;
@@ -61,6 +83,11 @@ pub use code_hash::CodeHash;
 pub use difficulty::Difficulty;
 pub use ecdsa_rs::EcdsaRs;
 pub use ecdsa_v::EcdsaV;
+pub use evm_trace::CallFrame;
+pub use evm_trace::CallFrameLog;
+pub use evm_trace::CallKind;
+pub use evm_trace::EvmTrace;
+pub use evm_trace::PrestateAccount;
 pub use execution::EvmExecution;
 pub use execution::ExecutionChanges;
 pub use execution_account_changes::ExecutionAccountChanges;
@@ -74,9 +101,16 @@ pub use external_block::ExternalBlock;
 pub use external_receipt::ExternalReceipt;
 pub use external_receipts::ExternalReceipts;
 pub use external_transaction::ExternalTransaction;
+pub use fee_history::FeeHistory;
+pub use fee_market::FeeMarket;
 pub use gas::Gas;
+pub use genesis::Genesis;
+pub use genesis::GenesisAccount;
+pub use hardfork::Hardfork;
+pub use hardfork::HardforkSchedule;
 pub use hash::Hash;
 pub use index::Index;
+pub use internal_transaction::InternalTransaction;
 pub use log::Log;
 pub use log_filter::LogFilter;
 pub use log_filter_input::LogFilterInput;
@@ -88,11 +122,23 @@ pub use nonce::Nonce;
 pub use now::DateTimeNow;
 pub use pending_block::PendingBlock;
 pub use pending_block_header::PendingBlockHeader;
+pub use pending_transaction_event::PendingTransactionEvent;
+pub use revert_reason::decode_revert_reason;
+pub use simulated_call::SimulatedCall;
+pub use simulated_call::SimulationOptions;
 pub use size::Size;
 pub use slot::Slot;
 pub use slot_index::SlotIndex;
 pub use slot_value::SlotValue;
+pub use state_diff::AccountStateDiff;
+pub use state_diff::StateDiff;
+pub use state_diff::ValueDiff;
+pub use state_override::AccountOverride;
+pub use state_override::StateOverride;
 pub use stratus_error::StratusError;
+pub use tracer_options::TracerConfig;
+pub use tracer_options::TracerOptions;
+pub use tracer_options::TracerType;
 pub use transaction_execution::ExternalTransactionExecution;
 pub use transaction_execution::LocalTransactionExecution;
 pub use transaction_execution::TransactionExecution;
@@ -163,6 +209,8 @@ mod tests {
     gen_test_serde!(Slot);
     gen_test_serde!(SlotIndex);
     gen_test_serde!(SlotValue);
+    gen_test_serde!(TracerConfig);
+    gen_test_serde!(TracerOptions);
     gen_test_serde!(TransactionExecutionValueChangeBytes);
     gen_test_serde!(TransactionExecutionValueChangeNonce);
     gen_test_serde!(TransactionExecutionValueChangeOptionString);
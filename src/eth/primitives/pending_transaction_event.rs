@@ -0,0 +1,39 @@
+use display_json::DebugAsJson;
+use jsonrpsee::SubscriptionMessage;
+
+use crate::eth::primitives::Hash;
+
+/// Change in the set of transactions waiting to be mined.
+#[derive(DebugAsJson, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PendingTransactionEvent {
+    /// Transaction was executed and added to the pending block.
+    Added { hash: Hash },
+
+    /// Queued or pending transaction was discarded.
+    ///
+    /// It is usually replaced by another transaction with the same sender and nonce, but it may also be lost when it cannot be restored after its replacement fails.
+    Dropped {
+        hash: Hash,
+        #[serde(rename = "replacedBy")]
+        replaced_by: Option<Hash>,
+    },
+}
+
+impl PendingTransactionEvent {
+    /// Returns the hash of the added or dropped transaction.
+    pub fn hash(&self) -> Hash {
+        match self {
+            Self::Added { hash } => *hash,
+            Self::Dropped { hash, .. } => *hash,
+        }
+    }
+}
+
+impl TryFrom<PendingTransactionEvent> for SubscriptionMessage {
+    type Error = serde_json::Error;
+
+    fn try_from(value: PendingTransactionEvent) -> Result<Self, Self::Error> {
+        Self::from_json(&value)
+    }
+}
@@ -0,0 +1,123 @@
+use ethabi::param_type::Reader;
+use ethabi::ParamType;
+use ethabi::Token;
+use ethers_core::types::I256;
+use itertools::Itertools;
+
+use crate::eth::codegen::SIGNATURES_4_BYTES;
+
+/// Selector of `Error(string)`, used by `revert(string)` and `require(condition, string)`.
+const ERROR_STRING_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Selector of `Panic(uint256)`, used by failed assertions and checked arithmetic.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Decodes the reason of a revert from its output.
+///
+/// Supports `Error(string)`, `Panic(uint256)` and custom errors of the contracts present in `static/contracts-signatures`.
+/// Returns `None` if the output is empty or the error is not known.
+pub fn decode_revert_reason(output: &[u8]) -> Option<String> {
+    let (selector, params) = output.split_at_checked(4)?;
+    match selector {
+        s if s == ERROR_STRING_SELECTOR => match ethabi::decode(&[ParamType::String], params).ok()?.pop()? {
+            Token::String(reason) => Some(reason),
+            _ => None,
+        },
+        s if s == PANIC_SELECTOR => match ethabi::decode(&[ParamType::Uint(256)], params).ok()?.pop()? {
+            Token::Uint(code) => Some(format!("panic: {} ({:#x})", panic_description(u64::try_from(code).unwrap_or(u64::MAX)), code)),
+            _ => None,
+        },
+        s => {
+            let signature = SIGNATURES_4_BYTES.get(s)?;
+            let (name, param_types) = signature.split_once('(')?;
+            let param_types = match param_types {
+                ")" => Vec::new(),
+                param_types => match Reader::read(&format!("({}", param_types)).ok()? {
+                    ParamType::Tuple(param_types) => param_types,
+                    _ => return None,
+                },
+            };
+            let params = ethabi::decode(&param_types, params).ok()?;
+            Some(format!("{}({})", name, params.iter().map(format_token).join(", ")))
+        }
+    }
+}
+
+/// Describes the panic codes emitted by the Solidity compiler.
+fn panic_description(code: u64) -> &'static str {
+    match code {
+        0x00 => "generic compiler panic",
+        0x01 => "assertion failed",
+        0x11 => "arithmetic underflow or overflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "enum overflow",
+        0x22 => "invalid encoded storage byte array accessed",
+        0x31 => "out-of-bounds array access; popping on an empty array",
+        0x32 => "out-of-bounds access of an array or bytesN",
+        0x41 => "out of memory",
+        0x51 => "uninitialized function",
+        _ => "unknown panic code",
+    }
+}
+
+/// Formats an ABI parameter like Solidity literals.
+fn format_token(token: &Token) -> String {
+    match token {
+        Token::Address(address) => format!("{:#x}", address),
+        Token::FixedBytes(bytes) | Token::Bytes(bytes) => const_hex::encode_prefixed(bytes),
+        Token::Uint(value) => value.to_string(),
+        Token::Int(value) => I256::from_raw(*value).to_string(),
+        Token::Bool(value) => value.to_string(),
+        Token::String(value) => format!("{:?}", value),
+        Token::FixedArray(tokens) | Token::Array(tokens) => format!("[{}]", tokens.iter().map(format_token).join(", ")),
+        Token::Tuple(tokens) => format!("({})", tokens.iter().map(format_token).join(", ")),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use ethereum_types::U256;
+    use hex_literal::hex;
+
+    use super::*;
+
+    #[test]
+    fn decode_revert_reason_of_error_string() {
+        let output = [
+            ERROR_STRING_SELECTOR.as_slice(),
+            &ethabi::encode(&[Token::String("insufficient balance".into())]),
+        ]
+        .concat();
+        assert_eq!(decode_revert_reason(&output).as_deref(), Some("insufficient balance"));
+    }
+
+    #[test]
+    fn decode_revert_reason_of_panic() {
+        let output = [PANIC_SELECTOR.as_slice(), &ethabi::encode(&[Token::Uint(U256::from(0x11))])].concat();
+        assert_eq!(decode_revert_reason(&output).as_deref(), Some("panic: arithmetic underflow or overflow (0x11)"));
+    }
+
+    #[test]
+    fn decode_revert_reason_of_custom_error() {
+        // UnauthorizedMinter(address)
+        let minter = ethereum_types::H160::repeat_byte(0xab);
+        let output = [hex!("a4f39665").as_slice(), &ethabi::encode(&[Token::Address(minter)])].concat();
+        assert_eq!(
+            decode_revert_reason(&output).as_deref(),
+            Some("UnauthorizedMinter(0xabababababababababababababababababababab)")
+        );
+
+        // Unauthorized()
+        assert_eq!(decode_revert_reason(&hex!("82b42900")).as_deref(), Some("Unauthorized()"));
+    }
+
+    #[test]
+    fn decode_revert_reason_of_unknown_output() {
+        assert_eq!(decode_revert_reason(&[]), None);
+        assert_eq!(decode_revert_reason(&hex!("ffffffff")), None);
+        assert_eq!(decode_revert_reason(&ERROR_STRING_SELECTOR), None);
+    }
+}
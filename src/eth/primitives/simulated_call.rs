@@ -0,0 +1,60 @@
+use display_json::DebugAsJson;
+use ethereum_types::U64;
+
+use crate::alias::EthersLog;
+use crate::eth::primitives::Bytes;
+use crate::eth::primitives::EvmExecution;
+use crate::eth::primitives::EvmTrace;
+use crate::eth::primitives::Gas;
+use crate::if_else;
+
+/// Result of a call executed as part of a simulated bundle.
+#[derive(DebugAsJson, Clone, serde::Serialize)]
+pub struct SimulatedCall {
+    /// `0x1` if the call succeeded, `0x0` if it failed.
+    pub status: U64,
+
+    /// Output returned by the call (can be the function output or the revert reason).
+    #[serde(rename = "returnData")]
+    pub return_data: Bytes,
+
+    /// Consumed gas.
+    #[serde(rename = "gasUsed")]
+    pub gas_used: Gas,
+
+    /// Logs emitted by the call.
+    pub logs: Vec<EthersLog>,
+
+    /// Reason the call failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    /// Original and modified state of accounts modified by the call, in the `prestateTracer` diff format.
+    #[serde(rename = "stateDiff", skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<EvmTrace>,
+}
+
+impl SimulatedCall {
+    /// Creates the result of a simulated call from its execution.
+    pub fn new(execution: EvmExecution, with_state_diff: bool) -> Self {
+        let error = execution.failure_reason();
+        let state_diff = with_state_diff.then(|| EvmTrace::from_changes(&execution.changes, true));
+
+        Self {
+            status: if_else!(error.is_none(), U64::one(), U64::zero()),
+            return_data: execution.output,
+            gas_used: execution.gas,
+            logs: execution.logs.into_iter().map(EthersLog::from).collect(),
+            error,
+            state_diff,
+        }
+    }
+}
+
+/// Options of a bundle simulation.
+#[derive(DebugAsJson, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct SimulationOptions {
+    /// Returns the state diff of each call.
+    #[serde(rename = "stateDiff", default)]
+    pub state_diff: bool,
+}
@@ -0,0 +1,244 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use display_json::DebugAsJson;
+use ethereum_types::H256;
+use serde_json::json;
+
+use crate::alias::JsonValue;
+use crate::eth::primitives::Address;
+use crate::eth::primitives::Bytes;
+use crate::eth::primitives::ExecutionChanges;
+use crate::eth::primitives::ExecutionValueChange;
+use crate::eth::primitives::Nonce;
+use crate::eth::primitives::SlotIndex;
+use crate::eth::primitives::SlotValue;
+use crate::eth::primitives::Wei;
+use crate::ext::not;
+use crate::ext::to_json_string;
+use crate::if_else;
+
+/// Values modified by a transaction, together with the values they had before the transaction.
+#[derive(DebugAsJson, Clone, Default, PartialEq, Eq, fake::Dummy, serde::Serialize, serde::Deserialize)]
+pub struct StateDiff {
+    /// Modified accounts ordered by address.
+    pub accounts: Vec<AccountStateDiff>,
+}
+
+/// Values of an account modified by a transaction.
+#[derive(DebugAsJson, Clone, PartialEq, Eq, fake::Dummy, serde::Serialize, serde::Deserialize)]
+pub struct AccountStateDiff {
+    pub address: Address,
+    pub balance: Option<ValueDiff<Wei>>,
+    pub nonce: Option<ValueDiff<Nonce>>,
+    pub bytecode: Option<ValueDiff<Bytes>>,
+
+    /// Modified slots ordered by index.
+    pub slots: Vec<(SlotIndex, ValueDiff<SlotValue>)>,
+}
+
+/// Value modified by a transaction.
+#[derive(Clone, PartialEq, Eq, fake::Dummy, serde::Serialize, serde::Deserialize)]
+pub struct ValueDiff<T> {
+    /// Value before the transaction, or `None` if the account was created by the transaction.
+    pub from: Option<T>,
+
+    /// Value after the transaction.
+    pub to: T,
+}
+
+impl StateDiff {
+    /// Creates a state diff keeping only the values modified by the execution.
+    pub fn from_changes(changes: &ExecutionChanges) -> Self {
+        let mut accounts = Vec::new();
+        for account in changes.values() {
+            let new_account = account.new_account;
+
+            // absent bytecode is represented as empty bytecode
+            let bytecode = value_diff(&account.bytecode, new_account).map(|diff| ValueDiff {
+                from: diff.from.map(Option::unwrap_or_default),
+                to: diff.to.unwrap_or_default(),
+            });
+
+            let mut slots = account
+                .slots
+                .iter()
+                .filter_map(|(index, slot)| {
+                    let diff = value_diff(slot, new_account)?;
+                    Some((
+                        *index,
+                        ValueDiff {
+                            from: diff.from.map(|slot| slot.value),
+                            to: diff.to.value,
+                        },
+                    ))
+                })
+                .collect::<Vec<_>>();
+            slots.sort_by_key(|(index, _)| *index);
+
+            let account = AccountStateDiff {
+                address: account.address,
+                balance: value_diff(&account.balance, new_account),
+                nonce: value_diff(&account.nonce, new_account),
+                bytecode,
+                slots,
+            };
+            if account.balance.is_some() || account.nonce.is_some() || account.bytecode.is_some() || not(account.slots.is_empty()) {
+                accounts.push(account);
+            }
+        }
+        accounts.sort_by_key(|account| account.address.0);
+
+        Self { accounts }
+    }
+
+    /// Serializes the state diff in the `stateDiff` format returned by Parity/OpenEthereum `trace_*` methods.
+    pub fn to_parity_json(&self) -> JsonValue {
+        let mut accounts = serde_json::Map::new();
+        for account in &self.accounts {
+            let storage = account
+                .slots
+                .iter()
+                .map(|(index, diff)| {
+                    let diff = ValueDiff {
+                        from: diff.from.map(|value| H256(value.into())),
+                        to: H256(diff.to.into()),
+                    };
+                    (H256((*index).into()), diff.to_parity_json())
+                })
+                .collect::<BTreeMap<_, _>>();
+
+            let account_json = json!({
+                "balance": account.balance.as_ref().map(ValueDiff::to_parity_json).unwrap_or_else(|| json!("=")),
+                "nonce": account.nonce.as_ref().map(ValueDiff::to_parity_json).unwrap_or_else(|| json!("=")),
+                "code": account.bytecode.as_ref().map(ValueDiff::to_parity_json).unwrap_or_else(|| json!("=")),
+                "storage": storage,
+            });
+            accounts.insert(account.address.to_string(), account_json);
+        }
+        JsonValue::Object(accounts)
+    }
+}
+
+impl<T> Debug for ValueDiff<T>
+where
+    T: serde::Serialize,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&to_json_string(self))
+    }
+}
+
+impl<T> ValueDiff<T>
+where
+    T: serde::Serialize,
+{
+    /// Serializes the value as a created (`+`) or modified (`*`) value.
+    fn to_parity_json(&self) -> JsonValue {
+        match self.from {
+            Some(ref from) => json!({ "*": { "from": from, "to": self.to } }),
+            None => json!({ "+": self.to }),
+        }
+    }
+}
+
+/// Extracts the original and modified values of a value that was modified.
+fn value_diff<T>(change: &ExecutionValueChange<T>, new_account: bool) -> Option<ValueDiff<T>>
+where
+    T: Clone + PartialEq + serde::Serialize,
+{
+    if not(change.is_modified()) {
+        return None;
+    }
+    Some(ValueDiff {
+        from: if_else!(new_account, None, change.take_original_ref().cloned()),
+        to: change.take_modified_ref()?.clone(),
+    })
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use fake::Fake;
+    use fake::Faker;
+
+    use super::*;
+    use crate::eth::primitives::Account;
+    use crate::eth::primitives::ExecutionAccountChanges;
+    use crate::eth::primitives::Slot;
+
+    #[test]
+    fn state_diff_keeps_only_modified_values() {
+        let address: Address = Faker.fake();
+        let mut account = ExecutionAccountChanges::from_original_values(Account {
+            address,
+            nonce: Nonce::from(1u64),
+            balance: Wei::ONE,
+            ..Account::default()
+        });
+        for index in [1u64, 2u64] {
+            let slot = Slot::new(index.into(), index.into());
+            account.slots.insert(slot.index, ExecutionValueChange::from_original(slot));
+        }
+        account.apply_modifications(
+            Account {
+                address,
+                nonce: Nonce::from(2u64),
+                balance: Wei::ONE,
+                ..Account::default()
+            },
+            vec![Slot::new(2u64.into(), 3u64.into())],
+        );
+        let changes = HashMap::from([(address, account)]);
+
+        let diff = StateDiff::from_changes(&changes);
+        assert_eq!(diff.accounts.len(), 1);
+
+        let account = &diff.accounts[0];
+        assert!(account.balance.is_none());
+        assert!(account.bytecode.is_none());
+        assert_eq!(
+            account.nonce,
+            Some(ValueDiff {
+                from: Some(Nonce::from(1u64)),
+                to: Nonce::from(2u64)
+            })
+        );
+        assert_eq!(
+            account.slots,
+            vec![(
+                SlotIndex::from(2u64),
+                ValueDiff {
+                    from: Some(SlotValue::from(2u64)),
+                    to: SlotValue::from(3u64)
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn state_diff_to_parity_json() {
+        let address: Address = Faker.fake();
+        let account = ExecutionAccountChanges::from_modified_values(
+            Account {
+                address,
+                nonce: Nonce::from(1u64),
+                balance: Wei::ONE,
+                ..Account::default()
+            },
+            vec![],
+        );
+        let changes = HashMap::from([(address, account)]);
+
+        let json = StateDiff::from_changes(&changes).to_parity_json();
+        let account_json = &json[address.to_string()];
+        assert_eq!(account_json["balance"]["+"], "0x1");
+        assert_eq!(account_json["nonce"]["+"], "0x1");
+        assert_eq!(account_json["code"]["+"], "0x");
+    }
+}
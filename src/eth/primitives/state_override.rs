@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use display_json::DebugAsJson;
+
+use crate::eth::primitives::Account;
+use crate::eth::primitives::Address;
+use crate::eth::primitives::Bytes;
+use crate::eth::primitives::CodeHash;
+use crate::eth::primitives::Nonce;
+use crate::eth::primitives::SlotIndex;
+use crate::eth::primitives::SlotValue;
+use crate::eth::primitives::Wei;
+
+/// Accounts overridden by the caller while executing a call, replacing the values read from the storage.
+pub type StateOverride = HashMap<Address, AccountOverride>;
+
+/// Account values overridden by the caller while executing a call.
+///
+/// Fields that are not specified are read from the storage.
+#[derive(DebugAsJson, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccountOverride {
+    /// Replaces the account balance.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<Wei>,
+
+    /// Replaces the account nonce.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<Nonce>,
+
+    /// Replaces the account bytecode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+
+    /// Replaces the entire account storage. Slots not present are empty.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<HashMap<SlotIndex, SlotValue>>,
+
+    /// Replaces only the specified slots of the account storage.
+    #[serde(rename = "stateDiff", skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<HashMap<SlotIndex, SlotValue>>,
+}
+
+impl AccountOverride {
+    /// Checks the override is valid.
+    ///
+    /// An override cannot replace the entire storage and some slots at the same time.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.state.is_some() && self.state_diff.is_some() {
+            return Err("account override cannot have both state and stateDiff");
+        }
+        Ok(())
+    }
+
+    /// Applies the overridden values to an account.
+    pub fn apply(&self, account: &mut Account) {
+        if let Some(balance) = self.balance {
+            account.balance = balance;
+        }
+        if let Some(nonce) = self.nonce {
+            account.nonce = nonce;
+        }
+        if let Some(ref code) = self.code {
+            account.code_hash = CodeHash::from_bytecode(Some(code.clone()));
+            account.bytecode = Some(code.clone());
+        }
+    }
+
+    /// Returns the overridden value of a slot.
+    ///
+    /// Returns `None` when the slot must be read from the storage.
+    pub fn slot(&self, index: &SlotIndex) -> Option<SlotValue> {
+        match (&self.state, &self.state_diff) {
+            (Some(state), _) => Some(state.get(index).copied().unwrap_or_default()),
+            (None, Some(state_diff)) => state_diff.get(index).copied(),
+            (None, None) => None,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_override_slot_reads_state_or_state_diff() {
+        let state: AccountOverride = serde_json::from_str(r#"{"state": {"0x1": "0x2"}}"#).unwrap();
+        assert_eq!(state.slot(&SlotIndex::ONE), Some(SlotValue::from(2u64)));
+        assert_eq!(state.slot(&SlotIndex::ZERO), Some(SlotValue::default()));
+
+        let state_diff: AccountOverride = serde_json::from_str(r#"{"stateDiff": {"0x1": "0x2"}}"#).unwrap();
+        assert_eq!(state_diff.slot(&SlotIndex::ONE), Some(SlotValue::from(2u64)));
+        assert_eq!(state_diff.slot(&SlotIndex::ZERO), None);
+
+        let both: AccountOverride = serde_json::from_str(r#"{"state": {}, "stateDiff": {}}"#).unwrap();
+        assert!(both.validate().is_err());
+    }
+
+    #[test]
+    fn account_override_applies_code_with_its_hash() {
+        let code = Bytes(vec![0x60, 0x00]);
+        let account_override = AccountOverride {
+            balance: Some(Wei::ONE),
+            code: Some(code.clone()),
+            ..AccountOverride::default()
+        };
+
+        let mut account = Account::new_empty(Address::ZERO);
+        account_override.apply(&mut account);
+        assert_eq!(account.balance, Wei::ONE);
+        assert_eq!(account.nonce, Nonce::ZERO);
+        assert_eq!(account.bytecode, Some(code.clone()));
+        assert_eq!(account.code_hash, CodeHash::from_bytecode(Some(code)));
+    }
+}
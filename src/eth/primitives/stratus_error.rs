@@ -4,15 +4,20 @@ use jsonrpsee::types::error::INVALID_PARAMS_CODE;
 use jsonrpsee::types::error::INVALID_REQUEST_CODE;
 use jsonrpsee::types::error::SERVER_IS_BUSY_CODE;
 use jsonrpsee::types::ErrorObjectOwned;
+use serde_json::json;
 use strum::EnumProperty;
 
 use crate::alias::JsonValue;
+use crate::eth::primitives::decode_revert_reason;
 use crate::eth::primitives::Address;
 use crate::eth::primitives::BlockFilter;
 use crate::eth::primitives::BlockNumber;
 use crate::eth::primitives::Bytes;
 use crate::eth::primitives::ExecutionConflicts;
+use crate::eth::primitives::Gas;
+use crate::eth::primitives::Hash;
 use crate::eth::primitives::Nonce;
+use crate::eth::primitives::Wei;
 use crate::ext::to_json_value;
 
 /// Valid  error catogories are:
@@ -38,6 +43,26 @@ pub enum StratusError {
     #[strum(props(kind = "client_request"))]
     RpcClientMissing,
 
+    #[error("Denied because reached maximum filter limit of {max}.")]
+    #[strum(props(kind = "client_state"))]
+    RpcFilterLimit { max: u32 },
+
+    #[error("Filter {id} not found.")]
+    #[strum(props(kind = "client_request"))]
+    RpcFilterNotFound { id: String },
+
+    #[error("Denied because bundle has more than {max} calls.")]
+    #[strum(props(kind = "client_request"))]
+    RpcBundleLimit { max: usize },
+
+    #[error("Denied because query returned more than {max} logs, try again with block range [{from}, {to}].")]
+    #[strum(props(kind = "client_request"))]
+    RpcLogsLimit { max: usize, from: BlockNumber, to: BlockNumber },
+
+    #[error("Denied because requested to mine more than {max} blocks.")]
+    #[strum(props(kind = "client_request"))]
+    RpcMineLimit { max: u64 },
+
     #[error("Failed to decode {rust_type} parameter.")]
     #[strum(props(kind = "client_request"))]
     RpcParameterInvalid { rust_type: &'static str, decode_error: String },
@@ -66,6 +91,14 @@ pub enum StratusError {
     #[strum(props(kind = "client_request"))]
     RpcTransactionInvalid { decode_error: String },
 
+    #[error("Transaction {hash} was not found or is not mined yet.")]
+    #[strum(props(kind = "client_request"))]
+    RpcTransactionNotFound { hash: Hash },
+
+    #[error("Denied because transaction sender {address} is not impersonated.")]
+    #[strum(props(kind = "client_request"))]
+    RpcSenderNotImpersonated { address: Address },
+
     // -------------------------------------------------------------------------
     // Transaction
     // -------------------------------------------------------------------------
@@ -85,6 +118,10 @@ pub enum StratusError {
     #[strum(props(kind = "execution"))]
     TransactionEvmFailed(String), // split this in multiple errors
 
+    #[error("Transaction max fee per gas is less than the block base fee of {base_fee}.")]
+    #[strum(props(kind = "execution"))]
+    TransactionFeeTooLow { base_fee: Wei },
+
     #[error("Failed to execute transaction in leader: {0:?}.")]
     #[strum(props(kind = "execution"))]
     TransactionLeaderFailed(ErrorObjectOwned),
@@ -97,10 +134,46 @@ pub enum StratusError {
     #[strum(props(kind = "execution"))]
     TransactionReverted { output: Bytes },
 
+    #[error("Transaction halted during execution: {reason}.")]
+    #[strum(props(kind = "execution"))]
+    TransactionHalted { reason: String },
+
+    #[error("Transaction requires more gas than the allowance of {gas}.")]
+    #[strum(props(kind = "execution"))]
+    TransactionGasAllowanceExceeded { gas: Gas },
+
+    #[error("Transaction does not fit in the remaining gas of the pending block with gas limit of {limit}.")]
+    #[strum(props(kind = "execution"))]
+    TransactionBlockGasLimitExceeded { limit: Gas },
+
     #[error("Transaction from zero address is not allowed.")]
     #[strum(props(kind = "execution"))]
     TransactionFromZeroAddress,
 
+    #[error("Replacement transaction underpriced: fees must be increased by at least {price_bump}% to replace transaction {hash}.")]
+    #[strum(props(kind = "execution"))]
+    TransactionReplacementUnderpriced { hash: Hash, price_bump: u64 },
+
+    #[error("Transaction {hash} with the same sender and nonce cannot be replaced.")]
+    #[strum(props(kind = "execution"))]
+    TransactionReplacementNotAllowed { hash: Hash },
+
+    #[error("Transaction {hash} cannot be replaced because transactions executed after it depend on its changes.")]
+    #[strum(props(kind = "execution"))]
+    TransactionReplacementConflict { hash: Hash },
+
+    #[error("Transaction nonce {transaction} is more than {max_gap} nonces ahead of account nonce {account}.")]
+    #[strum(props(kind = "client_request"))]
+    TransactionNonceGapExceeded { transaction: Nonce, account: Nonce, max_gap: u64 },
+
+    #[error("Transaction pool already has the max of {max} queued transactions of sender {address}.")]
+    #[strum(props(kind = "client_state"))]
+    TransactionPoolSenderLimit { address: Address, max: usize },
+
+    #[error("Transaction pool already has the max of {max} queued transactions.")]
+    #[strum(props(kind = "server_state"))]
+    TransactionPoolLimit { max: usize },
+
     // -------------------------------------------------------------------------
     // Storage
     // -------------------------------------------------------------------------
@@ -116,6 +189,18 @@ pub enum StratusError {
     #[strum(props(kind = "internal"))]
     StoragePendingNumberConflict { new: BlockNumber, pending: BlockNumber },
 
+    #[error("Genesis block {stored} in the permanent storage differs from genesis block {genesis} of the genesis file.")]
+    #[strum(props(kind = "internal"))]
+    StorageGenesisMismatch { stored: Hash, genesis: Hash },
+
+    #[error("Snapshots are not supported by the permanent storage because it cannot discard mined blocks.")]
+    #[strum(props(kind = "internal"))]
+    StorageSnapshotUnsupported,
+
+    #[error("Proofs are not supported when forked from another chain because the state trie does not contain the state of the fork.")]
+    #[strum(props(kind = "internal"))]
+    StorageProofUnsupported,
+
     #[error("There are ({pending_txs}) pending transactions.")]
     #[strum(props(kind = "internal"))]
     PendingTransactionsExist { pending_txs: usize },
@@ -226,8 +311,16 @@ impl StratusError {
     }
 
     /// Error message to be used in JSON-RPC response.
+    ///
+    /// Reverted transactions include the decoded revert reason in the same format used by Geth.
     pub fn rpc_message(&self) -> String {
-        self.to_string()
+        match self {
+            Self::TransactionReverted { output } => match decode_revert_reason(output) {
+                Some(reason) => format!("execution reverted: {}", reason),
+                None => self.to_string(),
+            },
+            _ => self.to_string(),
+        }
     }
 
     /// Error additional data to be used in JSON-RPC response.
@@ -235,6 +328,7 @@ impl StratusError {
         match self {
             // RPC
             Self::RpcBlockFilterInvalid { filter } => to_json_value(filter),
+            Self::RpcLogsLimit { from, to, .. } => json!({ "fromBlock": from, "toBlock": to }),
             Self::RpcParameterInvalid { decode_error, .. } => to_json_value(decode_error),
 
             // Transaction
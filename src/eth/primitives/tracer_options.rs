@@ -0,0 +1,52 @@
+use display_json::DebugAsJson;
+
+/// Options accepted by `debug_trace*` methods.
+///
+/// Follows the format used by Geth: <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-debug#traceconfig>
+#[derive(DebugAsJson, Clone, Default, PartialEq, Eq, fake::Dummy, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TracerOptions {
+    /// Tracer used to inspect the execution.
+    ///
+    /// Defaults to the `callTracer` because the Geth struct logger is not supported.
+    #[serde(default)]
+    pub tracer: TracerType,
+
+    /// Configuration specific to the selected tracer.
+    #[serde(default)]
+    pub tracer_config: TracerConfig,
+}
+
+/// Native tracers supported by Stratus.
+#[derive(DebugAsJson, strum::Display, Clone, Copy, Default, PartialEq, Eq, fake::Dummy, serde::Serialize, serde::Deserialize)]
+pub enum TracerType {
+    /// Tracks all call frames executed during a transaction.
+    #[default]
+    #[serde(rename = "callTracer")]
+    #[strum(to_string = "callTracer")]
+    CallTracer,
+
+    /// Tracks the state of all accounts touched by a transaction before (and optionally after) its execution.
+    #[serde(rename = "prestateTracer")]
+    #[strum(to_string = "prestateTracer")]
+    PrestateTracer,
+}
+
+/// Configuration of the native tracers.
+///
+/// Options not relevant to the selected tracer are ignored.
+#[derive(DebugAsJson, Clone, Default, PartialEq, Eq, fake::Dummy, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TracerConfig {
+    /// `callTracer`: tracks only the top-level call frame.
+    #[serde(default)]
+    pub only_top_call: bool,
+
+    /// `callTracer`: includes logs emitted in each call frame.
+    #[serde(default)]
+    pub with_log: bool,
+
+    /// `prestateTracer`: returns the state before and after the execution instead of only the state before.
+    #[serde(default)]
+    pub diff_mode: bool,
+}
@@ -1,3 +1,5 @@
+use std::cmp::min;
+
 use anyhow::anyhow;
 use display_json::DebugAsJson;
 use ethereum_types::U256;
@@ -43,11 +45,51 @@ pub struct TransactionInput {
     pub gas_limit: Gas,
     pub gas_price: Wei,
 
+    /// Max fee per gas, including the priority fee, the signer agreed to pay. Present only in EIP-1559 transactions.
+    pub max_fee_per_gas: Option<Wei>,
+
+    /// Max fee per gas paid to the block producer on top of the base fee. Present only in EIP-1559 transactions.
+    pub max_priority_fee_per_gas: Option<Wei>,
+
     pub v: U64,
     pub r: U256,
     pub s: U256,
 }
 
+impl TransactionInput {
+    /// Calculates the price paid for each unit of gas when the transaction is included in a block with the specified base fee.
+    ///
+    /// Blocks with zero base fee were mined without a fee market, so their transactions did not pay for gas.
+    pub fn effective_gas_price(&self, base_fee: Wei) -> Wei {
+        if base_fee.is_zero() {
+            return Wei::ZERO;
+        }
+        match (self.max_fee_per_gas, self.max_priority_fee_per_gas) {
+            (Some(max_fee), Some(max_priority_fee)) => min(max_fee, Wei(base_fee.0.saturating_add(max_priority_fee.0))),
+            _ => self.gas_price,
+        }
+    }
+
+    /// Calculates the fee paid to the block producer for each unit of gas when the transaction is included in a block with the specified base fee.
+    pub fn effective_priority_fee(&self, base_fee: Wei) -> Wei {
+        Wei(self.effective_gas_price(base_fee).0.saturating_sub(base_fee.0))
+    }
+
+    #[cfg(feature = "dev")]
+    /// Signs a legacy transaction sent by an impersonated account with a fake signature and calculates its hash.
+    ///
+    /// The signature is derived from the signer, so the hash is still unique for each signer and nonce, but the signer cannot be recovered from it.
+    pub fn sign_impersonated(mut self) -> Self {
+        let chain_id = self.chain_id.map(|chain_id| chain_id.0.as_u64()).unwrap_or_default();
+        self.tx_type = Some(U64::zero());
+        self.v = U64::from(chain_id * 2 + 35);
+        self.r = U256::from_big_endian(self.signer.0.as_bytes());
+        self.s = U256::one();
+        self.hash = EthersTransaction::from(self.clone()).hash().into();
+        self
+    }
+}
+
 impl Dummy<Faker> for TransactionInput {
     fn dummy_with_rng<R: ethers_core::rand::prelude::Rng + ?Sized>(faker: &Faker, rng: &mut R) -> Self {
         Self {
@@ -62,6 +104,8 @@ impl Dummy<Faker> for TransactionInput {
             input: faker.fake_with_rng(rng),
             gas_limit: faker.fake_with_rng(rng),
             gas_price: faker.fake_with_rng(rng),
+            max_fee_per_gas: faker.fake_with_rng(rng),
+            max_priority_fee_per_gas: faker.fake_with_rng(rng),
             v: rng.next_u64().into(),
             r: rng.next_u64().into(),
             s: rng.next_u64().into(),
@@ -128,7 +172,9 @@ fn try_from_ethers_transaction(value: EthersTransaction, compute_signer: bool) -
         value: value.value.into(),
         input: value.input.clone().into(),
         gas_limit: value.gas.try_into()?,
-        gas_price: value.gas_price.unwrap_or_default().into(),
+        gas_price: value.gas_price.or(value.max_fee_per_gas).unwrap_or_default().into(),
+        max_fee_per_gas: value.max_fee_per_gas.map_into(),
+        max_priority_fee_per_gas: value.max_priority_fee_per_gas.map_into(),
         v: value.v,
         r: value.r,
         s: value.s,
@@ -151,6 +197,8 @@ impl From<TransactionInput> for EthersTransaction {
             input: value.input.clone().into(),
             gas: value.gas_limit.into(),
             gas_price: Some(value.gas_price.into()),
+            max_fee_per_gas: value.max_fee_per_gas.map_into(),
+            max_priority_fee_per_gas: value.max_priority_fee_per_gas.map_into(),
             v: value.v,
             r: value.r,
             s: value.s,
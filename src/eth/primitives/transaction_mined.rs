@@ -1,19 +1,29 @@
 use std::hash::Hash as HashTrait;
+use std::iter;
 
 use display_json::DebugAsJson;
 use itertools::Itertools;
+use rlp::RlpStream;
 
 use crate::alias::EthersReceipt;
 use crate::alias::EthersTransaction;
+use crate::alias::JsonValue;
+use crate::eth::primitives::decode_revert_reason;
 use crate::eth::primitives::logs_bloom::LogsBloom;
+use crate::eth::primitives::Address;
 use crate::eth::primitives::BlockNumber;
+use crate::eth::primitives::CallKind;
 use crate::eth::primitives::EvmExecution;
+use crate::eth::primitives::ExecutionResult;
 use crate::eth::primitives::ExternalReceipt;
 use crate::eth::primitives::ExternalTransaction;
+use crate::eth::primitives::Gas;
 use crate::eth::primitives::Hash;
 use crate::eth::primitives::Index;
 use crate::eth::primitives::LogMined;
 use crate::eth::primitives::TransactionInput;
+use crate::eth::primitives::Wei;
+use crate::ext::not;
 use crate::ext::OptionExt;
 use crate::if_else;
 
@@ -37,6 +47,20 @@ pub struct TransactionMined {
 
     /// Block hash where the transaction was mined.
     pub block_hash: Hash,
+
+    /// Gas consumed by this transaction and all transactions that precede it in the block.
+    ///
+    /// Missing in transactions stored before it existed, so it must be filled from the block when zero.
+    #[serde(default)]
+    pub cumulative_gas_used: Gas,
+
+    /// Gas price paid by the transaction according to the base fee of the block.
+    #[serde(default)]
+    pub effective_gas_price: Wei,
+
+    /// Bloom filter of the logs emitted by the transaction.
+    #[serde(default)]
+    pub logs_bloom: LogsBloom,
 }
 
 impl PartialOrd for TransactionMined {
@@ -69,6 +93,10 @@ impl TransactionMined {
             block_hash: receipt.block_hash(),
             transaction_index: receipt.transaction_index.into(),
             logs: receipt.0.logs.into_iter().map(LogMined::try_from).collect::<Result<Vec<LogMined>, _>>()?,
+            // calculated when the block is mined
+            cumulative_gas_used: Gas::ZERO,
+            effective_gas_price: Wei::ZERO,
+            logs_bloom: LogsBloom::default(),
         })
     }
 
@@ -77,13 +105,93 @@ impl TransactionMined {
         self.execution.is_success()
     }
 
-    fn compute_bloom(&self) -> LogsBloom {
+    /// Calculates the bloom filter of the logs emitted by the transaction.
+    pub fn compute_bloom(&self) -> LogsBloom {
         let mut bloom = LogsBloom::default();
         for log_mined in self.logs.iter() {
             bloom.accrue_log(&(log_mined.log));
         }
         bloom
     }
+
+    /// Returns the addresses the transaction interacted with.
+    ///
+    /// They are the sender, the recipient, the created contracts and the accounts whose state was modified by the execution.
+    pub fn involved_addresses(&self) -> Vec<Address> {
+        let modified_addresses = self
+            .execution
+            .changes
+            .values()
+            .filter(|changes| changes.is_account_modified() || changes.slots.values().any(|slot| slot.is_modified()))
+            .map(|changes| changes.address);
+
+        iter::once(self.input.from)
+            .chain(self.input.to)
+            .chain(self.created_contracts())
+            .chain(modified_addresses)
+            .filter(|address| not(address.is_coinbase()))
+            .unique()
+            .collect()
+    }
+
+    /// Returns the addresses of the contracts created by the transaction, including the ones created by other contracts.
+    pub fn created_contracts(&self) -> Vec<Address> {
+        let created_addresses = self
+            .execution
+            .changes
+            .values()
+            .filter(|changes| changes.bytecode.take_modified_ref().is_some_and(|bytecode| bytecode.is_some()))
+            .map(|changes| changes.address);
+
+        self.execution.contract_address().into_iter().chain(created_addresses).unique().collect()
+    }
+
+    /// Returns the address that created a contract created by the transaction.
+    ///
+    /// Contracts deployed by the transaction are created by the sender. Contracts created during the execution are created
+    /// by the contract that executed the CREATE or CREATE2, or by the called contract in transactions mined before internal
+    /// transactions were recorded.
+    pub fn contract_creator(&self, contract: &Address) -> Address {
+        let internal_creation = self
+            .execution
+            .internal_transactions
+            .iter()
+            .find(|internal_tx| matches!(internal_tx.kind, CallKind::Create | CallKind::Create2) && internal_tx.to == *contract);
+        if let Some(internal_creation) = internal_creation {
+            return internal_creation.from;
+        }
+
+        match self.input.to {
+            Some(to) if self.execution.contract_address().as_ref() != Some(contract) => to,
+            _ => self.input.from,
+        }
+    }
+
+    /// Encodes the receipt of the transaction as it is inserted in the receipts trie.
+    ///
+    /// Typed transactions have the receipt prefixed by the transaction type (EIP-2718).
+    pub fn encode_receipt(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(4);
+        stream.append(&u8::from(self.is_success()));
+        stream.append(&self.cumulative_gas_used.as_u64());
+        stream.append(&self.logs_bloom.0);
+        stream.begin_list(self.logs.len());
+        for log_mined in &self.logs {
+            let topics = log_mined.log.topics_non_empty();
+            stream.begin_list(3);
+            stream.append(&log_mined.log.address.0);
+            stream.begin_list(topics.len());
+            for topic in topics {
+                stream.append(&topic.0);
+            }
+            stream.append(&log_mined.log.data.0);
+        }
+
+        match self.input.tx_type.map(|tx_type| tx_type.as_u64()) {
+            Some(tx_type) if tx_type > 0 => [&[tx_type as u8], stream.out().as_ref()].concat(),
+            _ => stream.out().to_vec(),
+        }
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -116,12 +224,18 @@ impl From<TransactionMined> for EthersTransaction {
 
 impl From<TransactionMined> for EthersReceipt {
     fn from(value: TransactionMined) -> Self {
-        let logs_bloom = value.compute_bloom().into();
-        Self {
+        let revert_reason = match value.execution.result {
+            ExecutionResult::Reverted => decode_revert_reason(&value.execution.output),
+            _ => None,
+        };
+
+        let mut receipt = Self {
             // receipt specific
             status: Some(if_else!(value.is_success(), 1, 0).into()),
             contract_address: value.execution.contract_address().map_into(),
             gas_used: Some(value.execution.gas.into()),
+            cumulative_gas_used: value.cumulative_gas_used.into(),
+            effective_gas_price: Some(value.effective_gas_price.into()),
 
             // transaction
             transaction_hash: value.input.hash.into(),
@@ -135,11 +249,15 @@ impl From<TransactionMined> for EthersReceipt {
 
             // logs
             logs: value.logs.into_iter().map_into().collect(),
-            logs_bloom, // TODO: save this to the database instead of computing it every time (could also be useful for eth_getLogs)
+            logs_bloom: value.logs_bloom.into(),
 
             // TODO: there are more fields to populate here
             ..Default::default()
+        };
+        if let Some(revert_reason) = revert_reason {
+            receipt.other.insert("revertReason".to_owned(), JsonValue::String(revert_reason));
         }
+        receipt
     }
 }
 
@@ -150,6 +268,7 @@ mod tests {
     use rand::Rng;
 
     use super::*;
+    use crate::eth::primitives::InternalTransaction;
 
     fn create_tx(transaction_index: u64, block_number: u64) -> TransactionMined {
         TransactionMined {
@@ -159,6 +278,9 @@ mod tests {
             transaction_index: transaction_index.into(),
             block_number: block_number.into(),
             block_hash: Hash::default(),
+            cumulative_gas_used: Gas::ZERO,
+            effective_gas_price: Wei::ZERO,
+            logs_bloom: LogsBloom::default(),
         }
     }
 
@@ -176,4 +298,39 @@ mod tests {
             .collect_vec();
         assert!(is_sorted(&v));
     }
+
+    #[test]
+    fn receipt_has_revert_reason_of_reverted_transaction() {
+        let mut tx = create_tx(0, 0);
+        tx.execution.result = ExecutionResult::Reverted;
+        tx.execution.output = ethabi::short_signature("Error", &[ethabi::ParamType::String])
+            .into_iter()
+            .chain(ethabi::encode(&[ethabi::Token::String("not allowed".into())]))
+            .collect_vec()
+            .into();
+
+        let receipt: EthersReceipt = tx.clone().into();
+        assert_eq!(receipt.other.get("revertReason"), Some(&JsonValue::String("not allowed".into())));
+
+        tx.execution.result = ExecutionResult::Success;
+        let receipt: EthersReceipt = tx.into();
+        assert_eq!(receipt.other.get("revertReason"), None);
+    }
+
+    #[test]
+    fn contract_creator_of_internally_created_contract() {
+        let mut tx = create_tx(0, 0);
+        let factory: Address = Faker.fake();
+        let contract: Address = Faker.fake();
+        tx.input.to = Some(Faker.fake());
+        tx.execution.internal_transactions = vec![
+            InternalTransaction::new(CallKind::Call, tx.input.to.unwrap(), factory, Wei::ZERO, &[], 1),
+            InternalTransaction::new(CallKind::Create2, factory, contract, Wei::ZERO, &[], 2),
+        ];
+        assert_eq!(tx.contract_creator(&contract), factory);
+
+        // transactions mined before internal transactions were recorded
+        tx.execution.internal_transactions.clear();
+        assert_eq!(tx.contract_creator(&contract), tx.input.to.unwrap());
+    }
 }
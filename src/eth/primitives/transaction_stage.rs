@@ -1,6 +1,7 @@
 use crate::alias::EthersReceipt;
 use crate::alias::EthersTransaction;
 use crate::alias::JsonValue;
+use crate::eth::primitives::EvmExecution;
 use crate::eth::primitives::TransactionExecution;
 use crate::eth::primitives::TransactionMined;
 use crate::ext::to_json_value;
@@ -17,6 +18,14 @@ pub enum TransactionStage {
 }
 
 impl TransactionStage {
+    /// Returns the EVM execution of the transaction.
+    pub fn execution(&self) -> &EvmExecution {
+        match self {
+            Self::Executed(tx) => tx.execution(),
+            Self::Mined(tx) => &tx.execution,
+        }
+    }
+
     /// Serializes itself to JSON-RPC transaction format.
     pub fn to_json_rpc_transaction(self) -> JsonValue {
         match self {
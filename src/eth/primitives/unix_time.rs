@@ -31,6 +31,24 @@ impl UnixTime {
         offset::set(timestamp, latest_timestamp)
     }
 
+    #[cfg(feature = "dev")]
+    /// Moves the current time forward by the specified number of seconds, returning the total offset from the system time.
+    pub fn increase_offset(seconds: u64) -> i64 {
+        offset::increase(seconds)
+    }
+
+    #[cfg(feature = "dev")]
+    /// Returns the offset from the system time and the timestamp of the next block, so they can be restored later.
+    pub fn read_offset() -> (i64, UnixTime) {
+        offset::read()
+    }
+
+    #[cfg(feature = "dev")]
+    /// Restores the offset from the system time and the timestamp of the next block returned by `read_offset`.
+    pub fn restore_offset((time_offset, next_timestamp): (i64, UnixTime)) {
+        offset::restore(time_offset, next_timestamp);
+    }
+
     pub fn to_i64(&self) -> i64 {
         self.0.try_into().expect("UNIX time is unrealistically high")
     }
@@ -132,6 +150,20 @@ mod offset {
         Ok(())
     }
 
+    pub fn increase(seconds: u64) -> i64 {
+        let seconds = seconds as i64;
+        TIME_OFFSET.fetch_add(seconds, SeqCst) + seconds
+    }
+
+    pub fn read() -> (i64, UnixTime) {
+        (TIME_OFFSET.load(Acquire), UnixTime(NEXT_TIMESTAMP.load(Acquire)))
+    }
+
+    pub fn restore(time_offset: i64, next_timestamp: UnixTime) {
+        NEXT_TIMESTAMP.store(*next_timestamp, SeqCst);
+        TIME_OFFSET.store(time_offset, SeqCst);
+    }
+
     pub fn now() -> UnixTime {
         let offset_time = NEXT_TIMESTAMP.load(Acquire);
         let time_offset = TIME_OFFSET.load(Acquire);
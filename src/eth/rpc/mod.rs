@@ -3,6 +3,7 @@
 mod rpc_client_app;
 mod rpc_config;
 mod rpc_context;
+mod rpc_filters;
 mod rpc_http_middleware;
 mod rpc_method_wrapper;
 mod rpc_middleware;
@@ -13,6 +14,7 @@ mod rpc_subscriptions;
 pub use rpc_client_app::RpcClientApp;
 pub use rpc_config::RpcServerConfig;
 pub use rpc_context::RpcContext;
+pub use rpc_filters::RpcFilters;
 use rpc_http_middleware::RpcHttpMiddleware;
 use rpc_middleware::RpcMiddleware;
 use rpc_parser::next_rpc_param;
@@ -16,4 +16,24 @@ pub struct RpcServerConfig {
     /// JSON-RPC server max active subscriptions per client.
     #[arg(long = "max-subscriptions", env = "MAX_SUBSCRIPTIONS", default_value = "15")]
     pub rpc_max_subscriptions: u32,
+
+    /// JSON-RPC server max installed filters per client.
+    #[arg(long = "max-filters", env = "MAX_FILTERS", default_value = "15")]
+    pub rpc_max_filters: u32,
+
+    /// JSON-RPC server max logs returned by a single logs query.
+    #[arg(long = "max-logs", env = "MAX_LOGS", default_value = "10000")]
+    pub rpc_max_logs: usize,
+
+    /// JSON-RPC server max blocks read by a single logs query.
+    #[arg(long = "max-logs-block-range", env = "MAX_LOGS_BLOCK_RANGE", default_value = "5000")]
+    pub rpc_max_logs_block_range: u64,
+
+    /// JSON-RPC server max calls simulated by a single bundle simulation.
+    #[arg(long = "max-bundle-calls", env = "MAX_BUNDLE_CALLS", default_value = "100")]
+    pub rpc_max_bundle_calls: usize,
+
+    /// JSON-RPC server max pending and queued transactions returned by a single transaction pool query.
+    #[arg(long = "max-txpool-transactions", env = "MAX_TXPOOL_TRANSACTIONS", default_value = "5000")]
+    pub rpc_max_txpool_transactions: usize,
 }
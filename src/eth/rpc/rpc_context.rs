@@ -1,3 +1,5 @@
+#[cfg(feature = "dev")]
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::sync::Arc;
 use std::sync::RwLock;
@@ -6,7 +8,10 @@ use crate::alias::JsonValue;
 use crate::eth::executor::Executor;
 use crate::eth::follower::consensus::Consensus;
 use crate::eth::miner::Miner;
+#[cfg(feature = "dev")]
+use crate::eth::primitives::Address;
 use crate::eth::primitives::ChainId;
+use crate::eth::rpc::rpc_filters::RpcFiltersInstalled;
 use crate::eth::rpc::rpc_subscriptions::RpcSubscriptionsConnected;
 use crate::eth::rpc::RpcServerConfig;
 use crate::eth::storage::StratusStorage;
@@ -19,9 +24,6 @@ pub struct RpcContext {
     pub chain_id: ChainId,
     pub client_version: &'static str,
 
-    // gas config
-    pub gas_price: usize,
-
     // services
     pub executor: Arc<Executor>,
     pub miner: Arc<Miner>,
@@ -29,6 +31,11 @@ pub struct RpcContext {
     pub consensus: RwLock<Option<Arc<dyn Consensus>>>,
     pub rpc_server: RpcServerConfig,
     pub subs: Arc<RpcSubscriptionsConnected>,
+    pub filters: Arc<RpcFiltersInstalled>,
+
+    // dev
+    #[cfg(feature = "dev")]
+    pub impersonated_accounts: RwLock<HashSet<Address>>,
 }
 
 impl RpcContext {
@@ -50,6 +57,34 @@ impl RpcContext {
             poison_error.into_inner()
         }) = new_consensus;
     }
+
+    #[cfg(feature = "dev")]
+    /// Checks if transactions can be sent from the account without being signed by it.
+    pub fn is_impersonated(&self, address: &Address) -> bool {
+        self.impersonated_accounts
+            .read()
+            .unwrap_or_else(|poison_error| {
+                tracing::error!("impersonated accounts read lock was poisoned");
+                self.impersonated_accounts.clear_poison();
+                poison_error.into_inner()
+            })
+            .contains(address)
+    }
+
+    #[cfg(feature = "dev")]
+    /// Starts or stops allowing transactions to be sent from the account without being signed by it.
+    pub fn set_impersonated(&self, address: Address, impersonated: bool) {
+        let mut accounts = self.impersonated_accounts.write().unwrap_or_else(|poison_error| {
+            tracing::error!("impersonated accounts write lock was poisoned");
+            self.impersonated_accounts.clear_poison();
+            poison_error.into_inner()
+        });
+        if impersonated {
+            accounts.insert(address);
+        } else {
+            accounts.remove(&address);
+        }
+    }
 }
 
 impl Debug for RpcContext {
@@ -57,7 +92,6 @@ impl Debug for RpcContext {
         f.debug_struct("RpcContext")
             .field("chain_id", &self.chain_id)
             .field("client_version", &self.client_version)
-            .field("gas_price", &self.gas_price)
             .finish_non_exhaustive()
     }
 }
@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::mem;
+use std::sync::Arc;
+
+use futures::join;
+use tokio::sync::broadcast;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+use tokio::time::Duration;
+use tokio::time::Instant;
+
+use crate::alias::JsonValue;
+use crate::eth::primitives::BlockHeader;
+use crate::eth::primitives::Hash;
+use crate::eth::primitives::LogFilter;
+use crate::eth::primitives::LogMined;
+use crate::eth::primitives::PendingTransactionEvent;
+use crate::eth::primitives::StratusError;
+use crate::eth::rpc::RpcClientApp;
+use crate::ext::not;
+use crate::ext::spawn_named;
+use crate::ext::to_json_value;
+use crate::ext::traced_sleep;
+use crate::ext::SleepReason;
+use crate::infra::tracing::warn_task_rx_closed;
+use crate::GlobalState;
+
+/// Frequency of cleaning up expired filters.
+const CLEANING_FREQUENCY: Duration = Duration::from_secs(10);
+
+/// Time a filter can stay without being polled before it is uninstalled.
+const FILTER_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// Max number of changes a filter accumulates between polls. Older changes are discarded when the limit is reached.
+const FILTER_MAX_CHANGES: usize = 10_000;
+
+/// Max wait since last checked shutdown in notifier.
+const NOTIFIER_SHUTDOWN_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// State of JSON-RPC polling filters.
+#[derive(Debug)]
+pub struct RpcFilters {
+    pub installed: Arc<RpcFiltersInstalled>,
+    pub handles: RpcFiltersHandles,
+}
+
+impl RpcFilters {
+    /// Creates a new filter manager that automatically spawns all necessary tasks in background.
+    pub fn spawn(
+        rx_pending_txs: broadcast::Receiver<PendingTransactionEvent>,
+        rx_blocks: broadcast::Receiver<BlockHeader>,
+        rx_logs: broadcast::Receiver<LogMined>,
+    ) -> Self {
+        let installed = Arc::new(RpcFiltersInstalled::default());
+
+        Self::spawn_filters_cleaner(Arc::clone(&installed));
+        let handles = RpcFiltersHandles {
+            new_pending_txs: Self::spawn_new_pending_txs_collector(Arc::clone(&installed), rx_pending_txs),
+            new_blocks: Self::spawn_new_blocks_collector(Arc::clone(&installed), rx_blocks),
+            logs: Self::spawn_logs_collector(Arc::clone(&installed), rx_logs),
+        };
+
+        Self { installed, handles }
+    }
+
+    /// Spawns a new task to uninstall filters that were not polled for a long time.
+    fn spawn_filters_cleaner(filters: Arc<RpcFiltersInstalled>) -> JoinHandle<anyhow::Result<()>> {
+        const TASK_NAME: &str = "rpc::filter::cleaner";
+        spawn_named(TASK_NAME, async move {
+            loop {
+                if GlobalState::is_shutdown_warn(TASK_NAME) {
+                    return Ok(());
+                }
+
+                // remove expired filters
+                let mut filters_cleaned = Vec::<(String, RpcClientApp)>::new();
+                filters.filters.write().await.retain(|id, filter| {
+                    let should_keep = filter.last_polled_at.elapsed() < FILTER_TIMEOUT;
+                    if !should_keep {
+                        filters_cleaned.push((id.clone(), filter.client.clone()));
+                    }
+                    should_keep
+                });
+
+                // log cleaned filters
+                if not(filters_cleaned.is_empty()) {
+                    tracing::info!(amount_cleaned = filters_cleaned.len(), filters = ?filters_cleaned, "cleaned expired filters");
+                }
+
+                // await next iteration
+                traced_sleep(CLEANING_FREQUENCY, SleepReason::Interval).await;
+            }
+        })
+    }
+
+    /// Spawns a new task that collects new executed transactions for pending transactions filters.
+    fn spawn_new_pending_txs_collector(
+        filters: Arc<RpcFiltersInstalled>,
+        mut rx_tx_event: broadcast::Receiver<PendingTransactionEvent>,
+    ) -> JoinHandle<anyhow::Result<()>> {
+        const TASK_NAME: &str = "rpc::filter::newPendingTransactions";
+        spawn_named(TASK_NAME, async move {
+            loop {
+                if GlobalState::is_shutdown_warn(TASK_NAME) {
+                    return Ok(());
+                }
+
+                let tx_event = match timeout(NOTIFIER_SHUTDOWN_CHECK_INTERVAL, rx_tx_event.recv()).await {
+                    Ok(Ok(tx_event)) => tx_event,
+                    Ok(Err(_channel_closed)) => break,
+                    Err(_timed_out) => continue,
+                };
+
+                // newPendingTransactions only reports transactions added to the pending block
+                let PendingTransactionEvent::Added { hash: tx_hash } = tx_event else {
+                    continue;
+                };
+
+                for filter in filters.filters.write().await.values_mut() {
+                    if let FilterChanges::PendingTransactions(hashes) = &mut filter.changes {
+                        push_change(hashes, tx_hash);
+                    }
+                }
+            }
+            warn_task_rx_closed(TASK_NAME);
+            Ok(())
+        })
+    }
+
+    /// Spawns a new task that collects new created blocks for block filters.
+    fn spawn_new_blocks_collector(filters: Arc<RpcFiltersInstalled>, mut rx_block: broadcast::Receiver<BlockHeader>) -> JoinHandle<anyhow::Result<()>> {
+        const TASK_NAME: &str = "rpc::filter::newBlocks";
+        spawn_named(TASK_NAME, async move {
+            loop {
+                if GlobalState::is_shutdown_warn(TASK_NAME) {
+                    return Ok(());
+                }
+
+                let block_header = match timeout(NOTIFIER_SHUTDOWN_CHECK_INTERVAL, rx_block.recv()).await {
+                    Ok(Ok(block)) => block,
+                    Ok(Err(_channel_closed)) => break,
+                    Err(_timed_out) => continue,
+                };
+
+                for filter in filters.filters.write().await.values_mut() {
+                    if let FilterChanges::Blocks(hashes) = &mut filter.changes {
+                        push_change(hashes, block_header.hash);
+                    }
+                }
+            }
+            warn_task_rx_closed(TASK_NAME);
+            Ok(())
+        })
+    }
+
+    /// Spawns a new task that collects new transactions logs for log filters.
+    fn spawn_logs_collector(filters: Arc<RpcFiltersInstalled>, mut rx_log_mined: broadcast::Receiver<LogMined>) -> JoinHandle<anyhow::Result<()>> {
+        const TASK_NAME: &str = "rpc::filter::logs";
+        spawn_named(TASK_NAME, async move {
+            loop {
+                if GlobalState::is_shutdown_warn(TASK_NAME) {
+                    return Ok(());
+                }
+
+                let log = match timeout(NOTIFIER_SHUTDOWN_CHECK_INTERVAL, rx_log_mined.recv()).await {
+                    Ok(Ok(log)) => log,
+                    Ok(Err(_channel_closed)) => break,
+                    Err(_timed_out) => continue,
+                };
+
+                for filter in filters.filters.write().await.values_mut() {
+                    if let FilterChanges::Logs(log_filter, logs) = &mut filter.changes {
+                        if log_filter.matches(&log) {
+                            push_change(logs, log.clone());
+                        }
+                    }
+                }
+            }
+            warn_task_rx_closed(TASK_NAME);
+            Ok(())
+        })
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Collector handles
+// -----------------------------------------------------------------------------
+
+/// Handles of filter background tasks.
+#[derive(Debug)]
+pub struct RpcFiltersHandles {
+    new_pending_txs: JoinHandle<anyhow::Result<()>>,
+    new_blocks: JoinHandle<anyhow::Result<()>>,
+    logs: JoinHandle<anyhow::Result<()>>,
+}
+
+impl RpcFiltersHandles {
+    pub async fn stopped(self) {
+        let _ = join!(self.new_pending_txs, self.new_blocks, self.logs);
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Installed filters
+// -----------------------------------------------------------------------------
+
+/// Filter installed by a client and the changes collected since it was last polled.
+#[derive(Debug)]
+pub struct Filter {
+    client: RpcClientApp,
+    last_polled_at: Instant,
+    changes: FilterChanges,
+}
+
+impl Filter {
+    fn new(client: RpcClientApp, changes: FilterChanges) -> Self {
+        Self {
+            client,
+            last_polled_at: Instant::now(),
+            changes,
+        }
+    }
+}
+
+/// Changes collected by a filter, according to its kind.
+#[derive(Debug)]
+enum FilterChanges {
+    /// Created by `eth_newFilter`.
+    Logs(LogFilter, VecDeque<LogMined>),
+
+    /// Created by `eth_newBlockFilter`.
+    Blocks(VecDeque<Hash>),
+
+    /// Created by `eth_newPendingTransactionFilter`.
+    PendingTransactions(VecDeque<Hash>),
+}
+
+/// Active client filters indexed by their id.
+#[derive(Debug, Default)]
+pub struct RpcFiltersInstalled {
+    pub filters: RwLock<HashMap<String, Filter>>,
+}
+
+impl RpcFiltersInstalled {
+    /// Installs a new filter that collects logs matching the filter criteria.
+    pub async fn add_logs_filter(&self, max_filters: u32, rpc_client: RpcClientApp, filter: LogFilter) -> Result<String, StratusError> {
+        tracing::info!(?filter, %rpc_client, "installing logs filter");
+        self.add_filter(max_filters, rpc_client, FilterChanges::Logs(filter, VecDeque::new())).await
+    }
+
+    /// Installs a new filter that collects hashes of new blocks.
+    pub async fn add_blocks_filter(&self, max_filters: u32, rpc_client: RpcClientApp) -> Result<String, StratusError> {
+        tracing::info!(%rpc_client, "installing blocks filter");
+        self.add_filter(max_filters, rpc_client, FilterChanges::Blocks(VecDeque::new())).await
+    }
+
+    /// Installs a new filter that collects hashes of new pending transactions.
+    pub async fn add_pending_txs_filter(&self, max_filters: u32, rpc_client: RpcClientApp) -> Result<String, StratusError> {
+        tracing::info!(%rpc_client, "installing pending transactions filter");
+        self.add_filter(max_filters, rpc_client, FilterChanges::PendingTransactions(VecDeque::new()))
+            .await
+    }
+
+    /// Takes all changes collected by a filter since it was last polled.
+    pub async fn take_filter_changes(&self, id: &str) -> Result<JsonValue, StratusError> {
+        let mut filters = self.filters.write().await;
+        let Some(filter) = filters.get_mut(id) else {
+            return Err(StratusError::RpcFilterNotFound { id: id.to_owned() });
+        };
+        filter.last_polled_at = Instant::now();
+
+        let changes = match &mut filter.changes {
+            FilterChanges::Logs(_, logs) => JsonValue::Array(mem::take(logs).into_iter().map(LogMined::to_json_rpc_log).collect()),
+            FilterChanges::Blocks(hashes) | FilterChanges::PendingTransactions(hashes) => to_json_value(mem::take(hashes)),
+        };
+        Ok(changes)
+    }
+
+    /// Retrieves the criteria of a logs filter.
+    pub async fn read_logs_filter(&self, id: &str) -> Result<LogFilter, StratusError> {
+        let mut filters = self.filters.write().await;
+        let Some(filter) = filters.get_mut(id) else {
+            return Err(StratusError::RpcFilterNotFound { id: id.to_owned() });
+        };
+        filter.last_polled_at = Instant::now();
+
+        match &filter.changes {
+            FilterChanges::Logs(log_filter, _) => Ok(log_filter.clone()),
+            _ => Err(StratusError::RpcFilterNotFound { id: id.to_owned() }),
+        }
+    }
+
+    /// Uninstalls a filter. Returns false if the filter does not exist.
+    pub async fn remove_filter(&self, id: &str) -> bool {
+        let removed = self.filters.write().await.remove(id);
+        if let Some(ref filter) = removed {
+            tracing::info!(%id, rpc_client = %filter.client, "uninstalling filter");
+        }
+        removed.is_some()
+    }
+
+    /// Installs a new filter if the client has not reached the max number of filters.
+    ///
+    /// The limit is checked under the same lock the filter is inserted, so concurrent requests cannot exceed it.
+    async fn add_filter(&self, max_filters: u32, rpc_client: RpcClientApp, changes: FilterChanges) -> Result<String, StratusError> {
+        let mut filters = self.filters.write().await;
+
+        let client_filters = filters.values().filter(|f| f.client == rpc_client).count();
+        tracing::info!(%client_filters, "current client filters");
+        if client_filters >= max_filters as usize {
+            return Err(StratusError::RpcFilterLimit { max: max_filters });
+        }
+
+        let id = new_filter_id();
+        filters.insert(id.clone(), Filter::new(rpc_client, changes));
+        Ok(id)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------------
+
+/// Generates a random filter id in the same format used by Geth.
+fn new_filter_id() -> String {
+    format!("0x{:032x}", rand::random::<u128>())
+}
+
+/// Appends a change to a filter, discarding the oldest one if the filter is full.
+fn push_change<T>(changes: &mut VecDeque<T>, change: T) {
+    if changes.len() >= FILTER_MAX_CHANGES {
+        changes.pop_front();
+    }
+    changes.push_back(change);
+}
@@ -81,9 +81,10 @@ impl<'a> RpcServiceT<'a> for RpcMiddleware {
         let mut client = request.extensions.rpc_client();
         let method = request.method_name().to_owned();
         let tx = match method.as_str() {
-            "eth_call" | "eth_estimateGas" => TransactionTracingIdentifiers::from_call(request.params()).ok(),
+            "eth_call" | "eth_estimateGas" | "debug_traceCall" => TransactionTracingIdentifiers::from_call(request.params()).ok(),
             "eth_sendRawTransaction" => TransactionTracingIdentifiers::from_transaction(request.params()).ok(),
-            "eth_getTransactionByHash" | "eth_getTransactionReceipt" => TransactionTracingIdentifiers::from_transaction_query(request.params()).ok(),
+            "eth_getTransactionByHash" | "eth_getTransactionReceipt" | "debug_traceTransaction" =>
+                TransactionTracingIdentifiers::from_transaction_query(request.params()).ok(),
             _ => None,
         };
         if let Some(tx_client) = tx.as_ref().and_then(|tx| tx.client.clone()) {
@@ -283,7 +284,7 @@ impl TransactionTracingIdentifiers {
         })
     }
 
-    /// eth_getTransactionByHash / eth_getTransactionReceipt
+    /// eth_getTransactionByHash / eth_getTransactionReceipt / debug_traceTransaction
     fn from_transaction_query(params: Params) -> anyhow::Result<Self> {
         let (_, hash) = next_rpc_param::<Hash>(params.sequence())?;
         Ok(Self {
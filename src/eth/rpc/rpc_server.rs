@@ -1,5 +1,6 @@
 //! RPC server for HTTP and WS.
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::ops::Deref;
 use std::str::FromStr;
@@ -8,6 +9,7 @@ use std::time::Duration;
 
 use anyhow::Result;
 use ethereum_types::U256;
+use ethereum_types::U64;
 use futures::join;
 use http::Method;
 use itertools::Itertools;
@@ -34,21 +36,37 @@ use tracing::Instrument;
 use tracing::Span;
 
 use super::rpc_method_wrapper::call_error_metrics_wrapper;
+use crate::alias::EthersReceipt;
+use crate::alias::EthersTransaction;
 use crate::alias::JsonValue;
 use crate::eth::executor::Executor;
 use crate::eth::follower::consensus::Consensus;
 use crate::eth::follower::importer::ImporterConfig;
 use crate::eth::miner::Miner;
 use crate::eth::miner::MinerMode;
+use crate::eth::primitives::decode_revert_reason;
 use crate::eth::primitives::Address;
 use crate::eth::primitives::BlockFilter;
+use crate::eth::primitives::BlockNumber;
+use crate::eth::primitives::BlockOverride;
+use crate::eth::primitives::BlockSearch;
 use crate::eth::primitives::Bytes;
 use crate::eth::primitives::CallInput;
 use crate::eth::primitives::ChainId;
+use crate::eth::primitives::FeeHistory;
 use crate::eth::primitives::Hash;
+use crate::eth::primitives::Index;
+use crate::eth::primitives::LogFilter;
 use crate::eth::primitives::LogFilterInput;
+use crate::eth::primitives::LogMined;
+use crate::eth::primitives::Nonce;
+use crate::eth::primitives::SimulatedCall;
+use crate::eth::primitives::SimulationOptions;
 use crate::eth::primitives::SlotIndex;
+use crate::eth::primitives::StateDiff;
+use crate::eth::primitives::StateOverride;
 use crate::eth::primitives::StratusError;
+use crate::eth::primitives::TracerOptions;
 use crate::eth::primitives::TransactionInput;
 use crate::eth::rpc::next_rpc_param;
 use crate::eth::rpc::next_rpc_param_or_default;
@@ -56,11 +74,11 @@ use crate::eth::rpc::parse_rpc_rlp;
 use crate::eth::rpc::rpc_parser::RpcExtensionsExt;
 use crate::eth::rpc::RpcClientApp;
 use crate::eth::rpc::RpcContext;
+use crate::eth::rpc::RpcFilters;
 use crate::eth::rpc::RpcHttpMiddleware;
 use crate::eth::rpc::RpcMiddleware;
 use crate::eth::rpc::RpcServerConfig;
 use crate::eth::rpc::RpcSubscriptions;
-use crate::eth::storage::StoragePointInTime;
 use crate::eth::storage::StratusStorage;
 use crate::ext::not;
 use crate::ext::parse_duration;
@@ -101,12 +119,18 @@ pub async fn serve_rpc(
         miner.notifier_logs.subscribe(),
     );
 
+    // configure filters
+    let filters = RpcFilters::spawn(
+        miner.notifier_pending_txs.subscribe(),
+        miner.notifier_blocks.subscribe(),
+        miner.notifier_logs.subscribe(),
+    );
+
     // configure context
     let ctx = RpcContext {
         app_config: to_json_value(app_config),
         chain_id,
         client_version: "stratus",
-        gas_price: 0,
 
         // services
         executor,
@@ -117,6 +141,13 @@ pub async fn serve_rpc(
 
         // subscriptions
         subs: Arc::clone(&subs.connected),
+
+        // filters
+        filters: Arc::clone(&filters.installed),
+
+        // dev
+        #[cfg(feature = "dev")]
+        impersonated_accounts: Default::default(),
     };
 
     // configure module
@@ -156,8 +187,8 @@ pub async fn serve_rpc(
         }
     }
 
-    // await rpc server and subscriptions to finish
-    join!(handle_rpc_server.stopped(), subs.handles.stopped());
+    // await rpc server, subscriptions and filters to finish
+    join!(handle_rpc_server.stopped(), subs.handles.stopped(), filters.handles.stopped());
 
     Ok(())
 }
@@ -170,6 +201,24 @@ fn register_methods(mut module: RpcModule<RpcContext>) -> anyhow::Result<RpcModu
         module.register_blocking_method("evm_mine", evm_mine)?;
         module.register_blocking_method("hardhat_reset", stratus_reset)?;
         module.register_blocking_method("stratus_reset", stratus_reset)?;
+        module.register_blocking_method("evm_increaseTime", evm_increase_time)?;
+        module.register_blocking_method("evm_snapshot", evm_snapshot)?;
+        module.register_blocking_method("evm_revert", evm_revert)?;
+        module.register_blocking_method("hardhat_mine", hardhat_mine)?;
+        module.register_blocking_method("anvil_mine", hardhat_mine)?;
+        module.register_blocking_method("hardhat_setBalance", hardhat_set_balance)?;
+        module.register_blocking_method("anvil_setBalance", hardhat_set_balance)?;
+        module.register_blocking_method("hardhat_setCode", hardhat_set_code)?;
+        module.register_blocking_method("anvil_setCode", hardhat_set_code)?;
+        module.register_blocking_method("hardhat_setNonce", hardhat_set_nonce)?;
+        module.register_blocking_method("anvil_setNonce", hardhat_set_nonce)?;
+        module.register_blocking_method("hardhat_setStorageAt", hardhat_set_storage_at)?;
+        module.register_blocking_method("anvil_setStorageAt", hardhat_set_storage_at)?;
+        module.register_blocking_method("hardhat_impersonateAccount", hardhat_impersonate_account)?;
+        module.register_blocking_method("anvil_impersonateAccount", hardhat_impersonate_account)?;
+        module.register_blocking_method("hardhat_stopImpersonatingAccount", hardhat_stop_impersonating_account)?;
+        module.register_blocking_method("anvil_stopImpersonatingAccount", hardhat_stop_impersonating_account)?;
+        module.register_blocking_method("eth_sendTransaction", eth_send_transaction)?;
     }
 
     // stratus status
@@ -204,23 +253,45 @@ fn register_methods(mut module: RpcModule<RpcContext>) -> anyhow::Result<RpcModu
 
     // gas
     module.register_method("eth_gasPrice", eth_gas_price)?;
+    module.register_method("eth_maxPriorityFeePerGas", eth_max_priority_fee_per_gas)?;
+    module.register_blocking_method("eth_feeHistory", eth_fee_history)?;
 
     // block
     module.register_blocking_method("eth_blockNumber", eth_block_number)?;
     module.register_blocking_method("eth_getBlockByNumber", eth_get_block_by_number)?;
     module.register_blocking_method("eth_getBlockByHash", eth_get_block_by_hash)?;
+    module.register_blocking_method("eth_getBlockTransactionCountByHash", eth_get_block_transaction_count_by_hash)?;
+    module.register_blocking_method("eth_getBlockTransactionCountByNumber", eth_get_block_transaction_count_by_number)?;
+    module.register_blocking_method("eth_getBlockReceipts", eth_get_block_receipts)?;
     module.register_method("eth_getUncleByBlockHashAndIndex", eth_get_uncle_by_block_hash_and_index)?;
+    module.register_blocking_method("eth_getUncleCountByBlockHash", eth_get_uncle_count_by_block_hash)?;
+    module.register_blocking_method("eth_getUncleCountByBlockNumber", eth_get_uncle_count_by_block_number)?;
+    module.register_blocking_method("eth_syncing", eth_syncing)?;
 
     // transactions
     module.register_blocking_method("eth_getTransactionByHash", eth_get_transaction_by_hash)?;
+    module.register_blocking_method("eth_getTransactionByBlockHashAndIndex", eth_get_transaction_by_block_hash_and_index)?;
+    module.register_blocking_method("eth_getTransactionByBlockNumberAndIndex", eth_get_transaction_by_block_number_and_index)?;
     module.register_blocking_method("eth_getTransactionReceipt", eth_get_transaction_receipt)?;
+    module.register_blocking_method("stratus_getInternalTransactions", stratus_get_internal_transactions)?;
+    module.register_blocking_method("stratus_getStateDiff", stratus_get_state_diff)?;
     module.register_blocking_method("eth_estimateGas", eth_estimate_gas)?;
+    module.register_blocking_method("eth_createAccessList", eth_create_access_list)?;
     module.register_blocking_method("eth_call", call_error_metrics_wrapper(eth_call))?;
+    module.register_blocking_method("stratus_simulateBundle", call_error_metrics_wrapper(stratus_simulate_bundle))?;
     module.register_blocking_method("eth_sendRawTransaction", call_error_metrics_wrapper(eth_send_raw_transaction))?;
 
     // logs
     module.register_blocking_method("eth_getLogs", eth_get_logs)?;
 
+    // filters
+    module.register_blocking_method("eth_newFilter", eth_new_filter)?;
+    module.register_blocking_method("eth_newBlockFilter", eth_new_block_filter)?;
+    module.register_blocking_method("eth_newPendingTransactionFilter", eth_new_pending_transaction_filter)?;
+    module.register_blocking_method("eth_getFilterChanges", eth_get_filter_changes)?;
+    module.register_blocking_method("eth_getFilterLogs", eth_get_filter_logs)?;
+    module.register_blocking_method("eth_uninstallFilter", eth_uninstall_filter)?;
+
     // account
     module.register_method("eth_accounts", eth_accounts)?;
     module.register_blocking_method("eth_getTransactionCount", eth_get_transaction_count)?;
@@ -229,6 +300,28 @@ fn register_methods(mut module: RpcModule<RpcContext>) -> anyhow::Result<RpcModu
 
     // storage
     module.register_blocking_method("eth_getStorageAt", eth_get_storage_at)?;
+    module.register_blocking_method("eth_getProof", eth_get_proof)?;
+
+    // debug
+    module.register_blocking_method("debug_traceTransaction", debug_trace_transaction)?;
+    module.register_blocking_method("debug_traceCall", debug_trace_call)?;
+
+    // trace
+    module.register_blocking_method("trace_replayBlockTransactions", trace_replay_block_transactions)?;
+
+    // otterscan
+    module.register_method("ots_getApiLevel", ots_get_api_level)?;
+    module.register_blocking_method("ots_hasCode", ots_has_code)?;
+    module.register_blocking_method("ots_getTransactionBySenderAndNonce", ots_get_transaction_by_sender_and_nonce)?;
+    module.register_blocking_method("ots_getContractCreator", ots_get_contract_creator)?;
+    module.register_blocking_method("ots_getInternalOperations", ots_get_internal_operations)?;
+    module.register_blocking_method("ots_searchTransactionsBefore", ots_search_transactions_before)?;
+    module.register_blocking_method("ots_searchTransactionsAfter", ots_search_transactions_after)?;
+
+    // txpool
+    module.register_blocking_method("txpool_status", txpool_status)?;
+    module.register_blocking_method("txpool_content", txpool_content)?;
+    module.register_blocking_method("txpool_inspect", txpool_inspect)?;
 
     // subscriptions
     module.register_subscription("eth_subscribe", "eth_subscription", "eth_unsubscribe", eth_subscribe)?;
@@ -240,6 +333,130 @@ fn register_methods(mut module: RpcModule<RpcContext>) -> anyhow::Result<RpcModu
 // Debug
 // -----------------------------------------------------------------------------
 
+fn debug_trace_transaction(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::debug_traceTransaction", tx_hash = field::Empty, tracer = field::Empty).entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (params, tx_hash) = next_rpc_param::<Hash>(params.sequence())?;
+    let (_, options) = next_rpc_param_or_default::<TracerOptions>(params)?;
+
+    // track
+    Span::with(|s| {
+        s.rec_str("tx_hash", &tx_hash);
+        s.rec_str("tracer", &options.tracer);
+    });
+    tracing::info!(%tx_hash, tracer = %options.tracer, "executing debug_traceTransaction");
+
+    // execute
+    match ctx.executor.trace_transaction(tx_hash, options) {
+        Ok(trace) => Ok(to_json_value(trace)),
+        Err(e) => {
+            if e.is_internal() {
+                tracing::error!(reason = ?e, "failed to execute debug_traceTransaction");
+            }
+            Err(e)
+        }
+    }
+}
+
+fn debug_trace_call(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!(
+        "rpc::debug_traceCall",
+        tx_from = field::Empty,
+        tx_to = field::Empty,
+        filter = field::Empty,
+        tracer = field::Empty
+    )
+    .entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (params, call) = next_rpc_param::<CallInput>(params.sequence())?;
+    let (params, filter) = next_rpc_param_or_default::<BlockFilter>(params)?;
+    let (_, options) = next_rpc_param_or_default::<TracerOptions>(params)?;
+
+    // track
+    Span::with(|s| {
+        s.rec_opt("tx_from", &call.from);
+        s.rec_opt("tx_to", &call.to);
+        s.rec_str("filter", &filter);
+        s.rec_str("tracer", &options.tracer);
+    });
+    tracing::info!(%filter, tracer = %options.tracer, "executing debug_traceCall");
+
+    // execute
+    let point_in_time = ctx.storage.translate_to_point_in_time(&filter)?;
+    match ctx.executor.trace_local_call(call, point_in_time, options) {
+        Ok(trace) => Ok(to_json_value(trace)),
+        Err(e) => {
+            if e.is_internal() {
+                tracing::error!(reason = ?e, "failed to execute debug_traceCall");
+            }
+            Err(e)
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Trace
+// -----------------------------------------------------------------------------
+
+/// Returns the persisted state diffs of the block transactions in the format of Parity/OpenEthereum.
+///
+/// Transactions are not replayed, so only the `stateDiff` trace type is supported.
+fn trace_replay_block_transactions(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::trace_replayBlockTransactions", filter = field::Empty, found = field::Empty).entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (params, filter) = next_rpc_param::<BlockFilter>(params.sequence())?;
+    let (_, trace_types) = next_rpc_param::<Vec<String>>(params)?;
+    if let Some(trace_type) = trace_types.iter().find(|trace_type| trace_type.as_str() != "stateDiff") {
+        return Err(StratusError::RpcParameterInvalid {
+            rust_type: "Vec<String>",
+            decode_error: format!("trace type {} is not supported, only stateDiff is", trace_type),
+        });
+    }
+    let with_state_diff = not(trace_types.is_empty());
+
+    // track
+    Span::with(|s| s.rec_str("filter", &filter));
+    tracing::info!(%filter, "reading block transactions state diffs");
+
+    // execute
+    let block = ctx.storage.read_block(&filter)?;
+    Span::with(|s| s.record("found", block.is_some()));
+    let Some(block) = block else { return Ok(JsonValue::Null) };
+
+    let mut traces = Vec::with_capacity(block.transactions.len());
+    for tx in block.transactions {
+        let state_diff = if with_state_diff {
+            ctx.storage.read_state_diff(&tx.input.hash)?.map(|state_diff| state_diff.to_parity_json())
+        } else {
+            None
+        };
+        traces.push(json!({
+            "output": tx.execution.output,
+            "stateDiff": state_diff,
+            "trace": [],
+            "vmTrace": null,
+            "transactionHash": tx.input.hash,
+        }));
+    }
+    Ok(JsonValue::Array(traces))
+}
+
+// -----------------------------------------------------------------------------
+// Dev
+// -----------------------------------------------------------------------------
+
 #[cfg(feature = "dev")]
 fn evm_mine(_params: Params<'_>, ctx: Arc<RpcContext>, _: Extensions) -> Result<JsonValue, StratusError> {
     ctx.miner.mine_local_and_commit()?;
@@ -260,6 +477,233 @@ fn evm_set_next_block_timestamp(params: Params<'_>, ctx: Arc<RpcContext>, _: Ext
     Ok(to_json_value(timestamp))
 }
 
+#[cfg(feature = "dev")]
+fn evm_increase_time(params: Params<'_>, _: Arc<RpcContext>, _: Extensions) -> Result<JsonValue, StratusError> {
+    use crate::eth::primitives::UnixTime;
+
+    let (_, seconds) = next_rpc_param::<U64>(params.sequence())?;
+    Ok(to_json_value(UnixTime::increase_offset(seconds.as_u64())))
+}
+
+#[cfg(feature = "dev")]
+fn evm_snapshot(_: Params<'_>, ctx: Arc<RpcContext>, _: Extensions) -> Result<JsonValue, StratusError> {
+    let id = ctx.storage.snapshot()?;
+    Ok(json!(hex_num(id)))
+}
+
+#[cfg(feature = "dev")]
+fn evm_revert(params: Params<'_>, ctx: Arc<RpcContext>, _: Extensions) -> Result<JsonValue, StratusError> {
+    let (_, id) = next_rpc_param::<U64>(params.sequence())?;
+    let reverted = ctx.storage.revert_to_snapshot(id.as_u64())?;
+    if reverted {
+        ctx.miner.reset_pending_base_fee()?;
+    }
+    Ok(to_json_value(reverted))
+}
+
+/// Max number of blocks that can be mined by a single `hardhat_mine` request.
+#[cfg(feature = "dev")]
+const MINE_MAX_BLOCK_COUNT: u64 = 1_000;
+
+/// Mines `count` blocks (default 1) with timestamps `interval` seconds apart (default 1).
+#[cfg(feature = "dev")]
+fn hardhat_mine(params: Params<'_>, ctx: Arc<RpcContext>, _: Extensions) -> Result<JsonValue, StratusError> {
+    use crate::eth::primitives::UnixTime;
+
+    let (params, count) = next_rpc_param_or_default::<Option<U64>>(params.sequence())?;
+    let (_, interval) = next_rpc_param_or_default::<Option<U64>>(params)?;
+    let count = count.map(|count| count.as_u64()).unwrap_or(1);
+    let interval = interval.map(|interval| interval.as_u64()).unwrap_or(1);
+    if count > MINE_MAX_BLOCK_COUNT {
+        return Err(StratusError::RpcMineLimit { max: MINE_MAX_BLOCK_COUNT });
+    }
+
+    for i in 0..count {
+        if i > 0 {
+            let Some(latest) = ctx.storage.read_block(&BlockFilter::Latest)? else {
+                return log_and_err!("reading latest block returned None")?;
+            };
+            let latest_timestamp = latest.header.timestamp;
+            UnixTime::set_offset(UnixTime::from(*latest_timestamp + interval), latest_timestamp)?;
+        }
+        ctx.miner.mine_local_and_commit()?;
+    }
+    Ok(to_json_value(true))
+}
+
+#[cfg(feature = "dev")]
+fn hardhat_set_balance(params: Params<'_>, ctx: Arc<RpcContext>, _: Extensions) -> Result<JsonValue, StratusError> {
+    use crate::eth::primitives::Wei;
+
+    let (params, address) = next_rpc_param::<Address>(params.sequence())?;
+    let (_, balance) = next_rpc_param::<Wei>(params)?;
+    dev_save_account_changes(&ctx, address, |changes| changes.balance.set_modified(balance))?;
+    Ok(to_json_value(true))
+}
+
+#[cfg(feature = "dev")]
+fn hardhat_set_code(params: Params<'_>, ctx: Arc<RpcContext>, _: Extensions) -> Result<JsonValue, StratusError> {
+    use crate::eth::primitives::CodeHash;
+
+    let (params, address) = next_rpc_param::<Address>(params.sequence())?;
+    let (_, code) = next_rpc_param::<Bytes>(params)?;
+    dev_save_account_changes(&ctx, address, |changes| {
+        changes.code_hash = CodeHash::from_bytecode(Some(code.clone()));
+        changes.bytecode.set_modified(Some(code));
+    })?;
+    Ok(to_json_value(true))
+}
+
+#[cfg(feature = "dev")]
+fn hardhat_set_nonce(params: Params<'_>, ctx: Arc<RpcContext>, _: Extensions) -> Result<JsonValue, StratusError> {
+    let (params, address) = next_rpc_param::<Address>(params.sequence())?;
+    let (_, nonce) = next_rpc_param::<Nonce>(params)?;
+    dev_save_account_changes(&ctx, address, |changes| changes.nonce.set_modified(nonce))?;
+    Ok(to_json_value(true))
+}
+
+#[cfg(feature = "dev")]
+fn hardhat_set_storage_at(params: Params<'_>, ctx: Arc<RpcContext>, _: Extensions) -> Result<JsonValue, StratusError> {
+    use crate::eth::primitives::ExecutionValueChange;
+    use crate::eth::primitives::Slot;
+    use crate::eth::primitives::SlotValue;
+    use crate::eth::storage::StoragePointInTime;
+
+    let (params, address) = next_rpc_param::<Address>(params.sequence())?;
+    let (params, index) = next_rpc_param::<SlotIndex>(params)?;
+    let (_, value) = next_rpc_param::<SlotValue>(params)?;
+
+    let mut slot = ExecutionValueChange::from_original(ctx.storage.read_slot(&address, &index, &StoragePointInTime::Mined)?);
+    slot.set_modified(Slot::new(index, value));
+    dev_save_account_changes(&ctx, address, |changes| {
+        changes.slots.insert(index, slot);
+    })?;
+    Ok(to_json_value(true))
+}
+
+/// Modifies an account outside of transactions, as if the changes were made in the last mined block.
+#[cfg(feature = "dev")]
+fn dev_save_account_changes(
+    ctx: &RpcContext,
+    address: Address,
+    modify: impl FnOnce(&mut crate::eth::primitives::ExecutionAccountChanges),
+) -> Result<(), StratusError> {
+    use crate::eth::primitives::ExecutionAccountChanges;
+    use crate::eth::storage::StoragePointInTime;
+
+    let account = ctx.storage.read_account(&address, &StoragePointInTime::Mined)?;
+    let mut changes = ExecutionAccountChanges::from_original_values(account);
+    modify(&mut changes);
+    ctx.storage.save_account_changes(vec![changes])
+}
+
+#[cfg(feature = "dev")]
+fn hardhat_impersonate_account(params: Params<'_>, ctx: Arc<RpcContext>, _: Extensions) -> Result<JsonValue, StratusError> {
+    let (_, address) = next_rpc_param::<Address>(params.sequence())?;
+    ctx.set_impersonated(address, true);
+    Ok(to_json_value(true))
+}
+
+#[cfg(feature = "dev")]
+fn hardhat_stop_impersonating_account(params: Params<'_>, ctx: Arc<RpcContext>, _: Extensions) -> Result<JsonValue, StratusError> {
+    let (_, address) = next_rpc_param::<Address>(params.sequence())?;
+    ctx.set_impersonated(address, false);
+    Ok(to_json_value(true))
+}
+
+/// Sends a transaction from an impersonated account without requiring it to be signed.
+///
+/// Missing nonce, gas and gas price are filled with the pending nonce of the sender, the estimated gas and the suggested gas price.
+#[cfg(feature = "dev")]
+fn eth_send_transaction(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<String, StratusError> {
+    use ethers_core::types::TransactionRequest;
+
+    use crate::eth::storage::StoragePointInTime;
+
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!(
+        "rpc::eth_sendTransaction",
+        tx_hash = field::Empty,
+        tx_from = field::Empty,
+        tx_to = field::Empty,
+        tx_nonce = field::Empty
+    )
+    .entered();
+
+    // parse params
+    let (_, request) = next_rpc_param::<TransactionRequest>(params.sequence())?;
+    let from: Address = request.from.unwrap_or_default().into();
+    if not(ctx.is_impersonated(&from)) {
+        return Err(StratusError::RpcSenderNotImpersonated { address: from });
+    }
+
+    // check feature
+    if not(GlobalState::is_transactions_enabled()) {
+        tracing::warn!("failed to execute eth_sendTransaction because transactions are disabled");
+        return Err(StratusError::RpcTransactionDisabled);
+    }
+
+    // fill missing fields
+    let to: Option<Address> = request.to.map(Into::into);
+    let value = request.value.unwrap_or_default().into();
+    let input: Bytes = request.data.map(|data| data.to_vec()).unwrap_or_default().into();
+    let nonce = match request.nonce {
+        Some(nonce) => nonce.as_u64().into(),
+        None => ctx.storage.read_account(&from, &StoragePointInTime::Pending)?.nonce,
+    };
+    let gas_limit = match request.gas {
+        Some(gas) => gas.as_u64().into(),
+        None => {
+            let call = CallInput {
+                from: Some(from),
+                to,
+                value,
+                data: input.clone(),
+                gas: None,
+            };
+            ctx.executor.estimate_gas(call, StoragePointInTime::Pending)?
+        }
+    };
+    let gas_price = match request.gas_price {
+        Some(gas_price) => gas_price.into(),
+        None => ctx.miner.pending_base_fee() + ctx.miner.fee_market().suggested_priority_fee(),
+    };
+    let tx = TransactionInput {
+        chain_id: Some(ctx.chain_id),
+        nonce,
+        signer: from,
+        from,
+        to,
+        value,
+        input,
+        gas_limit,
+        gas_price,
+        ..TransactionInput::default()
+    }
+    .sign_impersonated();
+    let tx_hash = tx.hash;
+
+    // track
+    Span::with(|s| {
+        s.rec_str("tx_hash", &tx_hash);
+        s.rec_str("tx_from", &tx.signer);
+        s.rec_opt("tx_to", &tx.to);
+        s.rec_str("tx_nonce", &tx.nonce);
+    });
+
+    // execute
+    match ctx.executor.submit_local_transaction(tx) {
+        Ok(_) => Ok(hex_data(tx_hash)),
+        Err(e) => {
+            if e.is_internal() {
+                tracing::error!(reason = ?e, "failed to execute eth_sendTransaction");
+            }
+            Err(e)
+        }
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Status - Health checks
 // -----------------------------------------------------------------------------
@@ -568,10 +1012,12 @@ async fn stratus_get_subscriptions(_: Params<'_>, ctx: Arc<RpcContext>, ext: Ext
     // NOTE: this is a workaround for holding only one lock at a time
     let pending_txs = serde_json::to_value(ctx.subs.new_heads.read().await.values().collect_vec()).expect_infallible();
     let new_heads = serde_json::to_value(ctx.subs.pending_txs.read().await.values().collect_vec()).expect_infallible();
+    let dropped_txs = serde_json::to_value(ctx.subs.dropped_txs.read().await.values().collect_vec()).expect_infallible();
     let logs = serde_json::to_value(ctx.subs.logs.read().await.values().flat_map(HashMap::values).collect_vec()).expect_infallible();
 
     let response = json!({
         "newPendingTransactions": pending_txs,
+        "droppedTransactions": dropped_txs,
         "newHeads": new_heads,
         "logs": logs,
     });
@@ -606,8 +1052,74 @@ fn web3_client_version(_: Params<'_>, ctx: &RpcContext, _: &Extensions) -> Strin
 // Gas
 // -----------------------------------------------------------------------------
 
-fn eth_gas_price(_: Params<'_>, _: &RpcContext, _: &Extensions) -> String {
-    hex_zero()
+/// Max number of blocks that can be read by a single fee history query.
+const FEE_HISTORY_MAX_BLOCK_COUNT: u64 = 1_024;
+
+fn eth_gas_price(_: Params<'_>, ctx: &RpcContext, _: &Extensions) -> String {
+    let gas_price = ctx.miner.pending_base_fee() + ctx.miner.fee_market().suggested_priority_fee();
+    hex_num(gas_price)
+}
+
+fn eth_max_priority_fee_per_gas(_: Params<'_>, ctx: &RpcContext, _: &Extensions) -> String {
+    hex_num(ctx.miner.fee_market().suggested_priority_fee())
+}
+
+fn eth_fee_history(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::eth_feeHistory", filter = field::Empty, block_count = field::Empty).entered();
+
+    // parse params
+    let (params, block_count) = next_rpc_param::<U64>(params.sequence())?;
+    let (params, filter) = next_rpc_param::<BlockFilter>(params)?;
+    let (_, reward_percentiles) = next_rpc_param_or_default::<Option<Vec<f64>>>(params)?;
+
+    // track
+    Span::with(|s| {
+        s.rec_str("filter", &filter);
+        s.rec_str("block_count", &block_count);
+    });
+    tracing::info!(%filter, %block_count, ?reward_percentiles, "reading fee history");
+
+    // validate percentiles
+    if let Some(ref percentiles) = reward_percentiles {
+        let in_range = percentiles.iter().all(|p| (0.0..=100.0).contains(p));
+        let ascending = percentiles.iter().tuple_windows().all(|(a, b)| a <= b);
+        if not(in_range && ascending) {
+            return Err(StratusError::RpcParameterInvalid {
+                rust_type: "Vec<f64>",
+                decode_error: "reward percentiles must be ascending values between 0 and 100".to_owned(),
+            });
+        }
+    }
+
+    // empty range
+    if block_count.is_zero() {
+        return Ok(json!({ "oldestBlock": hex_zero(), "baseFeePerGas": [], "gasUsedRatio": [] }));
+    }
+
+    // read newest block
+    let Some(newest_block) = ctx.storage.read_block(&filter)? else {
+        return Err(StratusError::RpcBlockFilterInvalid { filter });
+    };
+
+    // read remaining blocks from the oldest to the newest
+    let newest_number = newest_block.number();
+    let block_count = block_count.as_u64().min(FEE_HISTORY_MAX_BLOCK_COUNT).min(newest_number.as_u64() + 1);
+    let oldest_number = BlockNumber::from(newest_number.as_u64() + 1 - block_count);
+    let mut blocks = Vec::with_capacity(block_count as usize);
+    for number in oldest_number.as_u64()..newest_number.as_u64() {
+        match ctx.storage.read_block(&BlockFilter::Number(number.into()))? {
+            Some(block) => blocks.push(block),
+            None => return Err(StratusError::RpcBlockFilterInvalid { filter }),
+        }
+    }
+    let next_base_fee = ctx.miner.fee_market().next_base_fee(Some(&newest_block.header));
+    blocks.push(newest_block);
+
+    // execute
+    let fee_history = FeeHistory::new(oldest_number, &blocks, next_base_fee, reward_percentiles.as_deref());
+    Ok(to_json_value(fee_history))
 }
 
 // -----------------------------------------------------------------------------
@@ -693,53 +1205,238 @@ fn eth_get_uncle_by_block_hash_and_index(_: Params<'_>, _: &RpcContext, _: &Exte
     Ok(JsonValue::Null)
 }
 
-// -----------------------------------------------------------------------------
-// Transaction
-// -----------------------------------------------------------------------------
+fn eth_get_block_transaction_count_by_hash(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    eth_get_block_transaction_count_by_selector::<'h'>(params, ctx, ext)
+}
 
-fn eth_get_transaction_by_hash(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+fn eth_get_block_transaction_count_by_number(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    eth_get_block_transaction_count_by_selector::<'n'>(params, ctx, ext)
+}
+
+#[inline(always)]
+fn eth_get_block_transaction_count_by_selector<const KIND: char>(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
     // enter span
     let _middleware_enter = ext.enter_middleware_span();
-    let _method_enter = info_span!("rpc::eth_getTransactionByHash", tx_hash = field::Empty, found = field::Empty).entered();
+    let _method_enter = if KIND == 'h' {
+        info_span!("rpc::eth_getBlockTransactionCountByHash", filter = field::Empty, found = field::Empty).entered()
+    } else {
+        info_span!("rpc::eth_getBlockTransactionCountByNumber", filter = field::Empty, found = field::Empty).entered()
+    };
 
     // parse params
     reject_unknown_client(ext.rpc_client())?;
-    let (_, tx_hash) = next_rpc_param::<Hash>(params.sequence())?;
+    let (_, filter) = next_rpc_param::<BlockFilter>(params.sequence())?;
 
     // track
-    Span::with(|s| s.rec_str("tx_hash", &tx_hash));
-    tracing::info!(%tx_hash, "reading transaction");
+    Span::with(|s| s.rec_str("filter", &filter));
+    tracing::info!(%filter, "reading block transaction count");
 
     // execute
-    let tx = ctx.storage.read_transaction(&tx_hash)?;
-    Span::with(|s| {
-        s.record("found", tx.is_some());
-    });
-
-    match tx {
-        Some(tx) => {
-            tracing::info!(%tx_hash, "transaction found");
-            Ok(tx.to_json_rpc_transaction())
-        }
-        None => {
-            tracing::info!(%tx_hash, "transaction not found");
-            Ok(JsonValue::Null)
-        }
+    let block = ctx.storage.read_block(&filter)?;
+    Span::with(|s| s.record("found", block.is_some()));
+    match block {
+        Some(block) => Ok(json!(hex_num(block.transactions.len() as u64))),
+        None => Ok(JsonValue::Null),
     }
 }
 
-fn eth_get_transaction_receipt(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+fn eth_get_block_receipts(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
     // enter span
     let _middleware_enter = ext.enter_middleware_span();
-    let _method_enter = info_span!("rpc::eth_getTransactionReceipt", tx_hash = field::Empty, found = field::Empty).entered();
+    let _method_enter = info_span!("rpc::eth_getBlockReceipts", filter = field::Empty, found = field::Empty).entered();
 
     // parse params
     reject_unknown_client(ext.rpc_client())?;
-    let (_, tx_hash) = next_rpc_param::<Hash>(params.sequence())?;
+    let (_, filter) = next_rpc_param::<BlockFilter>(params.sequence())?;
 
     // track
-    Span::with(|s| s.rec_str("tx_hash", &tx_hash));
-    tracing::info!(%tx_hash, "reading transaction receipt");
+    Span::with(|s| s.rec_str("filter", &filter));
+    tracing::info!(%filter, "reading block receipts");
+
+    // execute
+    let block = ctx.storage.read_block(&filter)?;
+    Span::with(|s| s.record("found", block.is_some()));
+    match block {
+        Some(block) => {
+            let receipts = block.transactions.into_iter().map(EthersReceipt::from).collect_vec();
+            Ok(to_json_value(receipts))
+        }
+        None => Ok(JsonValue::Null),
+    }
+}
+
+fn eth_get_uncle_count_by_block_hash(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    eth_get_uncle_count_by_selector::<'h'>(params, ctx, ext)
+}
+
+fn eth_get_uncle_count_by_block_number(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    eth_get_uncle_count_by_selector::<'n'>(params, ctx, ext)
+}
+
+/// Stratus blocks never have uncles, so the count is always zero for existing blocks.
+#[inline(always)]
+fn eth_get_uncle_count_by_selector<const KIND: char>(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = if KIND == 'h' {
+        info_span!("rpc::eth_getUncleCountByBlockHash", filter = field::Empty, found = field::Empty).entered()
+    } else {
+        info_span!("rpc::eth_getUncleCountByBlockNumber", filter = field::Empty, found = field::Empty).entered()
+    };
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (_, filter) = next_rpc_param::<BlockFilter>(params.sequence())?;
+    Span::with(|s| s.rec_str("filter", &filter));
+
+    // execute
+    let block = ctx.storage.read_block(&filter)?;
+    Span::with(|s| s.record("found", block.is_some()));
+    match block {
+        Some(_) => Ok(json!(hex_zero())),
+        None => Ok(JsonValue::Null),
+    }
+}
+
+/// Returns the import progress of followers, or `false` when the node is not importing blocks.
+fn eth_syncing(_: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::eth_syncing", syncing = field::Empty).entered();
+
+    // only followers import blocks
+    let consensus = match GlobalState::get_node_mode() {
+        NodeMode::Leader => None,
+        NodeMode::Follower => ctx.consensus(),
+    };
+    let Some(consensus) = consensus else {
+        Span::with(|s| s.record("syncing", false));
+        return Ok(json!(false));
+    };
+
+    // execute
+    let (starting_block, highest_block) = consensus.import_progress();
+    let current_block = ctx.storage.read_mined_block_number()?;
+    let syncing = current_block < highest_block;
+    Span::with(|s| s.record("syncing", syncing));
+
+    if not(syncing) {
+        return Ok(json!(false));
+    }
+    Ok(json!({
+        "startingBlock": starting_block,
+        "currentBlock": current_block,
+        "highestBlock": highest_block,
+    }))
+}
+
+// -----------------------------------------------------------------------------
+// Transaction
+// -----------------------------------------------------------------------------
+
+fn eth_get_transaction_by_hash(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::eth_getTransactionByHash", tx_hash = field::Empty, found = field::Empty).entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (_, tx_hash) = next_rpc_param::<Hash>(params.sequence())?;
+
+    // track
+    Span::with(|s| s.rec_str("tx_hash", &tx_hash));
+    tracing::info!(%tx_hash, "reading transaction");
+
+    // execute
+    let tx = ctx.storage.read_transaction(&tx_hash)?;
+    Span::with(|s| {
+        s.record("found", tx.is_some());
+    });
+
+    match tx {
+        Some(tx) => {
+            tracing::info!(%tx_hash, "transaction found");
+            Ok(tx.to_json_rpc_transaction())
+        }
+        None => {
+            tracing::info!(%tx_hash, "transaction not found");
+            Ok(JsonValue::Null)
+        }
+    }
+}
+
+fn eth_get_transaction_by_block_hash_and_index(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    eth_get_transaction_by_block_selector_and_index::<'h'>(params, ctx, ext)
+}
+
+fn eth_get_transaction_by_block_number_and_index(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    eth_get_transaction_by_block_selector_and_index::<'n'>(params, ctx, ext)
+}
+
+#[inline(always)]
+fn eth_get_transaction_by_block_selector_and_index<const KIND: char>(
+    params: Params<'_>,
+    ctx: Arc<RpcContext>,
+    ext: Extensions,
+) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = if KIND == 'h' {
+        info_span!(
+            "rpc::eth_getTransactionByBlockHashAndIndex",
+            filter = field::Empty,
+            index = field::Empty,
+            found = field::Empty
+        )
+        .entered()
+    } else {
+        info_span!(
+            "rpc::eth_getTransactionByBlockNumberAndIndex",
+            filter = field::Empty,
+            index = field::Empty,
+            found = field::Empty
+        )
+        .entered()
+    };
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (params, filter) = next_rpc_param::<BlockFilter>(params.sequence())?;
+    let (_, index) = next_rpc_param::<U64>(params)?;
+    let index = Index::from(index);
+
+    // track
+    Span::with(|s| {
+        s.rec_str("filter", &filter);
+        s.rec_str("index", &index);
+    });
+    tracing::info!(%filter, %index, "reading transaction by block and index");
+
+    // execute
+    let tx = ctx
+        .storage
+        .read_block(&filter)?
+        .and_then(|block| block.transactions.into_iter().find(|tx| tx.transaction_index == index));
+    Span::with(|s| s.record("found", tx.is_some()));
+
+    match tx {
+        Some(tx) => Ok(to_json_value(EthersTransaction::from(tx))),
+        None => Ok(JsonValue::Null),
+    }
+}
+
+fn eth_get_transaction_receipt(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::eth_getTransactionReceipt", tx_hash = field::Empty, found = field::Empty).entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (_, tx_hash) = next_rpc_param::<Hash>(params.sequence())?;
+
+    // track
+    Span::with(|s| s.rec_str("tx_hash", &tx_hash));
+    tracing::info!(%tx_hash, "reading transaction receipt");
 
     // execute
     let tx = ctx.storage.read_transaction(&tx_hash)?;
@@ -759,41 +1456,143 @@ fn eth_get_transaction_receipt(params: Params<'_>, ctx: Arc<RpcContext>, ext: Ex
     }
 }
 
+fn stratus_get_internal_transactions(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::stratus_getInternalTransactions", tx_hash = field::Empty, found = field::Empty).entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (_, tx_hash) = next_rpc_param::<Hash>(params.sequence())?;
+
+    // track
+    Span::with(|s| s.rec_str("tx_hash", &tx_hash));
+    tracing::info!(%tx_hash, "reading internal transactions");
+
+    // execute
+    let tx = ctx.storage.read_transaction(&tx_hash)?;
+    Span::with(|s| s.record("found", tx.is_some()));
+
+    match tx {
+        Some(tx) => Ok(to_json_value(&tx.execution().internal_transactions)),
+        None => Ok(JsonValue::Null),
+    }
+}
+
+/// Returns the values modified by a transaction, or a list with the values modified by each transaction of a block.
+///
+/// A hash is looked up as a block hash first and then as a transaction hash.
+fn stratus_get_state_diff(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::stratus_getStateDiff", filter = field::Empty, found = field::Empty).entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (_, filter) = next_rpc_param::<BlockFilter>(params.sequence())?;
+
+    // track
+    Span::with(|s| s.rec_str("filter", &filter));
+    tracing::info!(%filter, "reading state diff");
+
+    // execute
+    let to_json = |tx_hash: Hash, state_diff: StateDiff| json!({ "transactionHash": tx_hash, "stateDiff": state_diff.to_parity_json() });
+    let block = ctx.storage.read_block(&filter)?;
+    match (block, filter) {
+        // state diffs of all block transactions
+        (Some(block), _) => {
+            Span::with(|s| s.record("found", true));
+            let mut state_diffs = Vec::with_capacity(block.transactions.len());
+            for tx in block.transactions {
+                if let Some(state_diff) = ctx.storage.read_state_diff(&tx.input.hash)? {
+                    state_diffs.push(to_json(tx.input.hash, state_diff));
+                }
+            }
+            Ok(JsonValue::Array(state_diffs))
+        }
+        // state diff of a single transaction
+        (None, BlockFilter::Hash(tx_hash)) => {
+            let state_diff = ctx.storage.read_state_diff(&tx_hash)?;
+            Span::with(|s| s.record("found", state_diff.is_some()));
+            match state_diff {
+                Some(state_diff) => Ok(to_json(tx_hash, state_diff)),
+                None => Ok(JsonValue::Null),
+            }
+        }
+        (None, _) => {
+            Span::with(|s| s.record("found", false));
+            Ok(JsonValue::Null)
+        }
+    }
+}
+
 fn eth_estimate_gas(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<String, StratusError> {
     // enter span
     let _middleware_enter = ext.enter_middleware_span();
-    let _method_enter = info_span!("rpc::eth_estimateGas", tx_from = field::Empty, tx_to = field::Empty).entered();
+    let _method_enter = info_span!("rpc::eth_estimateGas", tx_from = field::Empty, tx_to = field::Empty, filter = field::Empty).entered();
 
     // parse params
     reject_unknown_client(ext.rpc_client())?;
-    let (_, call) = next_rpc_param::<CallInput>(params.sequence())?;
+    let (params, call) = next_rpc_param::<CallInput>(params.sequence())?;
+    let (_, filter) = next_rpc_param_or_default::<BlockFilter>(params)?;
 
     // track
     Span::with(|s| {
         s.rec_opt("tx_from", &call.from);
         s.rec_opt("tx_to", &call.to);
+        s.rec_str("filter", &filter);
     });
-    tracing::info!("executing eth_estimateGas");
+    tracing::info!(%filter, "executing eth_estimateGas");
 
     // execute
-    match ctx.executor.execute_local_call(call, StoragePointInTime::Mined) {
+    let point_in_time = ctx.storage.translate_to_point_in_time(&filter)?;
+    match ctx.executor.estimate_gas(call, point_in_time) {
         // result is success
-        Ok(result) if result.is_success() => {
-            tracing::info!(tx_output = %result.output, "executed eth_estimateGas with success");
-            let overestimated_gas = (result.gas.as_u64()) as f64 * 1.1;
-            Ok(hex_num(overestimated_gas as u64))
+        Ok(gas) => {
+            tracing::info!(%gas, "executed eth_estimateGas with success");
+            Ok(hex_num(gas))
         }
 
         // result is failure
-        Ok(result) => {
-            tracing::warn!(tx_output = %result.output, "executed eth_estimateGas with failure");
-            Err(StratusError::TransactionReverted { output: result.output })
+        Err(e) => {
+            if e.is_internal() {
+                tracing::error!(reason = ?e, "failed to execute eth_estimateGas");
+            } else {
+                tracing::warn!(reason = ?e, "executed eth_estimateGas with failure");
+            }
+            Err(e)
         }
+    }
+}
 
-        // internal error
+fn eth_create_access_list(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::eth_createAccessList", tx_from = field::Empty, tx_to = field::Empty, filter = field::Empty).entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (params, call) = next_rpc_param::<CallInput>(params.sequence())?;
+    let (_, filter) = next_rpc_param_or_default::<BlockFilter>(params)?;
+
+    // track
+    Span::with(|s| {
+        s.rec_opt("tx_from", &call.from);
+        s.rec_opt("tx_to", &call.to);
+        s.rec_str("filter", &filter);
+    });
+    tracing::info!(%filter, "executing eth_createAccessList");
+
+    // execute
+    let point_in_time = ctx.storage.translate_to_point_in_time(&filter)?;
+    match ctx.executor.create_access_list(call, point_in_time) {
+        Ok(access_list) => {
+            tracing::info!(accounts = access_list.access_list.len(), gas_used = %access_list.gas_used, "executed eth_createAccessList");
+            Ok(to_json_value(access_list))
+        }
         Err(e) => {
             if e.is_internal() {
-                tracing::error!(reason = ?e, "failed to execute eth_estimateGas");
+                tracing::error!(reason = ?e, "failed to execute eth_createAccessList");
             }
             Err(e)
         }
@@ -808,7 +1607,19 @@ fn eth_call(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result
     // parse params
     reject_unknown_client(ext.rpc_client())?;
     let (params, call) = next_rpc_param::<CallInput>(params.sequence())?;
-    let (_, filter) = next_rpc_param_or_default::<BlockFilter>(params)?;
+    let (params, filter) = next_rpc_param_or_default::<BlockFilter>(params)?;
+    let (params, state_override) = next_rpc_param_or_default::<Option<StateOverride>>(params)?;
+    let (_, block_override) = next_rpc_param_or_default::<Option<BlockOverride>>(params)?;
+    let state_override = state_override.unwrap_or_default();
+    let block_override = block_override.unwrap_or_default();
+    for account_override in state_override.values() {
+        if let Err(decode_error) = account_override.validate() {
+            return Err(StratusError::RpcParameterInvalid {
+                rust_type: "StateOverride",
+                decode_error: decode_error.to_owned(),
+            });
+        }
+    }
 
     // track
     Span::with(|s| {
@@ -816,11 +1627,11 @@ fn eth_call(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result
         s.rec_opt("tx_to", &call.to);
         s.rec_str("filter", &filter);
     });
-    tracing::info!(%filter, "executing eth_call");
+    tracing::info!(%filter, overrides = state_override.len(), ?block_override, "executing eth_call");
 
     // execute
     let point_in_time = ctx.storage.translate_to_point_in_time(&filter)?;
-    match ctx.executor.execute_local_call(call, point_in_time) {
+    match ctx.executor.execute_local_call(call, point_in_time, state_override, block_override) {
         // result is success
         Ok(result) if result.is_success() => {
             tracing::info!(tx_output = %result.output, "executed eth_call with success");
@@ -829,7 +1640,7 @@ fn eth_call(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result
 
         // result is failure
         Ok(result) => {
-            tracing::warn!(tx_output = %result.output, "executed eth_call with failure");
+            tracing::warn!(tx_output = %result.output, revert_reason = ?decode_revert_reason(&result.output), "executed eth_call with failure");
             Err(StratusError::TransactionReverted { output: result.output })
         }
 
@@ -843,6 +1654,48 @@ fn eth_call(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result
     }
 }
 
+fn stratus_simulate_bundle(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::stratus_simulateBundle", calls = field::Empty, filter = field::Empty).entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (params, calls) = next_rpc_param::<Vec<CallInput>>(params.sequence())?;
+    let (params, filter) = next_rpc_param_or_default::<BlockFilter>(params)?;
+    let (_, options) = next_rpc_param_or_default::<SimulationOptions>(params)?;
+    if calls.len() > ctx.rpc_server.rpc_max_bundle_calls {
+        return Err(StratusError::RpcBundleLimit {
+            max: ctx.rpc_server.rpc_max_bundle_calls,
+        });
+    }
+
+    // track
+    Span::with(|s| {
+        s.rec_str("calls", &calls.len());
+        s.rec_str("filter", &filter);
+    });
+    tracing::info!(calls = calls.len(), %filter, "executing stratus_simulateBundle");
+
+    // execute
+    let point_in_time = ctx.storage.translate_to_point_in_time(&filter)?;
+    match ctx.executor.simulate_bundle(calls, point_in_time) {
+        Ok(executions) => {
+            let results = executions
+                .into_iter()
+                .map(|execution| SimulatedCall::new(execution, options.state_diff))
+                .collect_vec();
+            Ok(to_json_value(results))
+        }
+        Err(e) => {
+            if e.is_internal() {
+                tracing::error!(reason = ?e, "failed to execute stratus_simulateBundle");
+            }
+            Err(e)
+        }
+    }
+}
+
 fn eth_send_raw_transaction(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<String, StratusError> {
     // enter span
     let _middleware_enter = ext.enter_middleware_span();
@@ -877,7 +1730,7 @@ fn eth_send_raw_transaction(params: Params<'_>, ctx: Arc<RpcContext>, ext: Exten
 
     // execute locally or forward to leader
     match GlobalState::get_node_mode() {
-        NodeMode::Leader => match ctx.executor.execute_local_transaction(tx) {
+        NodeMode::Leader => match ctx.executor.submit_local_transaction(tx) {
             Ok(_) => Ok(hex_data(tx_hash)),
             Err(e) => {
                 if e.is_internal() {
@@ -904,8 +1757,6 @@ fn eth_send_raw_transaction(params: Params<'_>, ctx: Arc<RpcContext>, ext: Exten
 // -----------------------------------------------------------------------------
 
 fn eth_get_logs(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
-    const MAX_BLOCK_RANGE: u64 = 5_000;
-
     // enter span
     let _middleware_enter = ext.enter_middleware_span();
     let _method_enter = info_span!(
@@ -942,17 +1793,43 @@ fn eth_get_logs(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Re
     });
     tracing::info!(?filter, "reading logs");
 
+    // execute
+    let logs = read_logs_limited(&ctx, &filter)?;
+    Ok(JsonValue::Array(logs.into_iter().map(|x| x.to_json_rpc_log()).collect()))
+}
+
+/// Reads logs matching the filter, denying queries that read more blocks or return more logs than the configured limits.
+///
+/// Logs of the same block are never split, so a query is not denied when all its logs are in a single block, and the
+/// block range suggested in the error always makes progress.
+fn read_logs_limited(ctx: &RpcContext, filter: &LogFilter) -> Result<Vec<LogMined>, StratusError> {
     // check range
-    if blocks_in_range > MAX_BLOCK_RANGE {
-        return Err(StratusError::RpcBlockRangeInvalid {
-            actual: blocks_in_range,
-            max: MAX_BLOCK_RANGE,
-        });
+    if let Some(to_block) = filter.to_block {
+        let blocks_in_range = filter.from_block.count_to(&to_block);
+        let max = ctx.rpc_server.rpc_max_logs_block_range;
+        if blocks_in_range > max {
+            return Err(StratusError::RpcBlockRangeInvalid { actual: blocks_in_range, max });
+        }
     }
 
-    // execute
-    let logs = ctx.storage.read_logs(&filter)?;
-    Ok(JsonValue::Array(logs.into_iter().map(|x| x.to_json_rpc_log()).collect()))
+    let max = ctx.rpc_server.rpc_max_logs;
+    let logs = ctx.storage.read_logs(filter, max)?;
+    if logs.len() <= max {
+        return Ok(logs);
+    }
+
+    // the last block read exceeded the limit, so the client must retry up to the block before it
+    let (Some(first), Some(last)) = (logs.first(), logs.last()) else {
+        return Ok(logs);
+    };
+    match last.block_number.prev() {
+        Some(to) if first.block_number != last.block_number => Err(StratusError::RpcLogsLimit {
+            max,
+            from: filter.from_block,
+            to,
+        }),
+        _ => Ok(logs),
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -1031,6 +1908,307 @@ fn eth_get_code(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Re
     Ok(account.bytecode.map(hex_data).unwrap_or_else(hex_null))
 }
 
+// -----------------------------------------------------------------------------
+// Otterscan
+// -----------------------------------------------------------------------------
+
+/// Otterscan API level implemented by the `ots_` namespace.
+const OTS_API_LEVEL: u64 = 8;
+
+fn ots_get_api_level(_: Params<'_>, _: &RpcContext, _: &Extensions) -> u64 {
+    OTS_API_LEVEL
+}
+
+fn ots_has_code(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<bool, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::ots_hasCode", address = field::Empty, filter = field::Empty).entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (params, address) = next_rpc_param::<Address>(params.sequence())?;
+    let (_, filter) = next_rpc_param_or_default::<BlockFilter>(params)?;
+
+    // track
+    Span::with(|s| {
+        s.rec_str("address", &address);
+        s.rec_str("filter", &filter);
+    });
+
+    // execute
+    let point_in_time = ctx.storage.translate_to_point_in_time(&filter)?;
+    let account = ctx.storage.read_account(&address, &point_in_time)?;
+
+    Ok(account.bytecode.is_some_and(|bytecode| not(bytecode.is_empty())))
+}
+
+fn ots_get_transaction_by_sender_and_nonce(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!(
+        "rpc::ots_getTransactionBySenderAndNonce",
+        sender = field::Empty,
+        nonce = field::Empty,
+        found = field::Empty
+    )
+    .entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (params, sender) = next_rpc_param::<Address>(params.sequence())?;
+    let (_, nonce) = next_rpc_param::<u64>(params)?;
+    let nonce = Nonce::from(nonce);
+
+    // track
+    Span::with(|s| {
+        s.rec_str("sender", &sender);
+        s.rec_str("nonce", &nonce);
+    });
+    tracing::info!(%sender, %nonce, "reading transaction by sender and nonce");
+
+    // execute
+    let tx = ctx.storage.read_transaction_by_sender_and_nonce(&sender, nonce)?;
+    Span::with(|s| s.record("found", tx.is_some()));
+
+    match tx {
+        Some(tx) => Ok(to_json_value(tx.input.hash)),
+        None => Ok(JsonValue::Null),
+    }
+}
+
+fn ots_get_contract_creator(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::ots_getContractCreator", address = field::Empty, found = field::Empty).entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (_, address) = next_rpc_param::<Address>(params.sequence())?;
+
+    // track
+    Span::with(|s| s.rec_str("address", &address));
+    tracing::info!(%address, "reading contract creator");
+
+    // execute
+    let tx = ctx.storage.read_contract_creation(&address)?;
+    Span::with(|s| s.record("found", tx.is_some()));
+
+    match tx {
+        Some(tx) => Ok(json!({
+            "hash": tx.input.hash,
+            "creator": tx.contract_creator(&address),
+        })),
+        None => Ok(JsonValue::Null),
+    }
+}
+
+fn ots_get_internal_operations(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::ots_getInternalOperations", tx_hash = field::Empty, found = field::Empty).entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (_, tx_hash) = next_rpc_param::<Hash>(params.sequence())?;
+
+    // track
+    Span::with(|s| s.rec_str("tx_hash", &tx_hash));
+    tracing::info!(%tx_hash, "reading internal operations");
+
+    // execute
+    let tx = ctx.storage.read_transaction(&tx_hash)?;
+    Span::with(|s| s.record("found", tx.is_some()));
+
+    match tx {
+        Some(tx) => {
+            let operations = tx.execution().internal_transactions.iter().filter_map(|tx| tx.to_ots_operation()).collect_vec();
+            Ok(JsonValue::Array(operations))
+        }
+        None => Ok(JsonValue::Null),
+    }
+}
+
+fn ots_search_transactions_before(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    ots_search_transactions::<'b'>(params, ctx, ext)
+}
+
+fn ots_search_transactions_after(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    ots_search_transactions::<'a'>(params, ctx, ext)
+}
+
+/// Max number of transactions searched by a single transactions search page.
+const OTS_MAX_PAGE_SIZE: usize = 1_000;
+
+/// Searches a page of transactions of an address before or after a block.
+///
+/// Block number zero means searching from the most recent block (before) or from the genesis block (after). Transactions
+/// are returned from the most recent to the oldest in both directions. Pages larger than the max page size are reduced to it.
+#[inline(always)]
+fn ots_search_transactions<const KIND: char>(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = if KIND == 'b' {
+        info_span!("rpc::ots_searchTransactionsBefore", address = field::Empty, block_number = field::Empty).entered()
+    } else {
+        info_span!("rpc::ots_searchTransactionsAfter", address = field::Empty, block_number = field::Empty).entered()
+    };
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (params, address) = next_rpc_param::<Address>(params.sequence())?;
+    let (params, block_number) = next_rpc_param::<u64>(params)?;
+    let (_, page_size) = next_rpc_param::<usize>(params)?;
+    let page_size = page_size.min(OTS_MAX_PAGE_SIZE);
+
+    // track
+    Span::with(|s| {
+        s.rec_str("address", &address);
+        s.rec_str("block_number", &block_number);
+    });
+    tracing::info!(%address, %block_number, %page_size, "searching transactions");
+
+    // execute
+    let search = match (KIND, block_number) {
+        ('b', 0) => BlockSearch::Before(ctx.storage.read_mined_block_number()?.next_block_number()),
+        ('b', number) => BlockSearch::Before(number.into()),
+        (_, number) => BlockSearch::After(number.into()),
+    };
+    let mut txs = ctx.storage.read_transactions_by_address(&address, search, page_size)?;
+
+    // check if there are more transactions after the last block read
+    let has_more = match txs.last() {
+        Some(last_tx) => {
+            let next_search = match search {
+                BlockSearch::Before(_) => BlockSearch::Before(last_tx.block_number),
+                BlockSearch::After(_) => BlockSearch::After(last_tx.block_number),
+            };
+            not(ctx.storage.read_transactions_by_address(&address, next_search, 1)?.is_empty())
+        }
+        None => false,
+    };
+    let (first_page, last_page) = match search {
+        BlockSearch::Before(_) => (block_number == 0, not(has_more)),
+        BlockSearch::After(_) => {
+            txs.reverse();
+            (not(has_more), block_number == 0)
+        }
+    };
+
+    // read block timestamps for the receipts
+    let mut timestamps = HashMap::new();
+    for tx in &txs {
+        if let Some(block) = ctx.storage.read_block(&BlockFilter::Number(tx.block_number))? {
+            timestamps.insert(tx.block_number, block.header.timestamp.as_u64());
+        }
+    }
+
+    let mut json_txs = Vec::with_capacity(txs.len());
+    let mut json_receipts = Vec::with_capacity(txs.len());
+    for tx in txs {
+        let timestamp = timestamps.get(&tx.block_number).copied();
+        json_txs.push(to_json_value(EthersTransaction::from(tx.clone())));
+
+        let mut receipt = to_json_value(EthersReceipt::from(tx));
+        if let Some(receipt) = receipt.as_object_mut() {
+            receipt.insert("timestamp".to_owned(), json!(timestamp));
+        }
+        json_receipts.push(receipt);
+    }
+
+    Ok(json!({
+        "txs": json_txs,
+        "receipts": json_receipts,
+        "firstPage": first_page,
+        "lastPage": last_page,
+    }))
+}
+
+// -----------------------------------------------------------------------------
+// TxPool
+// -----------------------------------------------------------------------------
+
+/// Returns the number of local transactions executed in the pending block and the number of queued transactions.
+fn txpool_status(_: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::txpool_status").entered();
+    reject_unknown_client(ext.rpc_client())?;
+
+    // execute
+    let pending = txpool_pending_transactions(&ctx).len();
+    let queued = ctx.executor.transaction_pool().len();
+    Ok(json!({
+        "pending": hex_num(pending as u64),
+        "queued": hex_num(queued as u64),
+    }))
+}
+
+fn txpool_content(_: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::txpool_content").entered();
+    reject_unknown_client(ext.rpc_client())?;
+
+    // execute
+    let to_json = |tx: TransactionInput| to_json_value(EthersTransaction::from(tx));
+    let (pending, queued) = txpool_limited_transactions(&ctx);
+    Ok(json!({
+        "pending": txpool_group_transactions(pending, to_json),
+        "queued": txpool_group_transactions(queued, to_json),
+    }))
+}
+
+fn txpool_inspect(_: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::txpool_inspect").entered();
+    reject_unknown_client(ext.rpc_client())?;
+
+    // execute
+    let to_summary = |tx: TransactionInput| {
+        let to = match tx.to {
+            Some(to) => to.to_string(),
+            None => "contract creation".to_owned(),
+        };
+        json!(format!("{}: {} wei + {} gas × {} wei", to, tx.value, tx.gas_limit, tx.gas_price))
+    };
+    let (pending, queued) = txpool_limited_transactions(&ctx);
+    Ok(json!({
+        "pending": txpool_group_transactions(pending, to_summary),
+        "queued": txpool_group_transactions(queued, to_summary),
+    }))
+}
+
+/// Local transactions executed in the pending block.
+fn txpool_pending_transactions(ctx: &RpcContext) -> Vec<TransactionInput> {
+    ctx.storage
+        .pending_transactions()
+        .into_iter()
+        .filter_map(|tx| tx.as_local().map(|tx| tx.input))
+        .collect()
+}
+
+/// Pending and queued transactions up to the max returned by a single query, prioritizing the pending ones.
+fn txpool_limited_transactions(ctx: &RpcContext) -> (Vec<TransactionInput>, Vec<TransactionInput>) {
+    let max = ctx.rpc_server.rpc_max_txpool_transactions;
+    let mut pending = txpool_pending_transactions(ctx);
+    pending.truncate(max);
+    let mut queued = ctx.executor.transaction_pool().queued();
+    queued.truncate(max - pending.len());
+    (pending, queued)
+}
+
+/// Groups transactions by sender and nonce in the format returned by Geth `txpool_*` methods.
+fn txpool_group_transactions(txs: Vec<TransactionInput>, to_json: impl Fn(TransactionInput) -> JsonValue) -> JsonValue {
+    let mut senders: BTreeMap<String, BTreeMap<u64, JsonValue>> = BTreeMap::new();
+    for tx in txs {
+        let sender = tx.signer.to_string();
+        let nonce = tx.nonce.as_u64();
+        senders.entry(sender).or_default().insert(nonce, to_json(tx));
+    }
+    to_json_value(senders)
+}
+
 // -----------------------------------------------------------------------------
 // Subscriptions
 // -----------------------------------------------------------------------------
@@ -1074,6 +2252,14 @@ async fn eth_subscribe(params: Params<'_>, pending: PendingSubscriptionSink, ctx
                 .await;
         }
 
+        "droppedTransactions" => {
+            drop(method_enter);
+            ctx.subs
+                .add_dropped_txs_subscription(client, pending.accept().await?)
+                .instrument(method_span)
+                .await;
+        }
+
         "newHeads" => {
             drop(method_enter);
             ctx.subs
@@ -1104,6 +2290,109 @@ async fn eth_subscribe(params: Params<'_>, pending: PendingSubscriptionSink, ctx
     Ok(())
 }
 
+// -----------------------------------------------------------------------------
+// Filters
+// -----------------------------------------------------------------------------
+
+fn eth_new_filter(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<String, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::eth_newFilter", filter = field::Empty).entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let client = ext.rpc_client();
+    let (_, filter_input) = next_rpc_param_or_default::<LogFilterInput>(params.sequence())?;
+    let filter = filter_input.parse(&ctx.storage)?;
+
+    // track
+    Span::with(|s| s.rec_str("filter", &to_json_string(&filter)));
+
+    // execute
+    Handle::current().block_on(ctx.filters.add_logs_filter(ctx.rpc_server.rpc_max_filters, client, filter))
+}
+
+fn eth_new_block_filter(_: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<String, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::eth_newBlockFilter").entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let client = ext.rpc_client();
+
+    // execute
+    Handle::current().block_on(ctx.filters.add_blocks_filter(ctx.rpc_server.rpc_max_filters, client))
+}
+
+fn eth_new_pending_transaction_filter(_: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<String, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::eth_newPendingTransactionFilter").entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let client = ext.rpc_client();
+
+    // execute
+    Handle::current().block_on(ctx.filters.add_pending_txs_filter(ctx.rpc_server.rpc_max_filters, client))
+}
+
+fn eth_get_filter_changes(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::eth_getFilterChanges", filter_id = field::Empty).entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (_, filter_id) = next_rpc_param::<String>(params.sequence())?;
+
+    // track
+    Span::with(|s| s.rec_str("filter_id", &filter_id));
+
+    // execute
+    Handle::current().block_on(ctx.filters.take_filter_changes(&filter_id))
+}
+
+fn eth_get_filter_logs(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::eth_getFilterLogs", filter_id = field::Empty).entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (_, filter_id) = next_rpc_param::<String>(params.sequence())?;
+
+    // track
+    Span::with(|s| s.rec_str("filter_id", &filter_id));
+
+    // retrieve filter
+    let mut filter = Handle::current().block_on(ctx.filters.read_logs_filter(&filter_id))?;
+    if filter.to_block.is_none() {
+        filter.to_block = Some(ctx.storage.read_mined_block_number()?);
+    }
+
+    // execute
+    let logs = read_logs_limited(&ctx, &filter)?;
+    Ok(JsonValue::Array(logs.into_iter().map(|x| x.to_json_rpc_log()).collect()))
+}
+
+fn eth_uninstall_filter(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<bool, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::eth_uninstallFilter", filter_id = field::Empty).entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (_, filter_id) = next_rpc_param::<String>(params.sequence())?;
+
+    // track
+    Span::with(|s| s.rec_str("filter_id", &filter_id));
+
+    // execute
+    Ok(Handle::current().block_on(ctx.filters.remove_filter(&filter_id)))
+}
+
 // -----------------------------------------------------------------------------
 // Storage
 // -----------------------------------------------------------------------------
@@ -1132,6 +2421,28 @@ fn eth_get_storage_at(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions)
     Ok(hex_num_zero_padded(slot.value.as_u256()))
 }
 
+fn eth_get_proof(params: Params<'_>, ctx: Arc<RpcContext>, ext: Extensions) -> Result<JsonValue, StratusError> {
+    // enter span
+    let _middleware_enter = ext.enter_middleware_span();
+    let _method_enter = info_span!("rpc::eth_getProof", address = field::Empty, filter = field::Empty).entered();
+
+    // parse params
+    reject_unknown_client(ext.rpc_client())?;
+    let (params, address) = next_rpc_param::<Address>(params.sequence())?;
+    let (params, indexes) = next_rpc_param::<Vec<SlotIndex>>(params)?;
+    let (_, block_filter) = next_rpc_param_or_default::<BlockFilter>(params)?;
+
+    Span::with(|s| {
+        s.rec_str("address", &address);
+        s.rec_str("filter", &block_filter);
+    });
+
+    // execute
+    let point_in_time = ctx.storage.translate_to_point_in_time(&block_filter)?;
+    let proof = ctx.storage.read_proof(&address, &indexes, &point_in_time)?;
+    Ok(to_json_value(proof))
+}
+
 // -----------------------------------------------------------------------------
 // Request helpers
 // -----------------------------------------------------------------------------
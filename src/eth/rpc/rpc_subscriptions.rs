@@ -18,10 +18,10 @@ use tokio::time::Duration;
 
 use crate::eth::primitives::BlockHeader;
 use crate::eth::primitives::DateTimeNow;
-use crate::eth::primitives::Hash;
 use crate::eth::primitives::LogFilter;
 use crate::eth::primitives::LogFilterInput;
 use crate::eth::primitives::LogMined;
+use crate::eth::primitives::PendingTransactionEvent;
 use crate::eth::primitives::StratusError;
 use crate::eth::rpc::RpcClientApp;
 use crate::ext::not;
@@ -47,6 +47,7 @@ const NOTIFIER_SHUTDOWN_CHECK_INTERVAL: Duration = Duration::from_secs(2);
 #[cfg(feature = "metrics")]
 mod label {
     pub(super) const PENDING_TXS: &str = "newPendingTransactions";
+    pub(super) const DROPPED_TXS: &str = "droppedTransactions";
     pub(super) const NEW_HEADS: &str = "newHeads";
     pub(super) const LOGS: &str = "logs";
 }
@@ -60,7 +61,11 @@ pub struct RpcSubscriptions {
 
 impl RpcSubscriptions {
     /// Creates a new subscription manager that automatically spawns all necessary tasks in background.
-    pub fn spawn(rx_pending_txs: broadcast::Receiver<Hash>, rx_blocks: broadcast::Receiver<BlockHeader>, rx_logs: broadcast::Receiver<LogMined>) -> Self {
+    pub fn spawn(
+        rx_pending_txs: broadcast::Receiver<PendingTransactionEvent>,
+        rx_blocks: broadcast::Receiver<BlockHeader>,
+        rx_logs: broadcast::Receiver<LogMined>,
+    ) -> Self {
         let connected = Arc::new(RpcSubscriptionsConnected::default());
 
         Self::spawn_subscriptions_cleaner(Arc::clone(&connected));
@@ -84,6 +89,7 @@ impl RpcSubscriptions {
 
                 // store here which subscriptions were cleaned to later log them
                 let mut pending_txs_subs_cleaned = Vec::<RpcClientApp>::new();
+                let mut dropped_txs_subs_cleaned = Vec::<RpcClientApp>::new();
                 let mut new_heads_subs_cleaned = Vec::<RpcClientApp>::new();
                 let mut logs_subs_cleaned = Vec::<(RpcClientApp, LogFilterInput)>::new();
 
@@ -95,6 +101,13 @@ impl RpcSubscriptions {
                     }
                     should_keep
                 });
+                subs.dropped_txs.write().await.retain(|_, sub| {
+                    let should_keep = not(sub.sink.is_closed());
+                    if !should_keep {
+                        dropped_txs_subs_cleaned.push(sub.client.clone());
+                    }
+                    should_keep
+                });
                 subs.new_heads.write().await.retain(|_, sub| {
                     let should_keep = not(sub.sink.is_closed());
                     if !should_keep {
@@ -117,11 +130,12 @@ impl RpcSubscriptions {
                 });
 
                 // log cleaned subscriptions
-                let amount_cleaned = pending_txs_subs_cleaned.len() + new_heads_subs_cleaned.len() + logs_subs_cleaned.len();
+                let amount_cleaned = pending_txs_subs_cleaned.len() + dropped_txs_subs_cleaned.len() + new_heads_subs_cleaned.len() + logs_subs_cleaned.len();
                 if amount_cleaned > 0 {
                     tracing::info!(
                         amount_cleaned,
                         pending_txs = ?pending_txs_subs_cleaned,
+                        dropped_txs = ?dropped_txs_subs_cleaned,
                         new_heads = ?new_heads_subs_cleaned,
                         logs = ?logs_subs_cleaned,
                         "cleaned subscriptions",
@@ -136,6 +150,9 @@ impl RpcSubscriptions {
                     for client in pending_txs_subs_cleaned {
                         metrics::set_rpc_subscriptions_active(0, label::PENDING_TXS, client.to_string());
                     }
+                    for client in dropped_txs_subs_cleaned {
+                        metrics::set_rpc_subscriptions_active(0, label::DROPPED_TXS, client.to_string());
+                    }
                     for client in new_heads_subs_cleaned {
                         metrics::set_rpc_subscriptions_active(0, label::NEW_HEADS, client.to_string());
                     }
@@ -144,6 +161,7 @@ impl RpcSubscriptions {
                     }
 
                     sub_metrics::update_new_pending_txs_subscription_metrics(&(*subs.pending_txs.read().await));
+                    sub_metrics::update_dropped_txs_subscription_metrics(&(*subs.dropped_txs.read().await));
                     sub_metrics::update_new_heads_subscription_metrics(&(*subs.new_heads.read().await));
                     sub_metrics::update_logs_subscription_metrics(&(*subs.logs.read().await));
                 }
@@ -154,8 +172,11 @@ impl RpcSubscriptions {
         })
     }
 
-    /// Spawns a new task that notifies subscribers about new executed transactions.
-    fn spawn_new_pending_txs_notifier(subs: Arc<RpcSubscriptionsConnected>, mut rx_tx_hash: broadcast::Receiver<Hash>) -> JoinHandle<anyhow::Result<()>> {
+    /// Spawns a new task that notifies subscribers about new executed transactions and dropped transactions.
+    fn spawn_new_pending_txs_notifier(
+        subs: Arc<RpcSubscriptionsConnected>,
+        mut rx_tx_event: broadcast::Receiver<PendingTransactionEvent>,
+    ) -> JoinHandle<anyhow::Result<()>> {
         const TASK_NAME: &str = "rpc::sub::newPendingTransactions";
         spawn_named(TASK_NAME, async move {
             loop {
@@ -163,15 +184,25 @@ impl RpcSubscriptions {
                     return Ok(());
                 }
 
-                let tx_hash = match timeout(NOTIFIER_SHUTDOWN_CHECK_INTERVAL, rx_tx_hash.recv()).await {
-                    Ok(Ok(tx)) => tx,
+                let tx_event = match timeout(NOTIFIER_SHUTDOWN_CHECK_INTERVAL, rx_tx_event.recv()).await {
+                    Ok(Ok(tx_event)) => tx_event,
                     Ok(Err(_channel_closed)) => break,
                     Err(_timed_out) => continue,
                 };
 
-                let interested_subs = subs.pending_txs.read().await;
-                let interested_subs = interested_subs.values().collect_vec();
-                Self::notify(interested_subs, tx_hash.to_string());
+                match tx_event {
+                    // newPendingTransactions only reports the hashes of transactions added to the pending block
+                    PendingTransactionEvent::Added { hash: tx_hash } => {
+                        let interested_subs = subs.pending_txs.read().await;
+                        let interested_subs = interested_subs.values().collect_vec();
+                        Self::notify(interested_subs, tx_hash.to_string());
+                    }
+                    PendingTransactionEvent::Dropped { .. } => {
+                        let interested_subs = subs.dropped_txs.read().await;
+                        let interested_subs = interested_subs.values().collect_vec();
+                        Self::notify(interested_subs, tx_event);
+                    }
+                }
             }
             warn_task_rx_closed(TASK_NAME);
             Ok(())
@@ -346,6 +377,7 @@ pub struct SubscriptionWithFilter {
 #[derive(Debug, Default)]
 pub struct RpcSubscriptionsConnected {
     pub pending_txs: RwLock<HashMap<ConnectionId, Subscription>>,
+    pub dropped_txs: RwLock<HashMap<ConnectionId, Subscription>>,
     pub new_heads: RwLock<HashMap<ConnectionId, Subscription>>,
     pub logs: RwLock<HashMap<ConnectionId, HashMap<LogFilter, SubscriptionWithFilter>>>,
 }
@@ -354,6 +386,7 @@ impl RpcSubscriptionsConnected {
     /// Checks the number of subscriptions for a given client.
     pub async fn check_client_subscriptions(&self, max_subscriptions: u32, client: &RpcClientApp) -> Result<(), StratusError> {
         let pending_txs = self.pending_txs.read().await.values().filter(|s| s.client == *client).count();
+        let dropped_txs = self.dropped_txs.read().await.values().filter(|s| s.client == *client).count();
         let new_heads = self.new_heads.read().await.values().filter(|s| s.client == *client).count();
         let logs = self
             .logs
@@ -363,9 +396,9 @@ impl RpcSubscriptionsConnected {
             .flat_map(HashMap::values)
             .filter(|s| s.client == *client)
             .count();
-        tracing::info!(%pending_txs, %new_heads, %logs, "current client subscriptions");
+        tracing::info!(%pending_txs, %dropped_txs, %new_heads, %logs, "current client subscriptions");
 
-        if pending_txs + new_heads + logs >= max_subscriptions as usize {
+        if pending_txs + dropped_txs + new_heads + logs >= max_subscriptions as usize {
             return Err(StratusError::RpcSubscriptionLimit { max: max_subscriptions });
         }
 
@@ -386,6 +419,20 @@ impl RpcSubscriptionsConnected {
         sub_metrics::update_new_pending_txs_subscription_metrics(&subs);
     }
 
+    /// Adds a new subscriber to `droppedTransactions` event.
+    pub async fn add_dropped_txs_subscription(&self, rpc_client: RpcClientApp, sink: SubscriptionSink) {
+        tracing::info!(
+            id = sink.subscription_id().to_string_ext(),
+            %rpc_client,
+            "subscribing to droppedTransactions event"
+        );
+        let mut subs = self.dropped_txs.write().await;
+        subs.insert(sink.connection_id(), Subscription::new(rpc_client, sink.into()));
+
+        #[cfg(feature = "metrics")]
+        sub_metrics::update_dropped_txs_subscription_metrics(&subs);
+    }
+
     /// Adds a new subscriber to `newHeads` event.
     pub async fn add_new_heads_subscription(&self, rpc_client: RpcClientApp, sink: SubscriptionSink) {
         tracing::info!(
@@ -439,6 +486,10 @@ mod sub_metrics {
         update_subscription_count(label::PENDING_TXS, subs.values());
     }
 
+    pub fn update_dropped_txs_subscription_metrics(subs: &HashMap<ConnectionId, Subscription>) {
+        update_subscription_count(label::DROPPED_TXS, subs.values());
+    }
+
     pub fn update_new_heads_subscription_metrics(subs: &HashMap<ConnectionId, Subscription>) {
         update_subscription_count(label::NEW_HEADS, subs.values());
     }
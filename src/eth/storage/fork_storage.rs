@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use clap::Parser;
+use display_json::DebugAsJson;
+use futures::try_join;
+use tokio::runtime::Handle;
+
+use crate::eth::primitives::Account;
+use crate::eth::primitives::Address;
+use crate::eth::primitives::BlockNumber;
+use crate::eth::primitives::CodeHash;
+use crate::eth::primitives::Slot;
+use crate::eth::primitives::SlotIndex;
+use crate::ext::parse_duration;
+use crate::if_else;
+use crate::infra::BlockchainClient;
+
+/// Read-only copy of the state of another chain at a block.
+///
+/// Used to fill accounts and slots that are missing locally when Stratus runs forked from another chain.
+pub trait ForkStorage: Send + Sync + 'static {
+    /// Retrieves an account at the fork block.
+    fn read_account(&self, address: &Address) -> anyhow::Result<Account>;
+
+    /// Retrieves a slot at the fork block.
+    fn read_slot(&self, address: &Address, index: &SlotIndex) -> anyhow::Result<Slot>;
+}
+
+/// Fork storage that reads the state from an external node pinned at a block.
+///
+/// Reads block the current thread while the node is queried, so they must not be executed from async tasks.
+pub struct RpcForkStorage {
+    client: BlockchainClient,
+    block_number: BlockNumber,
+}
+
+impl RpcForkStorage {
+    pub fn new(client: BlockchainClient, block_number: BlockNumber) -> Self {
+        Self { client, block_number }
+    }
+}
+
+impl ForkStorage for RpcForkStorage {
+    fn read_account(&self, address: &Address) -> anyhow::Result<Account> {
+        let number = Some(self.block_number);
+        let (nonce, balance, bytecode) = Handle::current().block_on(async {
+            try_join!(
+                self.client.fetch_transaction_count(address, number),
+                self.client.fetch_balance(address, number),
+                self.client.fetch_code(address, number)
+            )
+        })?;
+
+        let bytecode = if_else!(bytecode.is_empty(), None, Some(bytecode));
+        Ok(Account {
+            address: *address,
+            nonce,
+            balance,
+            code_hash: CodeHash::from_bytecode(bytecode.clone()),
+            bytecode,
+        })
+    }
+
+    fn read_slot(&self, address: &Address, index: &SlotIndex) -> anyhow::Result<Slot> {
+        let value = Handle::current().block_on(self.client.fetch_storage_at(address, index, Some(self.block_number)))?;
+        Ok(Slot::new(*index, value))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Config
+// -----------------------------------------------------------------------------
+
+/// Fork configuration.
+#[derive(Parser, DebugAsJson, Clone, serde::Serialize)]
+pub struct ForkStorageConfig {
+    /// External RPC HTTP endpoint from which accounts and slots missing locally are fetched.
+    #[arg(long = "fork-url", env = "FORK_URL")]
+    pub fork_url: Option<String>,
+
+    /// Block of the external RPC the state is read from. Defaults to its current block.
+    #[arg(long = "fork-block", env = "FORK_BLOCK", requires = "fork_url")]
+    pub fork_block: Option<u64>,
+
+    /// Timeout for external RPC requests.
+    #[arg(long = "fork-timeout", value_parser=parse_duration, env = "FORK_TIMEOUT", default_value = "10s")]
+    pub fork_timeout: Duration,
+}
+
+impl ForkStorageConfig {
+    /// Initializes fork storage if a fork URL is configured.
+    pub async fn init(&self) -> anyhow::Result<Option<Box<dyn ForkStorage>>> {
+        let Some(ref fork_url) = self.fork_url else {
+            return Ok(None);
+        };
+        tracing::info!(config = ?self, "creating fork storage");
+
+        let client = BlockchainClient::new_http(fork_url, self.fork_timeout).await?;
+        let block_number = match self.fork_block {
+            Some(number) => BlockNumber::from(number),
+            None => client.fetch_block_number().await?,
+        };
+        tracing::info!(%fork_url, %block_number, "forking from external rpc");
+
+        Ok(Some(Box::new(RpcForkStorage::new(client, block_number))))
+    }
+}
@@ -1,5 +1,6 @@
 //! In-memory storage implementations.
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
@@ -12,22 +13,30 @@ use indexmap::IndexMap;
 use itertools::Itertools;
 
 use crate::eth::primitives::Account;
+use crate::eth::primitives::AccountProof;
 use crate::eth::primitives::Address;
 use crate::eth::primitives::Block;
 use crate::eth::primitives::BlockFilter;
 use crate::eth::primitives::BlockNumber;
+use crate::eth::primitives::BlockSearch;
 use crate::eth::primitives::Bytes;
 use crate::eth::primitives::CodeHash;
+use crate::eth::primitives::ExecutionAccountChanges;
 use crate::eth::primitives::Hash;
 use crate::eth::primitives::LogFilter;
 use crate::eth::primitives::LogMined;
 use crate::eth::primitives::Nonce;
 use crate::eth::primitives::Slot;
 use crate::eth::primitives::SlotIndex;
+use crate::eth::primitives::StateDiff;
 use crate::eth::primitives::TransactionMined;
 use crate::eth::primitives::Wei;
+use crate::eth::storage::check_block_state_root;
 use crate::eth::storage::inmemory::InMemoryHistory;
+use crate::eth::storage::prove_state_trie;
+use crate::eth::storage::update_state_trie;
 use crate::eth::storage::PermanentStorage;
+use crate::eth::storage::StateTrieUpdate;
 use crate::eth::storage::StoragePointInTime;
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
@@ -36,6 +45,103 @@ pub struct InMemoryPermanentStorageState {
     pub transactions: HashMap<Hash, Arc<Block>, hash_hasher::HashBuildHasher>,
     pub blocks_by_number: IndexMap<BlockNumber, Arc<Block>>,
     pub blocks_by_hash: IndexMap<Hash, Arc<Block>>,
+    pub bytecodes: HashMap<CodeHash, Bytes, hash_hasher::HashBuildHasher>,
+    pub trie_nodes: HashMap<Hash, Vec<u8>, hash_hasher::HashBuildHasher>,
+    pub state_roots: BTreeMap<BlockNumber, Hash>,
+}
+
+impl InMemoryPermanentStorageState {
+    /// Retrieves the state root at a point-in-time.
+    fn state_root(&self, point_in_time: &StoragePointInTime) -> Hash {
+        let root = match point_in_time {
+            StoragePointInTime::Mined | StoragePointInTime::Pending => self.state_roots.values().next_back(),
+            StoragePointInTime::MinedPast(number) => self.state_roots.range(..=*number).next_back().map(|(_, root)| root),
+        };
+        root.copied().unwrap_or(Hash::EMPTY_TRIE)
+    }
+
+    /// Persists the nodes of a state trie update and records the resulting state root at the specified block.
+    fn save_state_trie(&mut self, update: StateTrieUpdate, block_number: BlockNumber) {
+        self.trie_nodes.extend(update.nodes);
+        self.state_roots.insert(block_number, update.root);
+    }
+
+    /// Saves a block and its transactions, applying the account changes at the block.
+    fn save_block(&mut self, block: Block, account_changes: Vec<ExecutionAccountChanges>) -> anyhow::Result<()> {
+        // calculate and check state root before changing anything
+        let previous_root = self.state_root(&StoragePointInTime::Mined);
+        let update = update_state_trie(&self.trie_nodes, previous_root, &account_changes)?;
+        check_block_state_root(&block, previous_root, update.root, || {
+            let parent = block.number().prev().and_then(|parent_number| self.blocks_by_number.get(&parent_number));
+            Ok(parent.map(|parent| parent.header.state_root))
+        })?;
+
+        // save block
+        let block = Arc::new(block);
+        let block_number = block.number();
+        self.blocks_by_number.insert(block_number, Arc::clone(&block));
+        self.blocks_by_hash.insert(block.hash(), Arc::clone(&block));
+
+        // save transactions
+        for tx in &block.transactions {
+            self.transactions.insert(tx.input.hash, Arc::clone(&block));
+        }
+
+        // save block account changes
+        self.save_state_trie(update, block_number);
+        self.apply_account_values(account_changes, block_number);
+        Ok(())
+    }
+
+    /// Applies account changes made at the specified block to the accounts, bytecodes and state trie.
+    fn apply_account_changes(&mut self, account_changes: Vec<ExecutionAccountChanges>, block_number: BlockNumber) -> anyhow::Result<()> {
+        let update = update_state_trie(&self.trie_nodes, self.state_root(&StoragePointInTime::Mined), &account_changes)?;
+        self.save_state_trie(update, block_number);
+        self.apply_account_values(account_changes, block_number);
+        Ok(())
+    }
+
+    /// Applies account changes made at the specified block to the accounts and bytecodes.
+    fn apply_account_values(&mut self, account_changes: Vec<ExecutionAccountChanges>, block_number: BlockNumber) {
+        let mut bytecodes = Vec::new();
+        for changes in account_changes {
+            let account = self
+                .accounts
+                .entry(changes.address)
+                .or_insert_with(|| InMemoryPermanentAccount::new_empty(changes.address));
+
+            // account basic info
+            if let Some(nonce) = changes.nonce.take_modified() {
+                account.nonce.push(block_number, nonce);
+            }
+            if let Some(balance) = changes.balance.take_modified() {
+                account.balance.push(block_number, balance);
+            }
+
+            // bytecode
+            if let Some(Some(bytecode)) = changes.bytecode.take_modified() {
+                let code_hash = CodeHash::from_bytecode(Some(bytecode.clone()));
+                account.bytecode.push(block_number, Some(bytecode.clone()));
+                account.code_hash.push(block_number, code_hash);
+                bytecodes.push((code_hash, bytecode));
+            }
+
+            // slots
+            for (_, slot) in changes.slots {
+                if let Some(slot) = slot.take_modified() {
+                    match account.slots.get_mut(&slot.index) {
+                        Some(slot_history) => {
+                            slot_history.push(block_number, slot);
+                        }
+                        None => {
+                            account.slots.insert(slot.index, InMemoryHistory::new(block_number, slot));
+                        }
+                    }
+                }
+            }
+        }
+        self.bytecodes.extend(bytecodes);
+    }
 }
 
 #[derive(Debug)]
@@ -70,6 +176,9 @@ impl InMemoryPermanentStorage {
         state.transactions.clear();
         state.blocks_by_hash.clear();
         state.blocks_by_number.clear();
+        state.bytecodes.clear();
+        state.trie_nodes.clear();
+        state.state_roots.clear();
     }
 }
 
@@ -129,10 +238,15 @@ impl PermanentStorage for InMemoryPermanentStorage {
         }
     }
 
+    fn read_bytecode(&self, code_hash: &CodeHash) -> anyhow::Result<Option<Bytes>> {
+        let state = self.lock_read();
+        Ok(state.bytecodes.get(code_hash).cloned())
+    }
+
     fn read_block(&self, selection: &BlockFilter) -> anyhow::Result<Option<Block>> {
         let state_lock = self.lock_read();
         let block = match selection {
-            BlockFilter::Latest | BlockFilter::Pending => state_lock.blocks_by_number.values().last().cloned(),
+            BlockFilter::Latest | BlockFilter::Pending | BlockFilter::Safe | BlockFilter::Finalized => state_lock.blocks_by_number.values().last().cloned(),
             BlockFilter::Earliest => state_lock.blocks_by_number.values().next().cloned(),
             BlockFilter::Number(block_number) => state_lock.blocks_by_number.get(block_number).cloned(),
             BlockFilter::Hash(block_hash) => state_lock.blocks_by_hash.get(block_hash).cloned(),
@@ -143,13 +257,18 @@ impl PermanentStorage for InMemoryPermanentStorage {
         }
     }
 
+    fn read_block_hash(&self, number: BlockNumber) -> anyhow::Result<Option<Hash>> {
+        let state_lock = self.lock_read();
+        Ok(state_lock.blocks_by_number.get(&number).map(|block| block.hash()))
+    }
+
     fn read_transaction(&self, hash: &Hash) -> anyhow::Result<Option<TransactionMined>> {
         let state_lock = self.lock_read();
         let Some(block) = state_lock.transactions.get(hash) else { return Ok(None) };
         Ok(block.transactions.iter().find(|tx| &tx.input.hash == hash).cloned())
     }
 
-    fn read_logs(&self, filter: &LogFilter) -> anyhow::Result<Vec<LogMined>> {
+    fn read_logs(&self, filter: &LogFilter, limit: usize) -> anyhow::Result<Vec<LogMined>> {
         let state = self.lock_read();
 
         // determine block start and end
@@ -169,73 +288,144 @@ impl PermanentStorage for InMemoryPermanentStorage {
 
             let tx_logs = block.transactions.iter().flat_map(|tx| &tx.logs).filter(|log| filter.matches(log));
             filtered_logs.extend(tx_logs);
+            if filtered_logs.len() > limit {
+                break;
+            }
         }
 
         Ok(filtered_logs.into_iter().cloned().collect_vec())
     }
 
-    fn save_block(&self, block: Block) -> anyhow::Result<()> {
-        let mut state = self.lock_write();
+    fn read_transactions_by_address(&self, address: &Address, search: BlockSearch, limit: usize) -> anyhow::Result<Vec<TransactionMined>> {
+        let state = self.lock_read();
 
-        // save block
-        let block = Arc::new(block);
-        let block_number = block.number();
-        state.blocks_by_number.insert(block_number, Arc::clone(&block));
-        state.blocks_by_hash.insert(block.hash(), Arc::clone(&block));
+        // iterate blocks in the search order
+        let blocks = state
+            .blocks_by_number
+            .values()
+            .filter(|block| search.contains(block.number()))
+            .sorted_by_key(|block| block.number());
+        let blocks = match search {
+            BlockSearch::Before(_) => blocks.rev().collect_vec(),
+            BlockSearch::After(_) => blocks.collect_vec(),
+        };
 
-        // save transactions
-        for tx in &block.transactions {
-            state.transactions.insert(tx.input.hash, Arc::clone(&block));
+        // filter transactions until the limit is reached
+        let mut filtered_txs = Vec::new();
+        for block in blocks {
+            let block_txs = block.transactions.iter().filter(|tx| tx.involved_addresses().contains(address));
+            match search {
+                BlockSearch::Before(_) => filtered_txs.extend(block_txs.rev()),
+                BlockSearch::After(_) => filtered_txs.extend(block_txs),
+            }
+            if filtered_txs.len() >= limit {
+                break;
+            }
         }
 
-        // save block account changes
-        for changes in block.compact_account_changes() {
-            let account = state
-                .accounts
-                .entry(changes.address)
-                .or_insert_with(|| InMemoryPermanentAccount::new_empty(changes.address));
+        Ok(filtered_txs.into_iter().cloned().collect_vec())
+    }
 
-            // account basic info
-            if let Some(nonce) = changes.nonce.take_modified() {
-                account.nonce.push(block_number, nonce);
-            }
-            if let Some(balance) = changes.balance.take_modified() {
-                account.balance.push(block_number, balance);
-            }
+    fn read_transaction_by_sender_and_nonce(&self, sender: &Address, nonce: Nonce) -> anyhow::Result<Option<TransactionMined>> {
+        let state = self.lock_read();
+        let tx = state
+            .blocks_by_number
+            .values()
+            .flat_map(|block| &block.transactions)
+            .find(|tx| &tx.input.from == sender && tx.input.nonce == nonce);
+        Ok(tx.cloned())
+    }
 
-            // bytecode
-            if let Some(Some(bytecode)) = changes.bytecode.take_modified() {
-                account.bytecode.push(block_number, Some(bytecode));
-            }
+    fn read_contract_creation(&self, address: &Address) -> anyhow::Result<Option<TransactionMined>> {
+        let state = self.lock_read();
+        let tx = state
+            .blocks_by_number
+            .values()
+            .flat_map(|block| &block.transactions)
+            .find(|tx| tx.created_contracts().contains(address));
+        Ok(tx.cloned())
+    }
 
-            // slots
-            for (_, slot) in changes.slots {
-                if let Some(slot) = slot.take_modified() {
-                    match account.slots.get_mut(&slot.index) {
-                        Some(slot_history) => {
-                            slot_history.push(block_number, slot);
-                        }
-                        None => {
-                            account.slots.insert(slot.index, InMemoryHistory::new(block_number, slot));
-                        }
-                    }
-                }
-            }
-        }
+    fn read_state_diff(&self, tx_hash: &Hash) -> anyhow::Result<Option<StateDiff>> {
+        let state = self.lock_read();
+        let Some(block) = state.transactions.get(tx_hash) else { return Ok(None) };
+        let tx = block.transactions.iter().find(|tx| &tx.input.hash == tx_hash);
+        Ok(tx.map(|tx| StateDiff::from_changes(&tx.execution.changes)))
+    }
 
-        Ok(())
+    fn save_block(&self, block: Block) -> anyhow::Result<()> {
+        let account_changes = block.compact_account_changes();
+        self.lock_write().save_block(block, account_changes)
+    }
+
+    fn save_genesis(&self, block: Block, accounts: Vec<ExecutionAccountChanges>) -> anyhow::Result<()> {
+        self.lock_write().save_block(block, accounts)
     }
 
     fn save_accounts(&self, accounts: Vec<Account>) -> anyhow::Result<()> {
         let mut state = self.lock_write();
+        let mut changes = Vec::with_capacity(accounts.len());
         for account in accounts {
-            state
-                .accounts
-                .insert(account.address, InMemoryPermanentAccount::new_with_balance(account.address, account.balance));
+            let account = InMemoryPermanentAccount::new_with_balance(account.address, account.balance);
+            changes.push(ExecutionAccountChanges::from_modified_values(
+                account.to_account(&StoragePointInTime::Mined),
+                vec![],
+            ));
+            state.accounts.insert(account.address, account);
+        }
+
+        let block_number = state.state_roots.keys().next_back().copied().unwrap_or_default();
+        let update = update_state_trie(&state.trie_nodes, state.state_root(&StoragePointInTime::Mined), &changes)?;
+        state.save_state_trie(update, block_number);
+
+        Ok(())
+    }
+
+    fn save_account_changes(&self, changes: Vec<ExecutionAccountChanges>) -> anyhow::Result<()> {
+        let block_number = self.block_number.load(Ordering::SeqCst).into();
+        self.lock_write().apply_account_changes(changes, block_number)
+    }
+
+    fn save_fork_account(&self, account: Account) -> anyhow::Result<()> {
+        let mut state = self.lock_write();
+        if state.accounts.contains_key(&account.address) {
+            return Ok(());
+        }
+
+        // values read from the fork are the values since the first block, until they are changed locally
+        if let Some(ref bytecode) = account.bytecode {
+            state.bytecodes.insert(account.code_hash, bytecode.clone());
         }
+        state.accounts.insert(account.address, InMemoryPermanentAccount::new_from_account(account));
+        Ok(())
+    }
+
+    fn save_fork_slot(&self, address: &Address, slot: Slot) -> anyhow::Result<()> {
+        let mut state = self.lock_write();
+
+        // slots are kept inside accounts, so slots of accounts that are not stored yet are not cached
+        let Some(account) = state.accounts.get_mut(address) else {
+            return Ok(());
+        };
+        account.slots.entry(slot.index).or_insert_with(|| InMemoryHistory::new_at_zero(slot));
         Ok(())
     }
 
+    // -------------------------------------------------------------------------
+    // State trie operations
+    // -------------------------------------------------------------------------
+
+    fn compute_state_root(&self, changes: &[ExecutionAccountChanges]) -> anyhow::Result<Hash> {
+        let state = self.lock_read();
+        let update = update_state_trie(&state.trie_nodes, state.state_root(&StoragePointInTime::Mined), changes)?;
+        Ok(update.root)
+    }
+
+    fn read_proof(&self, address: &Address, indexes: &[SlotIndex], point_in_time: &StoragePointInTime) -> anyhow::Result<AccountProof> {
+        let state = self.lock_read();
+        prove_state_trie(&state.trie_nodes, state.state_root(point_in_time), *address, indexes)
+    }
+
     #[cfg(feature = "dev")]
     fn reset(&self) -> anyhow::Result<()> {
         self.block_number.store(0u64, Ordering::SeqCst);
@@ -245,6 +435,33 @@ impl PermanentStorage for InMemoryPermanentStorage {
 
         Ok(())
     }
+
+    #[cfg(feature = "dev")]
+    fn reset_at(&self, number: BlockNumber) -> anyhow::Result<()> {
+        self.block_number.store(number.as_u64(), Ordering::SeqCst);
+
+        let mut state = self.lock_write();
+
+        // blocks and transactions
+        state.blocks_by_number.retain(|block_number, _| *block_number <= number);
+        state.blocks_by_hash.retain(|_, block| block.number() <= number);
+        state.transactions.retain(|_, block| block.number() <= number);
+
+        // accounts and state roots
+        state.accounts = state
+            .accounts
+            .drain()
+            .filter_map(|(address, account)| Some((address, account.reset_at(number)?)))
+            .collect();
+        state.state_roots.retain(|block_number, _| *block_number <= number);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "dev")]
+    fn supports_reset_at(&self) -> bool {
+        true
+    }
 }
 
 /// TODO: group bytecode, code_hash, static_slot_indexes and mapping_slot_indexes into a single bytecode struct.
@@ -277,6 +494,33 @@ impl InMemoryPermanentAccount {
         }
     }
 
+    /// Creates a new permanent account with the account values since the first block.
+    fn new_from_account(account: Account) -> Self {
+        Self {
+            address: account.address,
+            balance: InMemoryHistory::new_at_zero(account.balance),
+            nonce: InMemoryHistory::new_at_zero(account.nonce),
+            bytecode: InMemoryHistory::new_at_zero(account.bytecode),
+            code_hash: InMemoryHistory::new_at_zero(account.code_hash),
+            slots: HashMap::default(),
+        }
+    }
+
+    /// Resets the account history to the specified block number.
+    ///
+    /// Returns `None` if the account did not exist at the block.
+    #[cfg(feature = "dev")]
+    fn reset_at(&self, number: BlockNumber) -> Option<Self> {
+        Some(Self {
+            address: self.address,
+            balance: self.balance.reset_at(number)?,
+            nonce: self.nonce.reset_at(number)?,
+            bytecode: self.bytecode.reset_at(number)?,
+            code_hash: self.code_hash.reset_at(number)?,
+            slots: self.slots.iter().filter_map(|(index, slot)| Some((*index, slot.reset_at(number)?))).collect(),
+        })
+    }
+
     /// Converts itself to an account at a point-in-time.
     pub fn to_account(&self, point_in_time: &StoragePointInTime) -> Account {
         Account {
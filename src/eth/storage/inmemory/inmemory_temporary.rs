@@ -5,6 +5,7 @@ use std::sync::RwLock;
 use std::sync::RwLockReadGuard;
 use std::sync::RwLockWriteGuard;
 
+use itertools::Itertools;
 use nonempty::NonEmpty;
 
 use crate::eth::primitives::Account;
@@ -13,6 +14,7 @@ use crate::eth::primitives::BlockNumber;
 use crate::eth::primitives::EvmExecution;
 use crate::eth::primitives::ExecutionConflicts;
 use crate::eth::primitives::ExecutionConflictsBuilder;
+use crate::eth::primitives::Gas;
 use crate::eth::primitives::Hash;
 use crate::eth::primitives::PendingBlock;
 use crate::eth::primitives::PendingBlockHeader;
@@ -21,6 +23,7 @@ use crate::eth::primitives::SlotIndex;
 use crate::eth::primitives::StratusError;
 use crate::eth::primitives::TransactionExecution;
 use crate::eth::storage::TemporaryStorage;
+use crate::ext::not;
 use crate::log_and_err;
 
 /// Number of previous blocks to keep inmemory to detect conflicts between different blocks.
@@ -144,42 +147,56 @@ impl TemporaryStorage for InMemoryTemporaryStorage {
             }
         }
 
-        // save account changes
-        let changes = tx.execution().changes.values();
-        for change in changes {
-            let account = states
-                .head
-                .accounts
-                .entry(change.address)
-                .or_insert_with(|| InMemoryTemporaryAccount::new(change.address));
-
-            // account basic info
-            if let Some(nonce) = change.nonce.take_ref() {
-                account.info.nonce = *nonce;
-            }
-            if let Some(balance) = change.balance.take_ref() {
-                account.info.balance = *balance;
-            }
-
-            // bytecode (todo: where is code_hash?)
-            if let Some(Some(bytecode)) = change.bytecode.take_ref() {
-                account.info.bytecode = Some(bytecode.clone());
-            }
-
-            // slots
-            for slot in change.slots.values() {
-                if let Some(slot) = slot.take_ref() {
-                    account.slots.insert(slot.index, *slot);
-                }
+        // check local transactions fit in the block gas limit
+        if tx.is_local() {
+            let pending_block = states.head.require_pending_block()?;
+            let block_gas = pending_block.transactions.values().map(|tx| tx.execution().gas.as_u64()).sum::<u64>();
+            if block_gas + tx.execution().gas.as_u64() > Gas::BLOCK_LIMIT.as_u64() {
+                return Err(StratusError::TransactionBlockGasLimitExceeded { limit: Gas::BLOCK_LIMIT });
             }
         }
 
+        // save account changes
+        do_apply_changes(&mut states.head, &tx);
+
         // save execution
         states.head.require_pending_block_mut()?.push_transaction(tx);
 
         Ok(())
     }
 
+    fn remove_pending_execution(&self, hash: &Hash) -> Result<Option<TransactionExecution>, StratusError> {
+        let mut states = self.lock_write();
+        let Some(ref mut pending_block) = states.head.block else { return Ok(None) };
+        let Some(index) = pending_block.transactions.get_index_of(hash) else {
+            return Ok(None);
+        };
+
+        // check executions saved after it did not access the accounts it modified
+        let modified_addresses = pending_block.transactions[index]
+            .execution()
+            .changes
+            .values()
+            .filter(|change| change.is_account_modified() || change.slots.values().any(|slot| slot.is_modified()))
+            .map(|change| change.address)
+            .collect_vec();
+        for (_, next_tx) in pending_block.transactions.iter().skip(index + 1) {
+            if modified_addresses.iter().any(|address| next_tx.execution().changes.contains_key(address)) {
+                return Err(StratusError::TransactionReplacementConflict { hash: *hash });
+            }
+        }
+
+        // remove execution and recreate accounts state from the remaining executions
+        let removed = pending_block.transactions.shift_remove_index(index).map(|(_, tx)| tx);
+        let remaining = pending_block.transactions.values().cloned().collect_vec();
+        states.head.accounts.clear();
+        for tx in &remaining {
+            do_apply_changes(&mut states.head, tx);
+        }
+
+        Ok(removed)
+    }
+
     fn read_pending_executions(&self) -> Vec<TransactionExecution> {
         self.lock_read()
             .head
@@ -206,6 +223,27 @@ impl TemporaryStorage for InMemoryTemporaryStorage {
         Ok(finished_block)
     }
 
+    fn restore_finished_block(&self) -> anyhow::Result<()> {
+        let mut states = self.lock_write();
+        let pending_block = states.head.require_pending_block()?;
+        if not(pending_block.transactions.is_empty()) {
+            return log_and_err!("failed to restore finished block because the pending block already has transactions");
+        }
+
+        // the state of the finished block is kept right after the pending block state
+        let pending_number = pending_block.header.number;
+        let finished_number = states.tail.first().and_then(|state| state.block.as_ref()).map(|block| block.header.number);
+        if finished_number.map(|number| number.next_block_number()) != Some(pending_number) {
+            return log_and_err!(
+                payload = pending_number,
+                "failed to restore finished block because it is not the block before the pending block"
+            );
+        }
+        states.head = states.tail.remove(0);
+
+        Ok(())
+    }
+
     fn read_pending_execution(&self, hash: &Hash) -> anyhow::Result<Option<TransactionExecution>> {
         let states = self.lock_read();
         let Some(ref pending_block) = states.head.block else { return Ok(None) };
@@ -243,6 +281,36 @@ impl TemporaryStorage for InMemoryTemporaryStorage {
 // -----------------------------------------------------------------------------
 // Implementations without lock
 // -----------------------------------------------------------------------------
+fn do_apply_changes(state: &mut InMemoryTemporaryStorageState, tx: &TransactionExecution) {
+    let changes = tx.execution().changes.values();
+    for change in changes {
+        let account = state
+            .accounts
+            .entry(change.address)
+            .or_insert_with(|| InMemoryTemporaryAccount::new(change.address));
+
+        // account basic info
+        if let Some(nonce) = change.nonce.take_ref() {
+            account.info.nonce = *nonce;
+        }
+        if let Some(balance) = change.balance.take_ref() {
+            account.info.balance = *balance;
+        }
+
+        // bytecode (todo: where is code_hash?)
+        if let Some(Some(bytecode)) = change.bytecode.take_ref() {
+            account.info.bytecode = Some(bytecode.clone());
+        }
+
+        // slots
+        for slot in change.slots.values() {
+            if let Some(slot) = slot.take_ref() {
+                account.slots.insert(slot.index, *slot);
+            }
+        }
+    }
+}
+
 fn do_read_account(states: &NonEmpty<InMemoryTemporaryStorageState>, address: &Address) -> Option<Account> {
     // search all
     for state in states.iter() {
@@ -1,12 +1,14 @@
 //! Ethereum / EVM storage.
 
 mod external_rpc_storage;
+mod fork_storage;
 mod inmemory;
 mod permanent_storage;
 mod postgres_external_rpc;
 pub mod rocks;
 
 mod redis;
+mod state_trie;
 mod storage_point_in_time;
 mod stratus_storage;
 mod temporary_storage;
@@ -15,6 +17,9 @@ pub use external_rpc_storage::ExternalBlockWithReceipts;
 pub use external_rpc_storage::ExternalRpcStorage;
 pub use external_rpc_storage::ExternalRpcStorageConfig;
 pub use external_rpc_storage::ExternalRpcStorageKind;
+pub use fork_storage::ForkStorage;
+pub use fork_storage::ForkStorageConfig;
+pub use fork_storage::RpcForkStorage;
 pub use inmemory::InMemoryPermanentStorage;
 pub use inmemory::InMemoryPermanentStorageState;
 pub use inmemory::InMemoryTemporaryStorage;
@@ -24,8 +29,14 @@ pub use permanent_storage::PermanentStorageKind;
 pub use postgres_external_rpc::PostgresExternalRpcStorage;
 pub use postgres_external_rpc::PostgresExternalRpcStorageConfig;
 pub use rocks::rocks_permanent::RocksPermanentStorage;
+pub use state_trie::check_block_state_root;
+pub use state_trie::prove_state_trie;
+pub use state_trie::update_state_trie;
+pub use state_trie::StateTrieUpdate;
+pub use state_trie::TrieNodeSource;
 pub use storage_point_in_time::StoragePointInTime;
 pub use stratus_storage::StratusStorage;
+pub use stratus_storage::StratusStorageBuilder;
 pub use stratus_storage::StratusStorageConfig;
 pub use temporary_storage::TemporaryStorage;
 pub use temporary_storage::TemporaryStorageConfig;
@@ -6,15 +6,22 @@ use clap::Parser;
 use display_json::DebugAsJson;
 
 use crate::eth::primitives::Account;
+use crate::eth::primitives::AccountProof;
 use crate::eth::primitives::Address;
 use crate::eth::primitives::Block;
 use crate::eth::primitives::BlockFilter;
 use crate::eth::primitives::BlockNumber;
+use crate::eth::primitives::BlockSearch;
+use crate::eth::primitives::Bytes;
+use crate::eth::primitives::CodeHash;
+use crate::eth::primitives::ExecutionAccountChanges;
 use crate::eth::primitives::Hash;
 use crate::eth::primitives::LogFilter;
 use crate::eth::primitives::LogMined;
+use crate::eth::primitives::Nonce;
 use crate::eth::primitives::Slot;
 use crate::eth::primitives::SlotIndex;
+use crate::eth::primitives::StateDiff;
 use crate::eth::primitives::TransactionMined;
 use crate::eth::storage::redis::RedisPermanentStorage;
 use crate::eth::storage::InMemoryPermanentStorage;
@@ -42,14 +49,36 @@ pub trait PermanentStorage: Send + Sync + 'static {
     /// Persists atomically all changes from a block.
     fn save_block(&self, block: Block) -> anyhow::Result<()>;
 
+    /// Persists atomically the genesis block together with the genesis accounts.
+    fn save_genesis(&self, block: Block, accounts: Vec<ExecutionAccountChanges>) -> anyhow::Result<()>;
+
     /// Retrieves a block from the storage.
     fn read_block(&self, block_filter: &BlockFilter) -> anyhow::Result<Option<Block>>;
 
+    /// Retrieves the hash of a block without its transactions.
+    fn read_block_hash(&self, number: BlockNumber) -> anyhow::Result<Option<Hash>>;
+
     /// Retrieves a transaction from the storage.
     fn read_transaction(&self, hash: &Hash) -> anyhow::Result<Option<TransactionMined>>;
 
     /// Retrieves logs from the storage.
-    fn read_logs(&self, filter: &LogFilter) -> anyhow::Result<Vec<LogMined>>;
+    ///
+    /// Blocks are read whole, so implementations stop after the first block that makes the result exceed `limit` logs.
+    fn read_logs(&self, filter: &LogFilter, limit: usize) -> anyhow::Result<Vec<LogMined>>;
+
+    /// Retrieves the transactions that interacted with an address in the searched blocks, in the search order.
+    ///
+    /// Blocks are read whole, so implementations stop after the first block that makes the result reach `limit` transactions.
+    fn read_transactions_by_address(&self, address: &Address, search: BlockSearch, limit: usize) -> anyhow::Result<Vec<TransactionMined>>;
+
+    /// Retrieves the transaction sent by an address with a nonce.
+    fn read_transaction_by_sender_and_nonce(&self, sender: &Address, nonce: Nonce) -> anyhow::Result<Option<TransactionMined>>;
+
+    /// Retrieves the transaction that created a contract.
+    fn read_contract_creation(&self, address: &Address) -> anyhow::Result<Option<TransactionMined>>;
+
+    /// Retrieves the values modified by a transaction.
+    fn read_state_diff(&self, tx_hash: &Hash) -> anyhow::Result<Option<StateDiff>>;
 
     // -------------------------------------------------------------------------
     // Account and slots
@@ -58,12 +87,36 @@ pub trait PermanentStorage: Send + Sync + 'static {
     /// Persists initial accounts (test accounts or genesis accounts).
     fn save_accounts(&self, accounts: Vec<Account>) -> anyhow::Result<()>;
 
+    /// Persists changes made to accounts outside of transactions as if they were made in the last mined block.
+    fn save_account_changes(&self, changes: Vec<ExecutionAccountChanges>) -> anyhow::Result<()>;
+
+    /// Persists an account read from the fork as the current state only if the account does not exist, without history and state trie changes.
+    ///
+    /// Values read from the fork never overwrite values saved while they were being read.
+    fn save_fork_account(&self, account: Account) -> anyhow::Result<()>;
+
+    /// Persists a slot read from the fork as the current state only if the slot does not exist, without history and state trie changes.
+    fn save_fork_slot(&self, address: &Address, slot: Slot) -> anyhow::Result<()>;
+
     /// Retrieves an account from the storage. Returns Option when not found.
     fn read_account(&self, address: &Address, point_in_time: &StoragePointInTime) -> anyhow::Result<Option<Account>>;
 
     /// Retrieves an slot from the storage. Returns Option when not found.
     fn read_slot(&self, address: &Address, index: &SlotIndex, point_in_time: &StoragePointInTime) -> anyhow::Result<Option<Slot>>;
 
+    /// Retrieves a contract bytecode by its hash. Returns Option when not found.
+    fn read_bytecode(&self, code_hash: &CodeHash) -> anyhow::Result<Option<Bytes>>;
+
+    // -------------------------------------------------------------------------
+    // State trie
+    // -------------------------------------------------------------------------
+
+    /// Calculates the state root resulting from applying the changes to the last mined state without persisting it.
+    fn compute_state_root(&self, changes: &[ExecutionAccountChanges]) -> anyhow::Result<Hash>;
+
+    /// Generates the Merkle-Patricia proof of an account and some of its slots.
+    fn read_proof(&self, address: &Address, indexes: &[SlotIndex], point_in_time: &StoragePointInTime) -> anyhow::Result<AccountProof>;
+
     // -------------------------------------------------------------------------
     // Global state
     // -------------------------------------------------------------------------
@@ -71,6 +124,14 @@ pub trait PermanentStorage: Send + Sync + 'static {
     #[cfg(feature = "dev")]
     /// Resets all state to a specific block number.
     fn reset(&self) -> anyhow::Result<()>;
+
+    #[cfg(feature = "dev")]
+    /// Discards all blocks and state changes after the specified block number.
+    fn reset_at(&self, number: BlockNumber) -> anyhow::Result<()>;
+
+    #[cfg(feature = "dev")]
+    /// Checks if the storage can discard blocks with `reset_at`.
+    fn supports_reset_at(&self) -> bool;
 }
 
 // -----------------------------------------------------------------------------
@@ -5,30 +5,50 @@ use redis::Connection as RedisConnection;
 use redis::RedisResult;
 
 use crate::eth::primitives::Account;
+use crate::eth::primitives::AccountProof;
 use crate::eth::primitives::Address;
 use crate::eth::primitives::Block;
 use crate::eth::primitives::BlockFilter;
 use crate::eth::primitives::BlockNumber;
+use crate::eth::primitives::BlockSearch;
+use crate::eth::primitives::Bytes;
+use crate::eth::primitives::CodeHash;
+use crate::eth::primitives::ExecutionAccountChanges;
+use crate::eth::primitives::Gas;
 use crate::eth::primitives::Hash;
 use crate::eth::primitives::LogFilter;
 use crate::eth::primitives::LogMined;
+use crate::eth::primitives::Nonce;
 use crate::eth::primitives::Slot;
 use crate::eth::primitives::SlotIndex;
+use crate::eth::primitives::StateDiff;
 use crate::eth::primitives::TransactionMined;
+use crate::eth::storage::check_block_state_root;
+use crate::eth::storage::prove_state_trie;
+use crate::eth::storage::update_state_trie;
 use crate::eth::storage::PermanentStorage;
+use crate::eth::storage::StateTrieUpdate;
 use crate::eth::storage::StoragePointInTime;
+use crate::eth::storage::TrieNodeSource;
 use crate::ext::from_json_str;
+use crate::ext::not;
 use crate::ext::to_json_object;
 use crate::ext::to_json_string;
 use crate::ext::to_json_value;
 use crate::log_and_err;
 
+/// Number of blocks read by each command when reading logs.
+const LOGS_BLOCKS_CHUNK_SIZE: usize = 1_000;
+
 type RedisVecOptString = RedisResult<Vec<Option<String>>>;
 type RedisVecString = RedisResult<Vec<String>>;
 type RedisOptString = RedisResult<Option<String>>;
 type RedisOptUsize = RedisResult<Option<usize>>;
 type RedisVoid = RedisResult<()>;
 
+/// Values written with MSET and sorted set values written with ZADD.
+type RedisValues = (Vec<(String, String)>, Vec<(String, String, u64)>);
+
 pub struct RedisPermanentStorage {
     client: redis::Client,
 }
@@ -48,59 +68,91 @@ impl RedisPermanentStorage {
             Err(e) => log_and_err!(reason = e, "failed to get redis connection"),
         }
     }
-}
 
-impl PermanentStorage for RedisPermanentStorage {
-    fn set_mined_block_number(&self, number: BlockNumber) -> anyhow::Result<()> {
+    /// Retrieves the state root at a point-in-time.
+    fn read_state_root(&self, point_in_time: &StoragePointInTime) -> anyhow::Result<Hash> {
+        // prepare key
+        let root_key = match point_in_time {
+            StoragePointInTime::Mined | StoragePointInTime::Pending => "state_root::latest".to_owned(),
+            StoragePointInTime::MinedPast(number) => key_state_root(*number),
+        };
+
         // execute command
         let mut conn = self.conn()?;
-        let set: RedisVoid = conn.set("number::mined", number.to_string());
+        let redis_root: RedisOptString = conn.get(root_key);
 
         // parse
-        match set {
-            Ok(_) => Ok(()),
-            Err(e) => log_and_err!(reason = e, "failed to write mined number to redis"),
+        match redis_root {
+            Ok(Some(json)) => Ok(from_json_str(&json)),
+            Ok(None) => Ok(Hash::EMPTY_TRIE),
+            Err(e) => log_and_err!(reason = e, "failed to read state root from redis"),
         }
     }
 
-    fn read_mined_block_number(&self) -> anyhow::Result<BlockNumber> {
-        // execute command
-        let mut conn = self.conn()?;
-        let value: RedisOptUsize = conn.get("number::mined");
-
-        // parse
-        match value {
-            Ok(Some(value)) => Ok(value.into()),
-            Ok(None) => Ok(BlockNumber::ZERO),
-            Err(e) => log_and_err!(reason = e, "failed to read miner block number from redis"),
+    /// Fills the receipt fields of a transaction stored before they existed using the block it was mined in.
+    fn fill_receipt(&self, tx: TransactionMined) -> anyhow::Result<TransactionMined> {
+        if tx.cumulative_gas_used != Gas::ZERO {
+            return Ok(tx);
         }
+        let Some(block) = self.read_block(&BlockFilter::Number(tx.block_number))? else {
+            return Ok(tx);
+        };
+        let block_tx = block.transactions.into_iter().find(|block_tx| block_tx.input.hash == tx.input.hash);
+        Ok(block_tx.unwrap_or(tx))
     }
 
-    fn save_block(&self, block: Block) -> anyhow::Result<()> {
-        // generate block keys
-        let key_block_number = key_block_by_number(block.number());
-        let key_block_hash = key_block_by_hash(&block.hash());
+    /// Reads the transactions referenced by index values, which are transaction hashes.
+    ///
+    /// Indexes written before they referenced transactions by hash have the whole transaction as value.
+    fn read_indexed_transactions(&self, values: Vec<String>) -> anyhow::Result<Vec<TransactionMined>> {
+        let (stored_txs, hashes): (Vec<String>, Vec<String>) = values.into_iter().partition(|value| value.starts_with('{'));
+        let mut txs: Vec<TransactionMined> = stored_txs.iter().map(|json| from_json_str(json)).collect_vec();
+
+        if not(hashes.is_empty()) {
+            // prepare keys
+            let tx_keys = hashes.iter().map(|json| key_tx(&from_json_str(json))).collect_vec();
+
+            // execute command
+            let mut conn = self.conn()?;
+            let redis_txs: RedisVecOptString = redis::cmd("MGET").arg(tx_keys).query(&mut conn);
+
+            // parse
+            match redis_txs {
+                Ok(vec_json) => txs.extend(vec_json.into_iter().flatten().map(|json| from_json_str::<TransactionMined>(&json))),
+                Err(e) => return log_and_err!(reason = e, "failed to read indexed transactions from redis"),
+            }
+        }
 
-        // generate values
-        let block_json = to_json_string(&block);
+        txs.into_iter().map(|tx| self.fill_receipt(tx)).collect()
+    }
 
-        // blocks
-        let mut mset_values = vec![
-            (key_block_number, block_json.clone()),
-            (key_block_hash, block_json.clone()),
-            ("block::latest".to_owned(), block_json),
-        ];
-        let mut zadd_values = vec![];
+    /// Applies changes to the state trie of the latest mined block.
+    ///
+    /// Returns the state root before the changes and the update that must be persisted.
+    fn update_state_trie(&self, changes: &[ExecutionAccountChanges]) -> anyhow::Result<(Hash, StateTrieUpdate)> {
+        let previous_root = self.read_state_root(&StoragePointInTime::Mined)?;
+        let update = update_state_trie(self, previous_root, changes)?;
+        Ok((previous_root, update))
+    }
 
-        // transactions
-        for tx in &block.transactions {
-            let tx_key = key_tx(&tx.input.hash);
-            let tx_value = to_json_string(&tx);
-            mset_values.push((tx_key, tx_value));
+    /// Generates the values that must be written to persist the new state trie nodes and state root.
+    fn state_trie_values(update: StateTrieUpdate, number: BlockNumber) -> Vec<(String, String)> {
+        let root_json = to_json_string(&update.root);
+        let mut mset_values = vec![(key_state_root(number), root_json.clone()), ("state_root::latest".to_owned(), root_json)];
+        for (hash, node) in update.nodes {
+            mset_values.push((key_trie_node(&hash), const_hex::encode(node)));
         }
+        mset_values
+    }
+
+    /// Generates the values that must be written to persist account changes made at a block, including the state trie changes.
+    fn account_changes_values(&self, update: StateTrieUpdate, account_changes: Vec<ExecutionAccountChanges>, number: BlockNumber) -> RedisValues {
+        // state trie
+        let mut mset_values = Self::state_trie_values(update, number);
+        let mut zadd_values = vec![];
 
         // changes
-        for changes in block.compact_account_changes() {
+        for changes in account_changes {
             // account
             if changes.is_account_modified() {
                 let mut account = Account {
@@ -114,16 +166,19 @@ impl PermanentStorage for RedisPermanentStorage {
                     account.balance = balance;
                 }
                 if let Some(bytecode) = changes.bytecode.take() {
+                    if let Some(ref bytecode) = bytecode {
+                        mset_values.push((key_bytecode(&CodeHash::from_bytecode(Some(bytecode.clone()))), to_json_string(bytecode)));
+                    }
                     account.bytecode = bytecode;
                 }
 
                 // add block number to force slot modification
                 let mut account_value = to_json_object(&account);
-                account_value.insert("block".to_owned(), to_json_value(block.number()));
+                account_value.insert("block".to_owned(), to_json_value(number));
                 let account_value = to_json_string(&account_value);
 
                 mset_values.push((key_account(&account.address), account_value.clone()));
-                zadd_values.push((key_account_history(&account.address), account_value, block.number().as_u64()));
+                zadd_values.push((key_account_history(&account.address), account_value, number.as_u64()));
             }
 
             // slots
@@ -131,40 +186,146 @@ impl PermanentStorage for RedisPermanentStorage {
                 if let Some(slot) = slot.take() {
                     // add block number to force slot modification
                     let mut slot_value = to_json_value(slot);
-                    slot_value.as_object_mut().unwrap().insert("block".to_owned(), to_json_value(block.number()));
+                    slot_value.as_object_mut().unwrap().insert("block".to_owned(), to_json_value(number));
                     let slot_value = to_json_string(&slot_value);
 
                     mset_values.push((key_slot(&changes.address, &slot.index), slot_value.clone()));
-                    zadd_values.push((key_slot_history(&changes.address, &slot.index), slot_value, block.number().as_u64()));
+                    zadd_values.push((key_slot_history(&changes.address, &slot.index), slot_value, number.as_u64()));
                 }
             }
         }
 
-        // execute mset command
-        let mut conn = self.conn()?;
-        let set: RedisVoid = conn.mset(&mset_values);
-        if let Err(e) = set {
-            return log_and_err!(reason = e, "failed to write block mset to redis");
+        (mset_values, zadd_values)
+    }
+
+    /// Persists a block, applying the specified account changes at the block instead of the changes of its transactions.
+    fn save_block_with_account_changes(&self, block: Block, account_changes: Vec<ExecutionAccountChanges>) -> anyhow::Result<()> {
+        // check state root before writing anything
+        let (previous_root, update) = self.update_state_trie(&account_changes)?;
+        check_block_state_root(&block, previous_root, update.root, || {
+            let Some(parent_number) = block.number().prev() else { return Ok(None) };
+            Ok(self.read_block(&BlockFilter::Number(parent_number))?.map(|parent| parent.header.state_root))
+        })?;
+
+        // generate block keys
+        let key_block_number = key_block_by_number(block.number());
+        let key_block_hash = key_block_by_hash(&block.hash());
+
+        // generate values
+        let block_json = to_json_string(&block);
+
+        // blocks
+        let mut mset_values = vec![
+            (key_block_number, block_json.clone()),
+            (key_block_hash, block_json.clone()),
+            ("block::latest".to_owned(), block_json),
+            (key_block_hash_by_number(block.number()), to_json_string(&block.hash())),
+        ];
+        let mut zadd_values = vec![];
+
+        // transactions
+        for tx in &block.transactions {
+            let tx_key = key_tx(&tx.input.hash);
+            let tx_value = to_json_string(&tx);
+            mset_values.push((tx_key, tx_value));
+
+            // indexes reference the transaction by hash
+            let hash_value = to_json_string(&tx.input.hash);
+            mset_values.push((key_tx_by_sender_nonce(&tx.input.from, tx.input.nonce), hash_value.clone()));
+            for contract in tx.created_contracts() {
+                mset_values.push((key_contract_creation(&contract), hash_value.clone()));
+            }
+            for address in tx.involved_addresses() {
+                zadd_values.push((key_address_txs(&address), hash_value.clone(), block.number().as_u64()));
+            }
         }
 
-        // execute zadd commands
+        // state and changes
+        let (account_mset_values, account_zadd_values) = self.account_changes_values(update, account_changes, block.number());
+        mset_values.extend(account_mset_values);
+        zadd_values.extend(account_zadd_values);
+
+        self.write_values(mset_values, zadd_values)
+    }
+
+    /// Writes atomically values with MSET and sorted set values with ZADD NX in a single transaction.
+    fn write_values(&self, mset_values: Vec<(String, String)>, zadd_values: Vec<(String, String, u64)>) -> anyhow::Result<()> {
+        // prepare commands
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        if not(mset_values.is_empty()) {
+            pipe.mset(&mset_values).ignore();
+        }
         for (key, value, score) in zadd_values {
-            let mut cmd = redis::cmd("ZADD");
-            cmd.arg(key).arg("NX").arg(score).arg(value);
+            pipe.cmd("ZADD").arg(key).arg("NX").arg(score).arg(value).ignore();
+        }
 
-            let zadd: RedisVoid = cmd.exec(&mut conn);
-            if let Err(e) = zadd {
-                return log_and_err!(reason = e, "failed to write block zadd to redis");
-            }
+        // execute commands
+        let mut conn = self.conn()?;
+        let write: RedisVoid = pipe.query(&mut conn);
+
+        // parse
+        match write {
+            Ok(_) => Ok(()),
+            Err(e) => log_and_err!(reason = e, "failed to write values to redis"),
+        }
+    }
+}
+
+impl TrieNodeSource for RedisPermanentStorage {
+    fn read_trie_node(&self, hash: &Hash) -> anyhow::Result<Option<Vec<u8>>> {
+        // execute command
+        let mut conn = self.conn()?;
+        let redis_node: RedisOptString = conn.get(key_trie_node(hash));
+
+        // parse
+        match redis_node {
+            Ok(Some(node)) => Ok(Some(const_hex::decode(node)?)),
+            Ok(None) => Ok(None),
+            Err(e) => log_and_err!(reason = e, "failed to read trie node from redis"),
+        }
+    }
+}
+
+impl PermanentStorage for RedisPermanentStorage {
+    fn set_mined_block_number(&self, number: BlockNumber) -> anyhow::Result<()> {
+        // execute command
+        let mut conn = self.conn()?;
+        let set: RedisVoid = conn.set("number::mined", number.to_string());
+
+        // parse
+        match set {
+            Ok(_) => Ok(()),
+            Err(e) => log_and_err!(reason = e, "failed to write mined number to redis"),
         }
+    }
+
+    fn read_mined_block_number(&self) -> anyhow::Result<BlockNumber> {
+        // execute command
+        let mut conn = self.conn()?;
+        let value: RedisOptUsize = conn.get("number::mined");
 
-        Ok(())
+        // parse
+        match value {
+            Ok(Some(value)) => Ok(value.into()),
+            Ok(None) => Ok(BlockNumber::ZERO),
+            Err(e) => log_and_err!(reason = e, "failed to read miner block number from redis"),
+        }
+    }
+
+    fn save_block(&self, block: Block) -> anyhow::Result<()> {
+        let account_changes = block.compact_account_changes();
+        self.save_block_with_account_changes(block, account_changes)
+    }
+
+    fn save_genesis(&self, block: Block, accounts: Vec<ExecutionAccountChanges>) -> anyhow::Result<()> {
+        self.save_block_with_account_changes(block, accounts)
     }
 
     fn read_block(&self, block_filter: &BlockFilter) -> anyhow::Result<Option<Block>> {
         // prepare keys
         let block_key = match block_filter {
-            BlockFilter::Latest | BlockFilter::Pending => "block::latest".to_owned(),
+            BlockFilter::Latest | BlockFilter::Pending | BlockFilter::Safe | BlockFilter::Finalized => "block::latest".to_owned(),
             BlockFilter::Earliest => "block::earliest".to_owned(),
             BlockFilter::Hash(hash) => key_block_by_hash(hash),
             BlockFilter::Number(number) => key_block_by_number(*number),
@@ -176,12 +337,41 @@ impl PermanentStorage for RedisPermanentStorage {
 
         // parse
         match redis_block {
-            Ok(Some(json)) => Ok(from_json_str(&json)),
+            Ok(Some(json)) => Ok(Some(parse_block(&json))),
             Ok(None) => Ok(None),
             Err(e) => log_and_err!(reason = e, "failed to read block from redis"),
         }
     }
 
+    fn read_bytecode(&self, code_hash: &CodeHash) -> anyhow::Result<Option<Bytes>> {
+        // prepare keys
+        let bytecode_key = key_bytecode(code_hash);
+
+        // execute command
+        let mut conn = self.conn()?;
+        let redis_bytecode: RedisOptString = conn.get(bytecode_key);
+
+        // parse
+        match redis_bytecode {
+            Ok(Some(json)) => Ok(from_json_str(&json)),
+            Ok(None) => Ok(None),
+            Err(e) => log_and_err!(reason = e, "failed to read bytecode from redis"),
+        }
+    }
+
+    fn read_block_hash(&self, number: BlockNumber) -> anyhow::Result<Option<Hash>> {
+        // execute command
+        let mut conn = self.conn()?;
+        let redis_hash: RedisOptString = conn.get(key_block_hash_by_number(number));
+
+        // parse (blocks saved before block hashes were indexed are read whole)
+        match redis_hash {
+            Ok(Some(json)) => Ok(Some(from_json_str(&json))),
+            Ok(None) => Ok(self.read_block(&BlockFilter::Number(number))?.map(|block| block.hash())),
+            Err(e) => log_and_err!(reason = e, "failed to read block hash from redis"),
+        }
+    }
+
     fn read_transaction(&self, hash: &Hash) -> anyhow::Result<Option<TransactionMined>> {
         // prepare keys
         let tx_key = key_tx(hash);
@@ -192,45 +382,138 @@ impl PermanentStorage for RedisPermanentStorage {
 
         // parse
         match redis_transaction {
-            Ok(Some(json)) => Ok(from_json_str(&json)),
+            Ok(Some(json)) => Ok(Some(self.fill_receipt(from_json_str(&json))?)),
             Ok(None) => Ok(None),
             Err(e) => log_and_err!(reason = e, "failed to read transaction from redis"),
         }
     }
 
-    fn read_logs(&self, filter: &LogFilter) -> anyhow::Result<Vec<LogMined>> {
+    fn read_logs(&self, filter: &LogFilter, limit: usize) -> anyhow::Result<Vec<LogMined>> {
         // prepare keys
         let from_block = filter.from_block.as_u64();
         let to_block = match filter.to_block {
             Some(number) => number.as_u64(),
             None => self.read_mined_block_number()?.as_u64(),
         };
-        let block_keys = (from_block..=to_block).map(key_block_by_number).collect_vec();
+        let block_keys = (from_block..=to_block).map(key_block_by_number);
+
+        // execute command in chunks of blocks until the limit is exceeded
+        let mut conn = self.conn()?;
+        let mut logs = vec![];
+        for chunk_keys in &block_keys.chunks(LOGS_BLOCKS_CHUNK_SIZE) {
+            let blocks: RedisVecOptString = conn.mget(chunk_keys.collect_vec());
+
+            // parse
+            let blocks: Vec<Block> = match blocks {
+                Ok(vec_json) => vec_json.into_iter().flatten().map(|json| parse_block(&json)).collect_vec(),
+                Err(e) => return log_and_err!(reason = e, "failed to read logs from redis"),
+            };
+
+            // filter
+            for block in blocks {
+                if filter.matches_bloom(&block.header.bloom) {
+                    logs.extend(block.transactions.into_iter().flat_map(|t| t.logs).filter(|log| filter.matches(log)));
+                }
+                if logs.len() > limit {
+                    return Ok(logs);
+                }
+            }
+        }
+
+        Ok(logs)
+    }
 
-        // exit if no keys
-        if block_keys.is_empty() {
+    fn read_transactions_by_address(&self, address: &Address, search: BlockSearch, limit: usize) -> anyhow::Result<Vec<TransactionMined>> {
+        if limit == 0 {
             return Ok(vec![]);
         }
 
+        // prepare key
+        let address_txs_key = key_address_txs(address);
+
+        // execute command for the first transactions in the search order until the limit
+        let mut conn = self.conn()?;
+        let mut cmd = redis::cmd("ZRANGE");
+        match search {
+            BlockSearch::Before(number) => cmd
+                .arg(&address_txs_key)
+                .arg(format!("({}", number.as_u64()))
+                .arg("-inf")
+                .arg("BYSCORE")
+                .arg("REV"),
+            BlockSearch::After(number) => cmd.arg(&address_txs_key).arg(format!("({}", number.as_u64())).arg("+inf").arg("BYSCORE"),
+        };
+        cmd.arg("LIMIT").arg(0).arg(limit).arg("WITHSCORES");
+        let redis_values: RedisResult<Vec<(String, u64)>> = cmd.query(&mut conn);
+        let mut values = match redis_values {
+            Ok(values) => values,
+            Err(e) => return log_and_err!(reason = e, "failed to read transactions by address from redis"),
+        };
+
+        // blocks are read whole, so the last block may have more transactions than the ones that fit the limit
+        if values.len() == limit {
+            if let Some(&(_, last_block)) = values.last() {
+                let redis_last_block_values: RedisVecString = redis::cmd("ZRANGE")
+                    .arg(&address_txs_key)
+                    .arg(last_block)
+                    .arg(last_block)
+                    .arg("BYSCORE")
+                    .query(&mut conn);
+                match redis_last_block_values {
+                    Ok(last_block_values) => {
+                        values.retain(|(_, block)| *block != last_block);
+                        values.extend(last_block_values.into_iter().map(|value| (value, last_block)));
+                    }
+                    Err(e) => return log_and_err!(reason = e, "failed to read transactions by address from redis"),
+                }
+            }
+        }
+
+        // parse and sort in the search order
+        let mut txs = self.read_indexed_transactions(values.into_iter().map(|(value, _)| value).collect())?;
+        txs.sort_by_key(|tx| (tx.block_number, tx.transaction_index));
+        if let BlockSearch::Before(_) = search {
+            txs.reverse();
+        }
+        Ok(txs)
+    }
+
+    fn read_transaction_by_sender_and_nonce(&self, sender: &Address, nonce: Nonce) -> anyhow::Result<Option<TransactionMined>> {
+        // prepare keys
+        let tx_key = key_tx_by_sender_nonce(sender, nonce);
+
         // execute command
         let mut conn = self.conn()?;
-        let blocks: RedisVecOptString = conn.mget(block_keys);
+        let redis_transaction: RedisOptString = conn.get(tx_key);
 
         // parse
-        let blocks: Vec<Block> = match blocks {
-            Ok(vec_json) => vec_json.into_iter().flatten().map(|json| from_json_str(&json)).collect_vec(),
-            Err(e) => return log_and_err!(reason = e, "failed to read logs from redis"),
-        };
+        match redis_transaction {
+            Ok(Some(value)) => Ok(self.read_indexed_transactions(vec![value])?.pop()),
+            Ok(None) => Ok(None),
+            Err(e) => log_and_err!(reason = e, "failed to read transaction by sender and nonce from redis"),
+        }
+    }
 
-        // filter
-        let logs = blocks
-            .into_iter()
-            .flat_map(|b| b.transactions)
-            .flat_map(|t| t.logs)
-            .filter(|log| filter.matches(log))
-            .collect_vec();
+    fn read_contract_creation(&self, address: &Address) -> anyhow::Result<Option<TransactionMined>> {
+        // prepare keys
+        let tx_key = key_contract_creation(address);
 
-        Ok(logs)
+        // execute command
+        let mut conn = self.conn()?;
+        let redis_transaction: RedisOptString = conn.get(tx_key);
+
+        // parse
+        match redis_transaction {
+            Ok(Some(value)) => Ok(self.read_indexed_transactions(vec![value])?.pop()),
+            Ok(None) => Ok(None),
+            Err(e) => log_and_err!(reason = e, "failed to read contract creation from redis"),
+        }
+    }
+
+    fn read_state_diff(&self, tx_hash: &Hash) -> anyhow::Result<Option<StateDiff>> {
+        // transactions are stored with their execution changes
+        let tx = self.read_transaction(tx_hash)?;
+        Ok(tx.map(|tx| StateDiff::from_changes(&tx.execution.changes)))
     }
 
     fn save_accounts(&self, accounts: Vec<Account>) -> anyhow::Result<()> {
@@ -240,7 +523,11 @@ impl PermanentStorage for RedisPermanentStorage {
         }
 
         // prepare values
-        let redis_accounts = accounts
+        let changes = accounts
+            .iter()
+            .map(|acc| ExecutionAccountChanges::from_modified_values(acc.clone(), vec![]))
+            .collect_vec();
+        let mut redis_accounts = accounts
             .into_iter()
             .map(|acc| {
                 let account_key = key_account(&acc.address);
@@ -248,6 +535,8 @@ impl PermanentStorage for RedisPermanentStorage {
                 (account_key, account_value)
             })
             .collect_vec();
+        let (_, update) = self.update_state_trie(&changes)?;
+        redis_accounts.extend(Self::state_trie_values(update, self.read_mined_block_number()?));
 
         // execute command
         let mut conn = self.conn()?;
@@ -260,6 +549,43 @@ impl PermanentStorage for RedisPermanentStorage {
         }
     }
 
+    fn save_account_changes(&self, changes: Vec<ExecutionAccountChanges>) -> anyhow::Result<()> {
+        let (_, update) = self.update_state_trie(&changes)?;
+        let (mset_values, zadd_values) = self.account_changes_values(update, changes, self.read_mined_block_number()?);
+        self.write_values(mset_values, zadd_values)
+    }
+
+    fn save_fork_account(&self, account: Account) -> anyhow::Result<()> {
+        // prepare command
+        let mut pipe = redis::pipe();
+        if let Some(ref bytecode) = account.bytecode {
+            pipe.set(key_bytecode(&account.code_hash), to_json_string(bytecode)).ignore();
+        }
+        pipe.set_nx(key_account(&account.address), to_json_string(&account)).ignore();
+
+        // execute command
+        let mut conn = self.conn()?;
+        let set: RedisVoid = pipe.query(&mut conn);
+
+        // parse
+        match set {
+            Ok(_) => Ok(()),
+            Err(e) => log_and_err!(reason = e, "failed to write fork account to redis"),
+        }
+    }
+
+    fn save_fork_slot(&self, address: &Address, slot: Slot) -> anyhow::Result<()> {
+        // execute command
+        let mut conn = self.conn()?;
+        let set: RedisVoid = conn.set_nx(key_slot(address, &slot.index), to_json_string(&slot));
+
+        // parse
+        match set {
+            Ok(_) => Ok(()),
+            Err(e) => log_and_err!(reason = e, "failed to write fork slot to redis"),
+        }
+    }
+
     fn read_account(&self, address: &Address, point_in_time: &crate::eth::storage::StoragePointInTime) -> anyhow::Result<Option<Account>> {
         let mut conn = self.conn()?;
         match point_in_time {
@@ -351,6 +677,15 @@ impl PermanentStorage for RedisPermanentStorage {
         }
     }
 
+    fn compute_state_root(&self, changes: &[ExecutionAccountChanges]) -> anyhow::Result<Hash> {
+        let update = update_state_trie(self, self.read_state_root(&StoragePointInTime::Mined)?, changes)?;
+        Ok(update.root)
+    }
+
+    fn read_proof(&self, address: &Address, indexes: &[SlotIndex], point_in_time: &StoragePointInTime) -> anyhow::Result<AccountProof> {
+        prove_state_trie(self, self.read_state_root(point_in_time)?, *address, indexes)
+    }
+
     #[cfg(feature = "dev")]
     fn reset(&self) -> anyhow::Result<()> {
         let mut conn = self.conn()?;
@@ -360,6 +695,29 @@ impl PermanentStorage for RedisPermanentStorage {
             Err(e) => log_and_err!(reason = e, "failed to clear all redis keys"),
         }
     }
+
+    #[cfg(feature = "dev")]
+    fn reset_at(&self, _number: BlockNumber) -> anyhow::Result<()> {
+        log_and_err!("resetting to a previous block is not supported by redis permanent storage")
+    }
+
+    #[cfg(feature = "dev")]
+    fn supports_reset_at(&self) -> bool {
+        false
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Parse helpers
+// -----------------------------------------------------------------------------
+
+/// Parses a block, filling the receipt fields of blocks stored before they existed.
+fn parse_block(json: &str) -> Block {
+    let mut block: Block = from_json_str(json);
+    if block.is_missing_receipts() {
+        block.fill_receipts();
+    }
+    block
 }
 
 // -----------------------------------------------------------------------------
@@ -376,6 +734,11 @@ fn key_block_by_hash(hash: &Hash) -> String {
     format!("block::hash::{}", hash)
 }
 
+/// Generates a key for accessing the hash of a block by its number.
+fn key_block_hash_by_number(number: impl Into<u64>) -> String {
+    format!("block::hash_by_number::{}", number.into())
+}
+
 /// Generates a key for accessing an account.
 fn key_account(address: &Address) -> String {
     format!("account::{}", address)
@@ -400,3 +763,33 @@ fn key_slot_history(address: &Address, index: &SlotIndex) -> String {
 fn key_tx(hash: &Hash) -> String {
     format!("tx::{}", hash)
 }
+
+/// Generates a key for accessing a transaction by its sender and nonce.
+fn key_tx_by_sender_nonce(sender: &Address, nonce: Nonce) -> String {
+    format!("tx::sender::{}::{}", sender, nonce)
+}
+
+/// Generates a key for accessing the transactions that interacted with an address.
+fn key_address_txs(address: &Address) -> String {
+    format!("address_txs::{}", address)
+}
+
+/// Generates a key for accessing the transaction that created a contract.
+fn key_contract_creation(address: &Address) -> String {
+    format!("contract_creation::{}", address)
+}
+
+/// Generates a key for accessing the state root of a block.
+fn key_state_root(number: impl Into<u64>) -> String {
+    format!("state_root::number::{}", number.into())
+}
+
+/// Generates a key for accessing a state trie node.
+fn key_trie_node(hash: &Hash) -> String {
+    format!("trie_node::{}", hash)
+}
+
+/// Generates a key for accessing a contract bytecode by its hash.
+fn key_bytecode(code_hash: &CodeHash) -> String {
+    format!("bytecode::{}", const_hex::encode_prefixed(code_hash))
+}
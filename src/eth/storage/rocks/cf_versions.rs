@@ -14,13 +14,23 @@ use strum::IntoStaticStr;
 use strum::VariantNames;
 
 use super::types::AccountRocksdb;
+use super::types::AccountRocksdbV1;
 use super::types::BlockNumberRocksdb;
 use super::types::BlockRocksdb;
+use super::types::BlockRocksdbV1;
+use super::types::BlockRocksdbV2;
+use super::types::BlockRocksdbV3;
+use super::types::BytesRocksdb;
+use super::types::HashRocksdb;
 use super::types::SlotValueRocksdb;
+use super::types::StateDiffRocksdb;
 use crate::eth::primitives::Account;
 use crate::eth::primitives::Block;
 use crate::eth::primitives::BlockNumber;
+use crate::eth::primitives::Bytes;
+use crate::eth::primitives::Hash;
 use crate::eth::primitives::SlotValue;
+use crate::eth::primitives::StateDiff;
 
 macro_rules! impl_single_version_cf_value {
     ($name:ident, $inner_type:ty, $non_rocks_equivalent: ty) => {
@@ -75,14 +85,101 @@ macro_rules! impl_single_version_cf_value {
     };
 }
 
-impl_single_version_cf_value!(CfAccountsValue, AccountRocksdb, Account);
-impl_single_version_cf_value!(CfAccountsHistoryValue, AccountRocksdb, Account);
 impl_single_version_cf_value!(CfAccountSlotsValue, SlotValueRocksdb, SlotValue);
 impl_single_version_cf_value!(CfAccountSlotsHistoryValue, SlotValueRocksdb, SlotValue);
 impl_single_version_cf_value!(CfTransactionsValue, BlockNumberRocksdb, BlockNumber);
-impl_single_version_cf_value!(CfBlocksByNumberValue, BlockRocksdb, Block);
 impl_single_version_cf_value!(CfBlocksByHashValue, BlockNumberRocksdb, BlockNumber);
+impl_single_version_cf_value!(CfBlockHashesByNumberValue, HashRocksdb, Hash);
 impl_single_version_cf_value!(CfLogsValue, BlockNumberRocksdb, BlockNumber);
+impl_single_version_cf_value!(CfLogsByAddressValue, HashRocksdb, Hash);
+impl_single_version_cf_value!(CfLogsByTopic0Value, HashRocksdb, Hash);
+impl_single_version_cf_value!(CfTransactionsByAddressValue, HashRocksdb, Hash);
+impl_single_version_cf_value!(CfTransactionsBySenderNonceValue, HashRocksdb, Hash);
+impl_single_version_cf_value!(CfContractCreationsValue, HashRocksdb, Hash);
+impl_single_version_cf_value!(CfStateDiffsValue, StateDiffRocksdb, StateDiff);
+impl_single_version_cf_value!(CfStateTrieNodesValue, BytesRocksdb, Bytes);
+impl_single_version_cf_value!(CfStateRootsValue, HashRocksdb, Hash);
+impl_single_version_cf_value!(CfBytecodesValue, BytesRocksdb, Bytes);
+impl_single_version_cf_value!(CfIndexesBackfillValue, BlockNumberRocksdb, BlockNumber);
+
+/// Implements a CF value for accounts, which can be stored with the bytecode inline (V1) or referenced by hash (V2).
+macro_rules! impl_account_cf_value {
+    ($name:ident) => {
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, EnumCount, VariantNames, IntoStaticStr)]
+        pub enum $name {
+            V1(AccountRocksdbV1),
+            V2(AccountRocksdb),
+        }
+
+        impl $name {
+            /// Converts the stored account to the latest version.
+            ///
+            /// V1 accounts have their bytecode inline, so it is also returned to be moved to the `bytecodes` column family.
+            pub fn into_latest(self) -> (AccountRocksdb, Option<BytesRocksdb>) {
+                match self {
+                    Self::V1(v1) => {
+                        let bytecode = v1.bytecode.clone();
+                        (v1.into(), bytecode)
+                    }
+                    Self::V2(v2) => (v2, None),
+                }
+            }
+        }
+
+        impl From<AccountRocksdb> for $name {
+            fn from(value: AccountRocksdb) -> Self {
+                Self::V2(value)
+            }
+        }
+
+        impl From<Account> for $name {
+            fn from(value: Account) -> Self {
+                Self::V2(value.into())
+            }
+        }
+    };
+}
+
+// V2 replaced the inline bytecode by its hash.
+impl_account_cf_value!(CfAccountsValue);
+impl_account_cf_value!(CfAccountsHistoryValue);
+
+/// Blocks stored by number.
+///
+/// V2 added fee market fields to block headers and transactions.
+/// V3 added cumulative gas used, effective gas price and logs bloom to transactions.
+/// V4 added internal transactions to transactions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, EnumCount, VariantNames, IntoStaticStr)]
+pub enum CfBlocksByNumberValue {
+    V1(BlockRocksdbV1),
+    V2(BlockRocksdbV2),
+    V3(BlockRocksdbV3),
+    V4(BlockRocksdb),
+}
+
+impl CfBlocksByNumberValue {
+    /// Converts the stored block to the latest version.
+    pub fn into_inner(self) -> BlockRocksdb {
+        match self {
+            Self::V1(v1) => BlockRocksdbV2::from(v1).into(),
+            Self::V2(v2) => v2.into(),
+            Self::V3(v3) => v3.into(),
+            Self::V4(v4) => v4,
+        }
+    }
+}
+
+impl From<BlockRocksdb> for CfBlocksByNumberValue {
+    fn from(value: BlockRocksdb) -> Self {
+        Self::V4(value)
+    }
+}
+
+impl From<Block> for CfBlocksByNumberValue {
+    fn from(value: Block) -> Self {
+        Self::V4(value.into())
+    }
+}
 
 #[cfg_attr(not(test), allow(dead_code))]
 trait ToCfName {
@@ -104,7 +201,18 @@ impl_to_cf_name!(CfAccountSlotsHistoryValue, "account_slots_history");
 impl_to_cf_name!(CfTransactionsValue, "transactions");
 impl_to_cf_name!(CfBlocksByNumberValue, "blocks_by_number");
 impl_to_cf_name!(CfBlocksByHashValue, "blocks_by_hash");
+impl_to_cf_name!(CfBlockHashesByNumberValue, "block_hashes_by_number");
 impl_to_cf_name!(CfLogsValue, "logs");
+impl_to_cf_name!(CfLogsByAddressValue, "logs_by_address");
+impl_to_cf_name!(CfLogsByTopic0Value, "logs_by_topic0");
+impl_to_cf_name!(CfTransactionsByAddressValue, "transactions_by_address");
+impl_to_cf_name!(CfTransactionsBySenderNonceValue, "transactions_by_sender_nonce");
+impl_to_cf_name!(CfContractCreationsValue, "contract_creations");
+impl_to_cf_name!(CfStateDiffsValue, "state_diffs");
+impl_to_cf_name!(CfStateTrieNodesValue, "state_trie_nodes");
+impl_to_cf_name!(CfStateRootsValue, "state_roots");
+impl_to_cf_name!(CfBytecodesValue, "bytecodes");
+impl_to_cf_name!(CfIndexesBackfillValue, "indexes_backfill");
 
 /// Test that deserialization works for each variant of the enum.
 ///
@@ -216,10 +324,10 @@ mod tests {
 
     /// Store snapshots of the current serialization format for each version.
     #[test]
-    fn test_snapshot_bincode_deserialization_for_single_version_enums() {
+    fn test_snapshot_bincode_deserialization_for_cf_values() {
         fn test_deserialization<CfValue, Inner, F>(inner_to_cf_value: F) -> Result<TestRunConfirmation<CfValue>>
         where
-            CfValue: From<Inner> + for<'de> Deserialize<'de> + Serialize + Clone + Debug + PartialEq + Into<&'static str> + ToCfName,
+            CfValue: for<'de> Deserialize<'de> + Serialize + Clone + Debug + PartialEq + Into<&'static str> + VariantNames + ToCfName,
             F: FnOnce(Inner) -> CfValue,
             Inner: Dummy<Faker>,
         {
@@ -249,16 +357,15 @@ mod tests {
 
             let snapshots = get_all_bincode_snapshots_from_folder(&snapshot_parent_path)?;
 
-            let [snapshot_path] = snapshots.as_slice() else {
-                bail!("expected 1 snapshot, found {}: {snapshots:?}", snapshots.len());
-            };
-
             ensure!(
-                snapshot_path == snapshot_path,
-                "snapshot path {snapshot_path:?} doesn't match the expected for v1: {snapshot_path:?}"
+                snapshots.len() == CfValue::VARIANTS.len(),
+                "expected {} snapshots, found {}: {snapshots:?}",
+                CfValue::VARIANTS.len(),
+                snapshots.len()
             );
+            ensure!(snapshots.contains(&snapshot_path), "snapshot path {snapshot_path:?} not found in {snapshots:?}");
 
-            let deserialized = bincode::deserialize::<CfValue>(&fs::read(snapshot_path)?)?;
+            let deserialized = bincode::deserialize::<CfValue>(&fs::read(&snapshot_path)?)?;
             ensure!(
                 expected == deserialized,
                 "deserialized value doesn't match expected\n deserialized = {deserialized:?}\n expected = {expected:?}",
@@ -274,15 +381,42 @@ mod tests {
         let mut transactions_checker = EnumCoverageDropBombChecker::<CfTransactionsValue>::new();
         let mut blocks_by_number_checker = EnumCoverageDropBombChecker::<CfBlocksByNumberValue>::new();
         let mut blocks_by_hash_checker = EnumCoverageDropBombChecker::<CfBlocksByHashValue>::new();
+        let mut block_hashes_by_number_checker = EnumCoverageDropBombChecker::<CfBlockHashesByNumberValue>::new();
         let mut logs_checker = EnumCoverageDropBombChecker::<CfLogsValue>::new();
-
-        accounts_checker.add(test_deserialization::<_, AccountRocksdb, _>(CfAccountsValue::V1).unwrap());
-        accounts_history_checker.add(test_deserialization::<_, AccountRocksdb, _>(CfAccountsHistoryValue::V1).unwrap());
+        let mut logs_by_address_checker = EnumCoverageDropBombChecker::<CfLogsByAddressValue>::new();
+        let mut logs_by_topic0_checker = EnumCoverageDropBombChecker::<CfLogsByTopic0Value>::new();
+        let mut transactions_by_address_checker = EnumCoverageDropBombChecker::<CfTransactionsByAddressValue>::new();
+        let mut transactions_by_sender_nonce_checker = EnumCoverageDropBombChecker::<CfTransactionsBySenderNonceValue>::new();
+        let mut contract_creations_checker = EnumCoverageDropBombChecker::<CfContractCreationsValue>::new();
+        let mut state_diffs_checker = EnumCoverageDropBombChecker::<CfStateDiffsValue>::new();
+        let mut state_trie_nodes_checker = EnumCoverageDropBombChecker::<CfStateTrieNodesValue>::new();
+        let mut state_roots_checker = EnumCoverageDropBombChecker::<CfStateRootsValue>::new();
+        let mut bytecodes_checker = EnumCoverageDropBombChecker::<CfBytecodesValue>::new();
+        let mut indexes_backfill_checker = EnumCoverageDropBombChecker::<CfIndexesBackfillValue>::new();
+
+        accounts_checker.add(test_deserialization::<_, AccountRocksdbV1, _>(CfAccountsValue::V1).unwrap());
+        accounts_checker.add(test_deserialization::<_, AccountRocksdb, _>(CfAccountsValue::V2).unwrap());
+        accounts_history_checker.add(test_deserialization::<_, AccountRocksdbV1, _>(CfAccountsHistoryValue::V1).unwrap());
+        accounts_history_checker.add(test_deserialization::<_, AccountRocksdb, _>(CfAccountsHistoryValue::V2).unwrap());
         account_slots_checker.add(test_deserialization::<_, SlotValueRocksdb, _>(CfAccountSlotsValue::V1).unwrap());
         account_slots_history_checker.add(test_deserialization::<_, SlotValueRocksdb, _>(CfAccountSlotsHistoryValue::V1).unwrap());
         transactions_checker.add(test_deserialization::<_, BlockNumberRocksdb, _>(CfTransactionsValue::V1).unwrap());
-        blocks_by_number_checker.add(test_deserialization::<_, BlockRocksdb, _>(CfBlocksByNumberValue::V1).unwrap());
+        blocks_by_number_checker.add(test_deserialization::<_, BlockRocksdbV1, _>(CfBlocksByNumberValue::V1).unwrap());
+        blocks_by_number_checker.add(test_deserialization::<_, BlockRocksdbV2, _>(CfBlocksByNumberValue::V2).unwrap());
+        blocks_by_number_checker.add(test_deserialization::<_, BlockRocksdbV3, _>(CfBlocksByNumberValue::V3).unwrap());
+        blocks_by_number_checker.add(test_deserialization::<_, BlockRocksdb, _>(CfBlocksByNumberValue::V4).unwrap());
         blocks_by_hash_checker.add(test_deserialization::<_, BlockNumberRocksdb, _>(CfBlocksByHashValue::V1).unwrap());
+        block_hashes_by_number_checker.add(test_deserialization::<_, HashRocksdb, _>(CfBlockHashesByNumberValue::V1).unwrap());
         logs_checker.add(test_deserialization::<_, BlockNumberRocksdb, _>(CfLogsValue::V1).unwrap());
+        logs_by_address_checker.add(test_deserialization::<_, HashRocksdb, _>(CfLogsByAddressValue::V1).unwrap());
+        logs_by_topic0_checker.add(test_deserialization::<_, HashRocksdb, _>(CfLogsByTopic0Value::V1).unwrap());
+        transactions_by_address_checker.add(test_deserialization::<_, HashRocksdb, _>(CfTransactionsByAddressValue::V1).unwrap());
+        transactions_by_sender_nonce_checker.add(test_deserialization::<_, HashRocksdb, _>(CfTransactionsBySenderNonceValue::V1).unwrap());
+        contract_creations_checker.add(test_deserialization::<_, HashRocksdb, _>(CfContractCreationsValue::V1).unwrap());
+        state_diffs_checker.add(test_deserialization::<_, StateDiffRocksdb, _>(CfStateDiffsValue::V1).unwrap());
+        state_trie_nodes_checker.add(test_deserialization::<_, BytesRocksdb, _>(CfStateTrieNodesValue::V1).unwrap());
+        state_roots_checker.add(test_deserialization::<_, HashRocksdb, _>(CfStateRootsValue::V1).unwrap());
+        bytecodes_checker.add(test_deserialization::<_, BytesRocksdb, _>(CfBytecodesValue::V1).unwrap());
+        indexes_backfill_checker.add(test_deserialization::<_, BlockNumberRocksdb, _>(CfIndexesBackfillValue::V1).unwrap());
     }
 }
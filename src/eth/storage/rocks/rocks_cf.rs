@@ -182,6 +182,20 @@ where
         Ok(())
     }
 
+    pub fn prepare_batch_deletion<I>(&self, deletions: I, batch: &mut WriteBatch) -> Result<()>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let cf = self.handle();
+
+        for key in deletions {
+            let serialized_key = self.serialize_key_with_context(&key)?;
+            // add the deletion operation to the batch
+            batch.delete_cf(&cf, serialized_key);
+        }
+        Ok(())
+    }
+
     #[allow(dead_code)]
     pub fn iter_start(&self) -> RocksCfIter<K, V> {
         let cf = self.handle();
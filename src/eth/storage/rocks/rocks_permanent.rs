@@ -1,28 +1,37 @@
 use std::path::Path;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::bail;
 
 use super::rocks_state::RocksStorageState;
 use crate::eth::primitives::Account;
+use crate::eth::primitives::AccountProof;
 use crate::eth::primitives::Address;
 use crate::eth::primitives::Block;
 use crate::eth::primitives::BlockFilter;
 use crate::eth::primitives::BlockNumber;
+use crate::eth::primitives::BlockSearch;
+use crate::eth::primitives::Bytes;
+use crate::eth::primitives::CodeHash;
+use crate::eth::primitives::ExecutionAccountChanges;
 use crate::eth::primitives::Hash;
 use crate::eth::primitives::LogFilter;
 use crate::eth::primitives::LogMined;
+use crate::eth::primitives::Nonce;
 use crate::eth::primitives::Slot;
 use crate::eth::primitives::SlotIndex;
+use crate::eth::primitives::StateDiff;
 use crate::eth::primitives::TransactionMined;
 use crate::eth::storage::PermanentStorage;
 use crate::eth::storage::StoragePointInTime;
+use crate::ext::spawn_thread;
 
 #[derive(Debug)]
 pub struct RocksPermanentStorage {
-    pub state: RocksStorageState,
+    pub state: Arc<RocksStorageState>,
     block_number: AtomicU64,
 }
 
@@ -48,9 +57,19 @@ impl RocksPermanentStorage {
             "data/rocksdb".to_string()
         };
 
-        let state = RocksStorageState::new(path, shutdown_timeout)?;
+        let state = Arc::new(RocksStorageState::new(path, shutdown_timeout)?);
         let block_number = state.preload_block_number()?;
 
+        // index existing blocks in background because it can take hours in large databases
+        if state.has_indexes_backfill() {
+            let state = Arc::clone(&state);
+            spawn_thread("rocks::indexes_backfill", move || {
+                if let Err(e) = state.backfill_indexes() {
+                    tracing::error!(reason = ?e, "failed to index existing blocks");
+                }
+            });
+        }
+
         Ok(Self { state, block_number })
     }
 
@@ -97,6 +116,12 @@ impl PermanentStorage for RocksPermanentStorage {
         })
     }
 
+    fn read_bytecode(&self, code_hash: &CodeHash) -> anyhow::Result<Option<Bytes>> {
+        self.state.read_bytecode(code_hash).inspect_err(|e| {
+            tracing::error!(reason = ?e, "failed to read bytecode in RocksPermanent");
+        })
+    }
+
     fn read_block(&self, selection: &BlockFilter) -> anyhow::Result<Option<Block>> {
         let block = self.state.read_block(selection).inspect_err(|e| {
             tracing::error!(reason = ?e, "failed to read block in RocksPermanent");
@@ -107,18 +132,48 @@ impl PermanentStorage for RocksPermanentStorage {
         block
     }
 
+    fn read_block_hash(&self, number: BlockNumber) -> anyhow::Result<Option<Hash>> {
+        self.state.read_block_hash(number).inspect_err(|e| {
+            tracing::error!(reason = ?e, "failed to read block hash in RocksPermanent");
+        })
+    }
+
     fn read_transaction(&self, hash: &Hash) -> anyhow::Result<Option<TransactionMined>> {
         self.state.read_transaction(hash).inspect_err(|e| {
             tracing::error!(reason = ?e, "failed to read transaction in RocksPermanent");
         })
     }
 
-    fn read_logs(&self, filter: &LogFilter) -> anyhow::Result<Vec<LogMined>> {
-        self.state.read_logs(filter).inspect_err(|e| {
+    fn read_logs(&self, filter: &LogFilter, limit: usize) -> anyhow::Result<Vec<LogMined>> {
+        self.state.read_logs(filter, limit).inspect_err(|e| {
             tracing::error!(reason = ?e, "failed to read log in RocksPermanent");
         })
     }
 
+    fn read_transactions_by_address(&self, address: &Address, search: BlockSearch, limit: usize) -> anyhow::Result<Vec<TransactionMined>> {
+        self.state.read_transactions_by_address(address, search, limit).inspect_err(|e| {
+            tracing::error!(reason = ?e, "failed to read transactions by address in RocksPermanent");
+        })
+    }
+
+    fn read_transaction_by_sender_and_nonce(&self, sender: &Address, nonce: Nonce) -> anyhow::Result<Option<TransactionMined>> {
+        self.state.read_transaction_by_sender_and_nonce(sender, nonce).inspect_err(|e| {
+            tracing::error!(reason = ?e, "failed to read transaction by sender and nonce in RocksPermanent");
+        })
+    }
+
+    fn read_contract_creation(&self, address: &Address) -> anyhow::Result<Option<TransactionMined>> {
+        self.state.read_contract_creation(address).inspect_err(|e| {
+            tracing::error!(reason = ?e, "failed to read contract creation in RocksPermanent");
+        })
+    }
+
+    fn read_state_diff(&self, tx_hash: &Hash) -> anyhow::Result<Option<StateDiff>> {
+        self.state.read_state_diff(tx_hash).inspect_err(|e| {
+            tracing::error!(reason = ?e, "failed to read state diff in RocksPermanent");
+        })
+    }
+
     fn save_block(&self, block: Block) -> anyhow::Result<()> {
         #[cfg(feature = "metrics")]
         {
@@ -131,12 +186,49 @@ impl PermanentStorage for RocksPermanentStorage {
         })
     }
 
+    fn save_genesis(&self, block: Block, accounts: Vec<ExecutionAccountChanges>) -> anyhow::Result<()> {
+        self.state.save_block_with_account_changes(block, accounts).inspect_err(|e| {
+            tracing::error!(reason = ?e, "failed to save genesis in RocksPermanent");
+        })
+    }
+
     fn save_accounts(&self, accounts: Vec<Account>) -> anyhow::Result<()> {
         self.state.save_accounts(accounts).inspect_err(|e| {
             tracing::error!(reason = ?e, "failed to save accounts in RocksPermanent");
         })
     }
 
+    fn save_account_changes(&self, changes: Vec<ExecutionAccountChanges>) -> anyhow::Result<()> {
+        let block_number = self.block_number.load(Ordering::SeqCst).into();
+        self.state.save_account_changes(changes, block_number).inspect_err(|e| {
+            tracing::error!(reason = ?e, "failed to save account changes in RocksPermanent");
+        })
+    }
+
+    fn save_fork_account(&self, account: Account) -> anyhow::Result<()> {
+        self.state.save_fork_account(account).inspect_err(|e| {
+            tracing::error!(reason = ?e, "failed to save fork account in RocksPermanent");
+        })
+    }
+
+    fn save_fork_slot(&self, address: &Address, slot: Slot) -> anyhow::Result<()> {
+        self.state.save_fork_slot(address, slot).inspect_err(|e| {
+            tracing::error!(reason = ?e, "failed to save fork slot in RocksPermanent");
+        })
+    }
+
+    fn compute_state_root(&self, changes: &[ExecutionAccountChanges]) -> anyhow::Result<Hash> {
+        self.state.compute_state_root(changes).inspect_err(|e| {
+            tracing::error!(reason = ?e, "failed to compute state root in RocksPermanent");
+        })
+    }
+
+    fn read_proof(&self, address: &Address, indexes: &[SlotIndex], point_in_time: &StoragePointInTime) -> anyhow::Result<AccountProof> {
+        self.state.read_proof(address, indexes, point_in_time).inspect_err(|e| {
+            tracing::error!(reason = ?e, "failed to read proof in RocksPermanent");
+        })
+    }
+
     #[cfg(feature = "dev")]
     fn reset(&self) -> anyhow::Result<()> {
         self.block_number.store(0u64, Ordering::SeqCst);
@@ -144,4 +236,18 @@ impl PermanentStorage for RocksPermanentStorage {
             tracing::error!(reason = ?e, "failed to reset in RocksPermanent");
         })
     }
+
+    #[cfg(feature = "dev")]
+    fn reset_at(&self, number: BlockNumber) -> anyhow::Result<()> {
+        self.state.reset_at(number).inspect_err(|e| {
+            tracing::error!(reason = ?e, "failed to reset to block in RocksPermanent");
+        })?;
+        self.block_number.store(number.as_u64(), Ordering::SeqCst);
+        Ok(())
+    }
+
+    #[cfg(feature = "dev")]
+    fn supports_reset_at(&self) -> bool {
+        true
+    }
 }
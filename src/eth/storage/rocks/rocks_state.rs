@@ -1,14 +1,20 @@
+use std::collections::BTreeSet;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Debug;
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
 use std::time::Instant;
 
 use anyhow::bail;
 use anyhow::Context;
 use anyhow::Result;
+use itertools::Itertools;
 use lazy_static::lazy_static;
 use rocksdb::Direction;
 use rocksdb::Options;
@@ -23,11 +29,23 @@ use super::cf_versions::CfAccountSlotsHistoryValue;
 use super::cf_versions::CfAccountSlotsValue;
 use super::cf_versions::CfAccountsHistoryValue;
 use super::cf_versions::CfAccountsValue;
+use super::cf_versions::CfBlockHashesByNumberValue;
 use super::cf_versions::CfBlocksByHashValue;
 use super::cf_versions::CfBlocksByNumberValue;
+use super::cf_versions::CfBytecodesValue;
+use super::cf_versions::CfContractCreationsValue;
+use super::cf_versions::CfIndexesBackfillValue;
+use super::cf_versions::CfLogsByAddressValue;
+use super::cf_versions::CfLogsByTopic0Value;
 use super::cf_versions::CfLogsValue;
+use super::cf_versions::CfStateDiffsValue;
+use super::cf_versions::CfStateRootsValue;
+use super::cf_versions::CfStateTrieNodesValue;
+use super::cf_versions::CfTransactionsByAddressValue;
+use super::cf_versions::CfTransactionsBySenderNonceValue;
 use super::cf_versions::CfTransactionsValue;
 use super::rocks_batch_writer::write_in_batch_for_multiple_cfs_impl;
+use super::rocks_cf::RocksCfIter;
 use super::rocks_cf::RocksCfRef;
 use super::rocks_config::CacheSetting;
 use super::rocks_config::DbConfig;
@@ -35,32 +53,46 @@ use super::rocks_db::create_or_open_db;
 use super::types::AccountRocksdb;
 use super::types::AddressRocksdb;
 use super::types::BlockNumberRocksdb;
+use super::types::BytesRocksdb;
+use super::types::CodeHashRocksdb;
 use super::types::HashRocksdb;
 use super::types::IndexRocksdb;
+use super::types::NonceRocksdb;
 use super::types::SlotIndexRocksdb;
 use crate::eth::primitives::Account;
+use crate::eth::primitives::AccountProof;
 use crate::eth::primitives::Address;
 use crate::eth::primitives::Block;
 use crate::eth::primitives::BlockFilter;
 use crate::eth::primitives::BlockNumber;
+use crate::eth::primitives::BlockSearch;
+use crate::eth::primitives::Bytes;
+use crate::eth::primitives::CodeHash;
 use crate::eth::primitives::ExecutionAccountChanges;
 use crate::eth::primitives::Hash;
+use crate::eth::primitives::Index;
 use crate::eth::primitives::LogFilter;
 use crate::eth::primitives::LogMined;
+use crate::eth::primitives::Nonce;
 use crate::eth::primitives::Slot;
 use crate::eth::primitives::SlotIndex;
+use crate::eth::primitives::StateDiff;
 use crate::eth::primitives::TransactionMined;
+use crate::eth::storage::check_block_state_root;
+use crate::eth::storage::prove_state_trie;
 use crate::eth::storage::rocks::types::SlotValueRocksdb;
+use crate::eth::storage::update_state_trie;
 use crate::eth::storage::StoragePointInTime;
+use crate::eth::storage::TrieNodeSource;
+use crate::ext::not;
 use crate::ext::MutexExt;
 use crate::ext::OptionExt;
 use crate::log_and_err;
 use crate::utils::GIGABYTE;
+use crate::GlobalState;
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "metrics")] {
-        use std::sync::Mutex;
-
         use rocksdb::statistics::Histogram;
         use rocksdb::statistics::Ticker;
 
@@ -78,10 +110,33 @@ lazy_static! {
         "transactions" => DbConfig::LargeSSTFiles.to_options(CacheSetting::Disabled),
         "blocks_by_number" => DbConfig::LargeSSTFiles.to_options(CacheSetting::Disabled),
         "blocks_by_hash" => DbConfig::LargeSSTFiles.to_options(CacheSetting::Disabled),
+        "block_hashes_by_number" => DbConfig::Default.to_options(CacheSetting::Disabled),
         "logs" => DbConfig::LargeSSTFiles.to_options(CacheSetting::Disabled),
+        "logs_by_address" => DbConfig::LargeSSTFiles.to_options(CacheSetting::Disabled),
+        "logs_by_topic0" => DbConfig::LargeSSTFiles.to_options(CacheSetting::Disabled),
+        "transactions_by_address" => DbConfig::LargeSSTFiles.to_options(CacheSetting::Disabled),
+        "transactions_by_sender_nonce" => DbConfig::LargeSSTFiles.to_options(CacheSetting::Disabled),
+        "contract_creations" => DbConfig::Default.to_options(CacheSetting::Disabled),
+        "state_diffs" => DbConfig::LargeSSTFiles.to_options(CacheSetting::Disabled),
+        "state_trie_nodes" => DbConfig::Default.to_options(CacheSetting::Enabled(10 * GIGABYTE)),
+        "state_roots" => DbConfig::Default.to_options(CacheSetting::Disabled),
+        "bytecodes" => DbConfig::Default.to_options(CacheSetting::Enabled(2 * GIGABYTE)),
+        "indexes_backfill" => DbConfig::Default.to_options(CacheSetting::Disabled),
     };
 }
 
+/// Number of blocks indexed between writes when indexing existing blocks.
+const INDEXES_BACKFILL_BLOCKS: u64 = 10_000;
+
+/// Key of the logs indexes in the `indexes_backfill` column family.
+const LOGS_INDEXES: &str = "logs";
+
+/// Key of the transactions indexes in the `indexes_backfill` column family.
+const TRANSACTIONS_INDEXES: &str = "transactions";
+
+/// Number of accounts added to the state trie between writes when building it from existing accounts.
+const STATE_TRIE_BACKFILL_ACCOUNTS: usize = 10_000;
+
 /// Helper for creating a `RocksCfRef`, aborting if it wasn't declared in our option presets.
 fn new_cf_ref<K, V>(db: &Arc<DB>, column_family: &str) -> Result<RocksCfRef<K, V>>
 where
@@ -98,6 +153,51 @@ where
     RocksCfRef::new(Arc::clone(db), column_family)
 }
 
+/// Collects the numbers of the blocks in the filter range that have logs indexed by `key` in a `(key, block number, log index)` index.
+fn read_indexed_log_blocks<P, V>(
+    index: &RocksCfRef<(P, BlockNumberRocksdb, IndexRocksdb), V>,
+    key: P,
+    filter: &LogFilter,
+    numbers: &mut BTreeSet<BlockNumber>,
+) -> Result<()>
+where
+    P: Serialize + for<'de> Deserialize<'de> + Debug + std::hash::Hash + Eq + Copy,
+    V: Serialize + for<'de> Deserialize<'de> + Debug + Clone,
+{
+    let iter = index.iter_from((key, filter.from_block.into(), Index::ZERO.into()), Direction::Forward);
+    for next in iter? {
+        let ((indexed_key, number, _), _) = next?;
+        let number = BlockNumber::from(number);
+        if indexed_key != key || filter.to_block.is_some_and(|to_block| number > to_block) {
+            break;
+        }
+        numbers.insert(number);
+    }
+    Ok(())
+}
+
+/// Prepares the deletion of the entries read by the iterator that match the predicate, returning their keys.
+fn prepare_batch_deletion_where<K, V>(
+    cf: &RocksCfRef<K, V>,
+    iter: RocksCfIter<K, V>,
+    batch: &mut WriteBatch,
+    predicate: impl Fn(&K, &V) -> bool,
+) -> Result<Vec<K>>
+where
+    K: Serialize + for<'de> Deserialize<'de> + Debug + std::hash::Hash + Eq + Clone,
+    V: Serialize + for<'de> Deserialize<'de> + Debug + Clone,
+{
+    let mut deleted = vec![];
+    for next in iter {
+        let (key, value) = next?;
+        if predicate(&key, &value) {
+            deleted.push(key);
+        }
+    }
+    cf.prepare_batch_deletion(deleted.clone(), batch)?;
+    Ok(deleted)
+}
+
 /// State handler for our RocksDB storage, separating "tables" by column families.
 ///
 /// With data separated by column families, writing and reading should be done via the `RocksCfRef` fields.
@@ -111,7 +211,32 @@ pub struct RocksStorageState {
     transactions: RocksCfRef<HashRocksdb, CfTransactionsValue>,
     blocks_by_number: RocksCfRef<BlockNumberRocksdb, CfBlocksByNumberValue>,
     blocks_by_hash: RocksCfRef<HashRocksdb, CfBlocksByHashValue>,
+    /// Hash of each block, used to read hashes without reading the whole block.
+    block_hashes_by_number: RocksCfRef<BlockNumberRocksdb, CfBlockHashesByNumberValue>,
     logs: RocksCfRef<(HashRocksdb, IndexRocksdb), CfLogsValue>,
+    /// Index of logs by emitter address, used to skip blocks without logs of the address.
+    logs_by_address: RocksCfRef<(AddressRocksdb, BlockNumberRocksdb, IndexRocksdb), CfLogsByAddressValue>,
+    /// Index of logs by first topic, used to skip blocks without logs of the topic.
+    logs_by_topic0: RocksCfRef<(HashRocksdb, BlockNumberRocksdb, IndexRocksdb), CfLogsByTopic0Value>,
+    /// Index of transactions by the addresses they interacted with.
+    transactions_by_address: RocksCfRef<(AddressRocksdb, BlockNumberRocksdb, IndexRocksdb), CfTransactionsByAddressValue>,
+    /// Index of transactions by sender and nonce.
+    transactions_by_sender_nonce: RocksCfRef<(AddressRocksdb, NonceRocksdb), CfTransactionsBySenderNonceValue>,
+    /// Index of the transactions that created each contract.
+    contract_creations: RocksCfRef<AddressRocksdb, CfContractCreationsValue>,
+    /// Values modified by each transaction.
+    state_diffs: RocksCfRef<HashRocksdb, CfStateDiffsValue>,
+    state_trie_nodes: RocksCfRef<HashRocksdb, CfStateTrieNodesValue>,
+    state_roots: RocksCfRef<BlockNumberRocksdb, CfStateRootsValue>,
+    bytecodes: RocksCfRef<CodeHashRocksdb, CfBytecodesValue>,
+    /// Next block to be indexed by each indexes backfill that is not finished.
+    indexes_backfill: RocksCfRef<String, CfIndexesBackfillValue>,
+    /// Whether the logs indexes cover all blocks, otherwise logs are searched in all blocks.
+    logs_indexed: AtomicBool,
+    /// Whether the transactions indexes cover all blocks, otherwise they can't be read.
+    transactions_indexed: AtomicBool,
+    /// Held while the current state of accounts and slots is written, so values read from the fork are only inserted if absent.
+    current_state_lock: Mutex<()>,
     /// Last collected stats for a histogram
     #[cfg(feature = "metrics")]
     prev_stats: Mutex<HashMap<HistogramInt, (Sum, Count)>>,
@@ -146,7 +271,21 @@ impl RocksStorageState {
             transactions: new_cf_ref(&db, "transactions")?,
             blocks_by_number: new_cf_ref(&db, "blocks_by_number")?,
             blocks_by_hash: new_cf_ref(&db, "blocks_by_hash")?,
+            block_hashes_by_number: new_cf_ref(&db, "block_hashes_by_number")?,
             logs: new_cf_ref(&db, "logs")?,
+            logs_by_address: new_cf_ref(&db, "logs_by_address")?,
+            logs_by_topic0: new_cf_ref(&db, "logs_by_topic0")?,
+            transactions_by_address: new_cf_ref(&db, "transactions_by_address")?,
+            transactions_by_sender_nonce: new_cf_ref(&db, "transactions_by_sender_nonce")?,
+            contract_creations: new_cf_ref(&db, "contract_creations")?,
+            state_diffs: new_cf_ref(&db, "state_diffs")?,
+            state_trie_nodes: new_cf_ref(&db, "state_trie_nodes")?,
+            state_roots: new_cf_ref(&db, "state_roots")?,
+            bytecodes: new_cf_ref(&db, "bytecodes")?,
+            indexes_backfill: new_cf_ref(&db, "indexes_backfill")?,
+            logs_indexed: AtomicBool::new(true),
+            transactions_indexed: AtomicBool::new(true),
+            current_state_lock: Mutex::default(),
             #[cfg(feature = "metrics")]
             prev_stats: Mutex::default(),
             #[cfg(feature = "metrics")]
@@ -156,6 +295,8 @@ impl RocksStorageState {
         };
 
         tracing::debug!("opened database successfully");
+        state.prepare_indexes_backfill().context("when preparing to index existing blocks")?;
+        state.backfill_state_trie().context("when building state trie from existing accounts")?;
         Ok(state)
     }
 
@@ -179,10 +320,123 @@ impl RocksStorageState {
         self.transactions.clear()?;
         self.blocks_by_number.clear()?;
         self.blocks_by_hash.clear()?;
+        self.block_hashes_by_number.clear()?;
         self.logs.clear()?;
+        self.logs_by_address.clear()?;
+        self.logs_by_topic0.clear()?;
+        self.transactions_by_address.clear()?;
+        self.transactions_by_sender_nonce.clear()?;
+        self.contract_creations.clear()?;
+        self.state_diffs.clear()?;
+        self.state_trie_nodes.clear()?;
+        self.state_roots.clear()?;
+        self.bytecodes.clear()?;
+        self.indexes_backfill.clear()?;
         Ok(())
     }
 
+    /// Discards all blocks and state changes after the specified block number.
+    ///
+    /// Blocks are stored without their account changes, so the history and indexes column families are read entirely to find
+    /// the entries of the discarded blocks. The current state of the accounts and slots they modified is rebuilt from the history.
+    pub fn reset_at(&self, number: BlockNumber) -> Result<()> {
+        let _current_state_lock = self.current_state_lock.lock_or_clear("rocks current state lock was poisoned");
+
+        let mut batch = WriteBatch::default();
+        let discarded_start = BlockNumberRocksdb::from(number.next_block_number());
+        let number = BlockNumberRocksdb::from(number);
+
+        // blocks and state roots
+        let discarded_numbers = self
+            .blocks_by_number
+            .iter_from(discarded_start, Direction::Forward)?
+            .keys()
+            .collect::<Result<Vec<_>>>()?;
+        self.blocks_by_number.prepare_batch_deletion(discarded_numbers.clone(), &mut batch)?;
+        self.block_hashes_by_number.prepare_batch_deletion(discarded_numbers, &mut batch)?;
+        let discarded_roots = self
+            .state_roots
+            .iter_from(discarded_start, Direction::Forward)?
+            .keys()
+            .collect::<Result<Vec<_>>>()?;
+        self.state_roots.prepare_batch_deletion(discarded_roots, &mut batch)?;
+        prepare_batch_deletion_where(&self.blocks_by_hash, self.blocks_by_hash.iter_start(), &mut batch, |_, block| **block > number)?;
+
+        // transactions and logs
+        let tx_hashes: HashSet<HashRocksdb> = prepare_batch_deletion_where(&self.transactions, self.transactions.iter_start(), &mut batch, |_, tx_block| {
+            **tx_block > number
+        })?
+        .into_iter()
+        .collect();
+        prepare_batch_deletion_where(&self.logs, self.logs.iter_start(), &mut batch, |_, log_block| **log_block > number)?;
+        self.state_diffs.prepare_batch_deletion(tx_hashes.iter().copied(), &mut batch)?;
+
+        // indexes
+        prepare_batch_deletion_where(&self.logs_by_address, self.logs_by_address.iter_start(), &mut batch, |(_, block, _), _| {
+            *block > number
+        })?;
+        prepare_batch_deletion_where(&self.logs_by_topic0, self.logs_by_topic0.iter_start(), &mut batch, |(_, block, _), _| {
+            *block > number
+        })?;
+        prepare_batch_deletion_where(
+            &self.transactions_by_address,
+            self.transactions_by_address.iter_start(),
+            &mut batch,
+            |(_, block, _), _| *block > number,
+        )?;
+        prepare_batch_deletion_where(
+            &self.transactions_by_sender_nonce,
+            self.transactions_by_sender_nonce.iter_start(),
+            &mut batch,
+            |_, tx_hash| tx_hashes.contains(&**tx_hash),
+        )?;
+        prepare_batch_deletion_where(&self.contract_creations, self.contract_creations.iter_start(), &mut batch, |_, tx_hash| {
+            tx_hashes.contains(&**tx_hash)
+        })?;
+
+        // history
+        let discarded_accounts = prepare_batch_deletion_where(&self.accounts_history, self.accounts_history.iter_start(), &mut batch, |(_, block), _| {
+            *block > number
+        })?;
+        let discarded_slots = prepare_batch_deletion_where(
+            &self.account_slots_history,
+            self.account_slots_history.iter_start(),
+            &mut batch,
+            |(_, _, block), _| *block > number,
+        )?;
+
+        // current state of the accounts and slots modified by the discarded blocks
+        for address in discarded_accounts.into_iter().map(|(address, _)| address).unique() {
+            let previous = self.accounts_history.iter_from((address, number), Direction::Reverse)?.next().transpose()?;
+            match previous {
+                Some(((previous_address, _), account)) if previous_address == address => {
+                    let (account, inline_bytecode) = account.into_latest();
+                    if let Some(bytecode) = inline_bytecode {
+                        self.bytecodes.prepare_batch_insertion([(account.code_hash, bytecode.into())], &mut batch)?;
+                    }
+                    self.accounts.prepare_batch_insertion([(address, account.into())], &mut batch)?;
+                }
+                _ => self.accounts.prepare_batch_deletion([address], &mut batch)?,
+            }
+        }
+        for (address, index) in discarded_slots.into_iter().map(|(address, index, _)| (address, index)).unique() {
+            let previous = self
+                .account_slots_history
+                .iter_from((address, index, number), Direction::Reverse)?
+                .next()
+                .transpose()?;
+            match previous {
+                Some(((previous_address, previous_index, _), value)) if previous_address == address && previous_index == index => {
+                    self.account_slots
+                        .prepare_batch_insertion([((address, index), value.into_inner().into())], &mut batch)?;
+                }
+                _ => self.account_slots.prepare_batch_deletion([(address, index)], &mut batch)?,
+            }
+        }
+
+        self.write_in_batch_for_multiple_cfs(batch)
+    }
+
     /// Updates the in-memory state with changes from transaction execution
     fn prepare_batch_with_execution_changes<C>(&self, changes: C, block_number: BlockNumber, batch: &mut WriteBatch) -> Result<()>
     where
@@ -194,7 +448,13 @@ impl RocksStorageState {
 
             if change.is_account_modified() {
                 let address: AddressRocksdb = change.address.into();
-                let mut account_info_entry = self.accounts.get(&address)?.unwrap_or(AccountRocksdb::default().into());
+                let (mut account_info_entry, inline_bytecode) = self.accounts.get(&address)?.map(CfAccountsValue::into_latest).unwrap_or_default();
+
+                // accounts stored with the bytecode inline have it moved to the bytecodes column family
+                if let Some(bytecode) = inline_bytecode {
+                    self.bytecodes
+                        .prepare_batch_insertion([(account_info_entry.code_hash, bytecode.into())], batch)?;
+                }
 
                 if let Some(nonce) = change.nonce.take_modified() {
                     account_info_entry.nonce = nonce.into();
@@ -203,12 +463,16 @@ impl RocksStorageState {
                     account_info_entry.balance = balance.into();
                 }
                 if let Some(bytecode) = change.bytecode.take_modified() {
-                    account_info_entry.bytecode = bytecode.map_into();
+                    let code_hash = CodeHash::from_bytecode(bytecode.clone());
+                    if let Some(bytecode) = bytecode {
+                        self.bytecodes.prepare_batch_insertion([(code_hash.into(), bytecode.into())], batch)?;
+                    }
+                    account_info_entry.code_hash = code_hash.into();
                 }
 
-                self.accounts.prepare_batch_insertion([(address, account_info_entry.clone())], batch)?;
+                self.accounts.prepare_batch_insertion([(address, account_info_entry.clone().into())], batch)?;
                 self.accounts_history
-                    .prepare_batch_insertion([((address, block_number), account_info_entry.into_inner().into())], batch)?;
+                    .prepare_batch_insertion([((address, block_number), account_info_entry.into())], batch)?;
             }
 
             for (slot_index, slot_change) in &change.slots {
@@ -227,6 +491,42 @@ impl RocksStorageState {
         Ok(())
     }
 
+    /// Updates the state trie with changes from transaction execution and records the resulting state root.
+    ///
+    /// Returns the state root before and after the changes.
+    fn prepare_batch_with_state_trie(&self, changes: &[ExecutionAccountChanges], block_number: BlockNumber, batch: &mut WriteBatch) -> Result<(Hash, Hash)> {
+        let previous_root = self.read_state_root(&StoragePointInTime::Mined)?;
+        let update = update_state_trie(self, previous_root, changes)?;
+        let root = update.root;
+
+        let nodes = update.nodes.into_iter().map(|(hash, node)| (hash.into(), BytesRocksdb(node).into()));
+        self.state_trie_nodes.prepare_batch_insertion(nodes, batch)?;
+        self.state_roots.prepare_batch_insertion([(block_number.into(), root.into())], batch)?;
+
+        Ok((previous_root, root))
+    }
+
+    pub fn read_state_root(&self, point_in_time: &StoragePointInTime) -> Result<Hash> {
+        let root = match point_in_time {
+            StoragePointInTime::Mined | StoragePointInTime::Pending => self.state_roots.last_value()?,
+            StoragePointInTime::MinedPast(number) => match self.state_roots.iter_from((*number).into(), Direction::Reverse)?.next() {
+                Some(next) => Some(next?.1),
+                None => None,
+            },
+        };
+        Ok(root.map(|root| root.into_inner().into()).unwrap_or(Hash::EMPTY_TRIE))
+    }
+
+    pub fn compute_state_root(&self, changes: &[ExecutionAccountChanges]) -> Result<Hash> {
+        let root = self.read_state_root(&StoragePointInTime::Mined)?;
+        Ok(update_state_trie(self, root, changes)?.root)
+    }
+
+    pub fn read_proof(&self, address: &Address, indexes: &[SlotIndex], point_in_time: &StoragePointInTime) -> Result<AccountProof> {
+        let root = self.read_state_root(point_in_time)?;
+        prove_state_trie(self, root, *address, indexes)
+    }
+
     pub fn read_transaction(&self, tx_hash: &Hash) -> Result<Option<TransactionMined>> {
         let Some(block_number) = self.transactions.get(&(*tx_hash).into())? else {
             return Ok(None);
@@ -249,36 +549,178 @@ impl RocksStorageState {
         }
     }
 
-    pub fn read_logs(&self, filter: &LogFilter) -> Result<Vec<LogMined>> {
+    /// Reads logs matching the filter.
+    ///
+    /// Blocks are read whole, so reading stops after the first block that makes the result exceed `limit` logs.
+    pub fn read_logs(&self, filter: &LogFilter, limit: usize) -> Result<Vec<LogMined>> {
         let is_block_number_in_end_range = |number: BlockNumber| match filter.to_block.as_ref() {
             Some(&last_block) => number <= last_block,
             None => true,
         };
 
-        let iter = self
-            .blocks_by_number
-            .iter_from(BlockNumberRocksdb::from(filter.from_block), Direction::Forward)?;
-
         let mut logs_result = vec![];
 
-        for next in iter {
-            let (number, block) = next?;
+        // returns true when the limit was exceeded and reading must stop
+        let mut read_block_logs = |block: CfBlocksByNumberValue| {
+            let block = block.into_inner();
+            if filter.matches_bloom(&block.header.bloom.into()) {
+                let logs = block.transactions.into_iter().flat_map(|transaction| transaction.logs).map(LogMined::from);
+
+                let filtered_logs = logs.filter(|log| filter.matches(log));
+                logs_result.extend(filtered_logs);
+            }
+            logs_result.len() > limit
+        };
+
+        match self.read_logs_candidate_blocks(filter)? {
+            // only blocks with logs of the filtered addresses and first topics
+            Some(numbers) =>
+                for number in numbers {
+                    let Some(block) = self.blocks_by_number.get(&number.into())? else {
+                        return log_and_err!("rocks error, block wasn't found where the logs index pointed at")
+                            .with_context(|| format!("block_number = {}", number));
+                    };
+                    if read_block_logs(block) {
+                        break;
+                    }
+                },
+            // all blocks in the range
+            None => {
+                let iter = self
+                    .blocks_by_number
+                    .iter_from(BlockNumberRocksdb::from(filter.from_block), Direction::Forward)?;
+
+                for next in iter {
+                    let (number, block) = next?;
 
-            if !is_block_number_in_end_range(number.into()) {
+                    if not(is_block_number_in_end_range(number.into())) || read_block_logs(block) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(logs_result)
+    }
+
+    /// Reads the numbers of the blocks in the filter range that have logs of the filtered addresses and first topics.
+    ///
+    /// Returns `None` if the filter restricts neither or the indexes are still being built, in which case all blocks in the
+    /// range must be read.
+    fn read_logs_candidate_blocks(&self, filter: &LogFilter) -> Result<Option<BTreeSet<BlockNumber>>> {
+        if not(self.logs_indexed.load(Ordering::Relaxed)) {
+            return Ok(None);
+        }
+        let mut candidates: Option<BTreeSet<BlockNumber>> = None;
+
+        if not(filter.addresses.is_empty()) {
+            let mut numbers = BTreeSet::new();
+            for address in &filter.addresses {
+                read_indexed_log_blocks(&self.logs_by_address, (*address).into(), filter, &mut numbers)?;
+            }
+            candidates = Some(numbers);
+        }
+
+        if let Some(topics) = filter.first_topics() {
+            let mut numbers = BTreeSet::new();
+            for topic in topics {
+                read_indexed_log_blocks(&self.logs_by_topic0, Hash::from(topic.0).into(), filter, &mut numbers)?;
+            }
+            candidates = match candidates {
+                Some(candidates) => Some(candidates.intersection(&numbers).copied().collect()),
+                None => Some(numbers),
+            };
+        }
+
+        Ok(candidates)
+    }
+
+    /// Fails while the transactions indexes are built from existing blocks, because they would return partial results.
+    fn require_transactions_indexed(&self) -> Result<()> {
+        if not(self.transactions_indexed.load(Ordering::Relaxed)) {
+            return log_and_err!("transactions indexes are still being built from existing blocks");
+        }
+        Ok(())
+    }
+
+    /// Reads the transactions that interacted with an address in the searched blocks, in the search order.
+    ///
+    /// Blocks are read whole, so reading stops after the first block that makes the result reach `limit` transactions.
+    pub fn read_transactions_by_address(&self, address: &Address, search: BlockSearch, limit: usize) -> Result<Vec<TransactionMined>> {
+        self.require_transactions_indexed()?;
+        let address = AddressRocksdb::from(*address);
+        let iter = match search {
+            BlockSearch::Before(number) => {
+                let Some(start) = number.prev() else { return Ok(vec![]) };
+                let start_key = (address, start.into(), Index::new(u64::MAX).into());
+                self.transactions_by_address.iter_from(start_key, Direction::Reverse)?
+            }
+            BlockSearch::After(number) => {
+                let start_key = (address, number.next_block_number().into(), Index::ZERO.into());
+                self.transactions_by_address.iter_from(start_key, Direction::Forward)?
+            }
+        };
+
+        // collect the indexes of the transactions in each block
+        let mut blocks: Vec<(BlockNumber, Vec<Index>)> = vec![];
+        let mut count = 0;
+        for next in iter {
+            let ((indexed_address, number, index), _) = next?;
+            let number = BlockNumber::from(number);
+            if indexed_address != address || not(search.contains(number)) {
                 break;
             }
+            match blocks.last_mut() {
+                Some((last_number, indexes)) if *last_number == number => indexes.push(index.into()),
+                _ if count >= limit => break,
+                _ => blocks.push((number, vec![index.into()])),
+            }
+            count += 1;
+        }
 
-            let logs = block
-                .into_inner()
+        // read the transactions from their blocks
+        let mut transactions = Vec::with_capacity(count);
+        for (number, indexes) in blocks {
+            let Some(block) = self.blocks_by_number.get(&number.into())? else {
+                return log_and_err!("rocks error, block wasn't found where the transactions index pointed at")
+                    .with_context(|| format!("block_number = {}", number));
+            };
+            let block: Block = block.into_inner().into();
+
+            let mut block_transactions = block
                 .transactions
                 .into_iter()
-                .flat_map(|transaction| transaction.logs)
-                .map(LogMined::from);
+                .filter(|transaction| indexes.contains(&transaction.transaction_index))
+                .collect::<Vec<_>>();
+            block_transactions.sort_by_key(|transaction| transaction.transaction_index);
+            if matches!(search, BlockSearch::Before(_)) {
+                block_transactions.reverse();
+            }
+            transactions.extend(block_transactions);
+        }
+
+        Ok(transactions)
+    }
 
-            let filtered_logs = logs.filter(|log| filter.matches(log));
-            logs_result.extend(filtered_logs);
+    pub fn read_transaction_by_sender_and_nonce(&self, sender: &Address, nonce: Nonce) -> Result<Option<TransactionMined>> {
+        self.require_transactions_indexed()?;
+        match self.transactions_by_sender_nonce.get(&((*sender).into(), nonce.into()))? {
+            Some(tx_hash) => self.read_transaction(&tx_hash.into_inner().into()),
+            None => Ok(None),
         }
-        Ok(logs_result)
+    }
+
+    pub fn read_contract_creation(&self, address: &Address) -> Result<Option<TransactionMined>> {
+        self.require_transactions_indexed()?;
+        match self.contract_creations.get(&(*address).into())? {
+            Some(tx_hash) => self.read_transaction(&tx_hash.into_inner().into()),
+            None => Ok(None),
+        }
+    }
+
+    pub fn read_state_diff(&self, tx_hash: &Hash) -> Result<Option<StateDiff>> {
+        let state_diff = self.state_diffs.get(&(*tx_hash).into())?;
+        Ok(state_diff.map(|state_diff| state_diff.into_inner().into()))
     }
 
     pub fn read_slot(&self, address: &Address, index: &SlotIndex, point_in_time: &StoragePointInTime) -> Result<Option<Slot>> {
@@ -332,7 +774,7 @@ impl RocksStorageState {
                     return Ok(None);
                 };
 
-                let account = inner_account.to_account(address);
+                let account = self.to_account(address, inner_account.into_latest())?;
                 tracing::trace!(%address, ?account, "account found");
                 Ok(Some(account))
             }
@@ -342,7 +784,7 @@ impl RocksStorageState {
                 if let Some(next) = self.accounts_history.iter_from(iterator_start, rocksdb::Direction::Reverse)?.next() {
                     let ((addr, _), account_info) = next?;
                     if addr == (*address).into() {
-                        return Ok(Some(account_info.to_account(address)));
+                        return Ok(Some(self.to_account(address, account_info.into_latest())?));
                     }
                 }
                 Ok(None)
@@ -350,11 +792,29 @@ impl RocksStorageState {
         }
     }
 
+    /// Converts a stored account to an account, loading its bytecode from the bytecodes column family when it is not inline.
+    fn to_account(&self, address: &Address, (account, inline_bytecode): (AccountRocksdb, Option<BytesRocksdb>)) -> Result<Account> {
+        let bytecode = match inline_bytecode {
+            Some(bytecode) => Some(bytecode),
+            None if account.has_bytecode() => match self.bytecodes.get(&account.code_hash)? {
+                Some(bytecode) => Some(bytecode.into_inner()),
+                None => return log_and_err!(payload = address, "account bytecode was not found in the bytecodes column family"),
+            },
+            None => None,
+        };
+        Ok(account.to_account(address, bytecode.map_into()))
+    }
+
+    pub fn read_bytecode(&self, code_hash: &CodeHash) -> Result<Option<Bytes>> {
+        let bytecode = self.bytecodes.get(&(*code_hash).into())?;
+        Ok(bytecode.map(|bytecode| bytecode.into_inner().into()))
+    }
+
     pub fn read_block(&self, selection: &BlockFilter) -> Result<Option<Block>> {
         tracing::debug!(?selection, "reading block");
 
         let block = match selection {
-            BlockFilter::Latest | BlockFilter::Pending => self.blocks_by_number.last_value(),
+            BlockFilter::Latest | BlockFilter::Pending | BlockFilter::Safe | BlockFilter::Finalized => self.blocks_by_number.last_value(),
             BlockFilter::Earliest => self.blocks_by_number.first_value(),
             BlockFilter::Number(block_number) => self.blocks_by_number.get(&(*block_number).into()),
             BlockFilter::Hash(block_hash) =>
@@ -368,8 +828,24 @@ impl RocksStorageState {
         block.map(|block_option| block_option.map(|block| block.into_inner().into()))
     }
 
+    /// Reads the hash of a block without reading the whole block.
+    ///
+    /// Blocks saved before block hashes were indexed are read whole.
+    pub fn read_block_hash(&self, number: BlockNumber) -> Result<Option<Hash>> {
+        if let Some(hash) = self.block_hashes_by_number.get(&number.into())? {
+            return Ok(Some(hash.into_inner().into()));
+        }
+        Ok(self.read_block(&BlockFilter::Number(number))?.map(|block| block.hash()))
+    }
+
     pub fn save_accounts(&self, accounts: Vec<Account>) -> Result<()> {
         let mut write_batch = WriteBatch::default();
+        self.bytecodes.prepare_batch_insertion(
+            accounts
+                .iter()
+                .filter_map(|acc| Some((CodeHash::from_bytecode(acc.bytecode.clone()).into(), acc.bytecode.clone()?.into()))),
+            &mut write_batch,
+        )?;
         self.accounts.prepare_batch_insertion(
             accounts.iter().cloned().map(|acc| {
                 let tup = <(AddressRocksdb, AccountRocksdb)>::from(acc);
@@ -384,17 +860,216 @@ impl RocksStorageState {
             }),
             &mut write_batch,
         )?;
+
+        let changes = accounts
+            .into_iter()
+            .map(|account| ExecutionAccountChanges::from_modified_values(account, vec![]))
+            .collect::<Vec<_>>();
+        let block_number = self.state_roots.last_key()?.unwrap_or_default();
+        self.prepare_batch_with_state_trie(&changes, block_number.into(), &mut write_batch)?;
+
         write_in_batch_for_multiple_cfs_impl(&self.db, write_batch)?;
         Ok(())
     }
 
+    /// Adds the logs emitted by the transactions to the logs indexes.
+    fn prepare_batch_with_logs_indexes(&self, transactions: &[TransactionMined], batch: &mut WriteBatch) -> Result<()> {
+        let mut logs_by_address_batch = vec![];
+        let mut logs_by_topic0_batch = vec![];
+        for transaction in transactions {
+            let tx_hash = transaction.input.hash;
+            let block_number = BlockNumberRocksdb::from(transaction.block_number);
+            for log in &transaction.logs {
+                let log_index = IndexRocksdb::from(log.log_index);
+                logs_by_address_batch.push(((log.log.address.into(), block_number, log_index), tx_hash.into()));
+                if let Some(topic0) = log.log.topic0 {
+                    logs_by_topic0_batch.push(((Hash::from(topic0.0).into(), block_number, log_index), tx_hash.into()));
+                }
+            }
+        }
+
+        self.logs_by_address.prepare_batch_insertion(logs_by_address_batch, batch)?;
+        self.logs_by_topic0.prepare_batch_insertion(logs_by_topic0_batch, batch)?;
+        Ok(())
+    }
+
+    /// Adds the transactions to the address, sender nonce and contract creation indexes.
+    fn prepare_batch_with_transactions_indexes(&self, transactions: &[TransactionMined], batch: &mut WriteBatch) -> Result<()> {
+        let mut transactions_by_address_batch = vec![];
+        let mut transactions_by_sender_nonce_batch = vec![];
+        let mut contract_creations_batch = vec![];
+        for transaction in transactions {
+            let tx_hash = transaction.input.hash;
+            let block_number = BlockNumberRocksdb::from(transaction.block_number);
+            let tx_index = IndexRocksdb::from(transaction.transaction_index);
+            for address in transaction.involved_addresses() {
+                transactions_by_address_batch.push(((address.into(), block_number, tx_index), tx_hash.into()));
+            }
+            transactions_by_sender_nonce_batch.push(((transaction.input.from.into(), transaction.input.nonce.into()), tx_hash.into()));
+            for contract in transaction.created_contracts() {
+                contract_creations_batch.push((contract.into(), tx_hash.into()));
+            }
+        }
+
+        self.transactions_by_address.prepare_batch_insertion(transactions_by_address_batch, batch)?;
+        self.transactions_by_sender_nonce
+            .prepare_batch_insertion(transactions_by_sender_nonce_batch, batch)?;
+        self.contract_creations.prepare_batch_insertion(contract_creations_batch, batch)?;
+        Ok(())
+    }
+
+    /// Records the indexes that must be built from the blocks saved before they existed.
+    ///
+    /// It runs before any block is saved, because blocks saved afterwards are indexed when saved and would make the indexes
+    /// look complete. The indexes are built by `backfill_indexes`.
+    fn prepare_indexes_backfill(&self) -> Result<()> {
+        let mut batch = WriteBatch::default();
+        if self.logs_by_address.last_key()?.is_none() && self.logs.last_key()?.is_some() {
+            self.indexes_backfill
+                .prepare_batch_insertion([(LOGS_INDEXES.to_owned(), BlockNumber::ZERO.into())], &mut batch)?;
+        }
+        if self.transactions_by_sender_nonce.last_key()?.is_none() && self.transactions.last_key()?.is_some() {
+            self.indexes_backfill
+                .prepare_batch_insertion([(TRANSACTIONS_INDEXES.to_owned(), BlockNumber::ZERO.into())], &mut batch)?;
+        }
+        write_in_batch_for_multiple_cfs_impl(&self.db, batch)?;
+
+        self.logs_indexed
+            .store(self.indexes_backfill.get(&LOGS_INDEXES.to_owned())?.is_none(), Ordering::Relaxed);
+        self.transactions_indexed
+            .store(self.indexes_backfill.get(&TRANSACTIONS_INDEXES.to_owned())?.is_none(), Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Checks if there are indexes that must be built from existing blocks.
+    pub fn has_indexes_backfill(&self) -> bool {
+        not(self.logs_indexed.load(Ordering::Relaxed) && self.transactions_indexed.load(Ordering::Relaxed))
+    }
+
+    /// Indexes the blocks saved before the logs or transactions indexes existed.
+    ///
+    /// The next block to be indexed is saved with each write, so an interrupted backfill resumes from it in the next startup.
+    /// Blocks saved while it runs are indexed again, which rewrites the same entries.
+    pub fn backfill_indexes(&self) -> Result<()> {
+        const TASK_NAME: &str = "rocks::indexes_backfill";
+
+        let read_progress = |key: &str| -> Result<Option<BlockNumber>> {
+            let progress = self.indexes_backfill.get(&key.to_owned())?;
+            Ok(progress.map(|number| number.into_inner().into()))
+        };
+        let logs_from = read_progress(LOGS_INDEXES)?;
+        let transactions_from = read_progress(TRANSACTIONS_INDEXES)?;
+        let Some(from) = logs_from.into_iter().chain(transactions_from).min() else {
+            return Ok(());
+        };
+        tracing::info!(?logs_from, ?transactions_from, "indexing blocks saved before the indexes existed");
+
+        // saves the progress of the backfills that are running along with the indexes of the blocks before it
+        let prepare_batch_with_progress = |next: BlockNumber, batch: &mut WriteBatch| -> Result<()> {
+            let progress = [(LOGS_INDEXES, logs_from), (TRANSACTIONS_INDEXES, transactions_from)]
+                .into_iter()
+                .filter_map(|(key, from)| from.map(|from| (key.to_owned(), next.max(from).into())));
+            self.indexes_backfill.prepare_batch_insertion(progress, batch)
+        };
+
+        let mut batch = WriteBatch::default();
+        for next in self.blocks_by_number.iter_from(from.into(), Direction::Forward)? {
+            if GlobalState::is_shutdown_warn(TASK_NAME) {
+                return Ok(());
+            }
+
+            let (number, block) = next?;
+            let number = BlockNumber::from(number);
+            let block: Block = block.into_inner().into();
+            if logs_from.is_some_and(|from| number >= from) {
+                self.prepare_batch_with_logs_indexes(&block.transactions, &mut batch)?;
+            }
+            if transactions_from.is_some_and(|from| number >= from) {
+                self.prepare_batch_with_transactions_indexes(&block.transactions, &mut batch)?;
+            }
+
+            // flush periodically to avoid holding the whole index in memory
+            if number.as_u64() % INDEXES_BACKFILL_BLOCKS == 0 {
+                prepare_batch_with_progress(number.next_block_number(), &mut batch)?;
+                write_in_batch_for_multiple_cfs_impl(&self.db, std::mem::take(&mut batch))?;
+            }
+        }
+        let done = [LOGS_INDEXES, TRANSACTIONS_INDEXES].map(str::to_owned);
+        self.indexes_backfill.prepare_batch_deletion(done, &mut batch)?;
+        write_in_batch_for_multiple_cfs_impl(&self.db, batch)?;
+
+        self.logs_indexed.store(true, Ordering::Relaxed);
+        self.transactions_indexed.store(true, Ordering::Relaxed);
+        tracing::info!("indexed existing blocks");
+        Ok(())
+    }
+
+    /// Builds the state trie from the current accounts and slots of a database created before the state trie existed.
+    ///
+    /// It runs before the storage is used because the state root of the next block depends on it. The root is recorded only
+    /// after all accounts were added, so an interrupted build starts again in the next startup. Past blocks have no state root.
+    fn backfill_state_trie(&self) -> Result<()> {
+        if self.state_roots.last_key()?.is_some() {
+            return Ok(());
+        }
+        let Some(block_number) = self.blocks_by_number.last_key()? else {
+            return Ok(());
+        };
+        tracing::info!(%block_number, "building state trie from existing accounts");
+
+        let mut root = Hash::EMPTY_TRIE;
+        let mut changes = Vec::with_capacity(STATE_TRIE_BACKFILL_ACCOUNTS);
+        let mut accounts = self.accounts.iter_start().peekable();
+        while let Some(next) = accounts.next() {
+            let (address, account) = next?;
+            let account = self.to_account(&address.into(), account.into_latest())?;
+
+            // slots are sorted by address and index, so the slots of the account are the ones after its first possible slot
+            let mut slots = vec![];
+            for next in self.account_slots.iter_from((address, SlotIndex::ZERO.into()), Direction::Forward)? {
+                let ((slot_address, index), value) = next?;
+                if slot_address != address {
+                    break;
+                }
+                slots.push(Slot::new(index.into(), value.into_inner().into()));
+            }
+            changes.push(ExecutionAccountChanges::from_modified_values(account, slots));
+
+            // write nodes periodically to avoid holding the whole trie in memory
+            if changes.len() == STATE_TRIE_BACKFILL_ACCOUNTS || accounts.peek().is_none() {
+                let update = update_state_trie(self, root, &changes)?;
+                let nodes = update.nodes.into_iter().map(|(hash, node)| (hash.into(), BytesRocksdb(node).into()));
+                let mut batch = WriteBatch::default();
+                self.state_trie_nodes.prepare_batch_insertion(nodes, &mut batch)?;
+                write_in_batch_for_multiple_cfs_impl(&self.db, batch)?;
+                root = update.root;
+                changes.clear();
+            }
+        }
+
+        let mut batch = WriteBatch::default();
+        self.state_roots.prepare_batch_insertion([(block_number, root.into())], &mut batch)?;
+        write_in_batch_for_multiple_cfs_impl(&self.db, batch)?;
+
+        tracing::info!(%block_number, %root, "built state trie from existing accounts");
+        Ok(())
+    }
+
     pub fn save_block(&self, block: Block) -> Result<()> {
         let account_changes = block.compact_account_changes();
+        self.save_block_with_account_changes(block, account_changes)
+    }
+
+    /// Persists a block, applying the specified account changes at the block instead of the changes of its transactions.
+    pub fn save_block_with_account_changes(&self, block: Block, account_changes: Vec<ExecutionAccountChanges>) -> Result<()> {
+        let _current_state_lock = self.current_state_lock.lock_or_clear("rocks current state lock was poisoned");
 
         let mut txs_batch = vec![];
         let mut logs_batch = vec![];
+        let mut state_diffs_batch = vec![];
         for transaction in block.transactions.iter().cloned() {
             txs_batch.push((transaction.input.hash.into(), transaction.block_number.into()));
+            state_diffs_batch.push((transaction.input.hash.into(), StateDiff::from_changes(&transaction.execution.changes).into()));
             for log in transaction.logs {
                 logs_batch.push(((transaction.input.hash.into(), log.log_index.into()), transaction.block_number.into()));
             }
@@ -403,6 +1078,16 @@ impl RocksStorageState {
 
         self.transactions.prepare_batch_insertion(txs_batch, &mut batch)?;
         self.logs.prepare_batch_insertion(logs_batch, &mut batch)?;
+        self.state_diffs.prepare_batch_insertion(state_diffs_batch, &mut batch)?;
+        self.prepare_batch_with_logs_indexes(&block.transactions, &mut batch)?;
+        self.prepare_batch_with_transactions_indexes(&block.transactions, &mut batch)?;
+
+        // the batch is only written if the state root matches the one the block was mined with
+        let (previous_root, root) = self.prepare_batch_with_state_trie(&account_changes, block.number(), &mut batch)?;
+        check_block_state_root(&block, previous_root, root, || {
+            let Some(parent_number) = block.number().prev() else { return Ok(None) };
+            Ok(self.read_block(&BlockFilter::Number(parent_number))?.map(|parent| parent.header.state_root))
+        })?;
 
         let number = block.number();
         let block_hash = block.hash();
@@ -424,6 +1109,8 @@ impl RocksStorageState {
 
         let block_by_hash = (block_hash.into(), number.into());
         self.blocks_by_hash.prepare_batch_insertion([block_by_hash], &mut batch)?;
+        self.block_hashes_by_number
+            .prepare_batch_insertion([(number.into(), block_hash.into())], &mut batch)?;
 
         self.prepare_batch_with_execution_changes(account_changes, number, &mut batch)?;
 
@@ -431,6 +1118,49 @@ impl RocksStorageState {
         Ok(())
     }
 
+    /// Persists account changes made outside of a block, recording them at the specified block.
+    pub fn save_account_changes(&self, changes: Vec<ExecutionAccountChanges>, block_number: BlockNumber) -> Result<()> {
+        let _current_state_lock = self.current_state_lock.lock_or_clear("rocks current state lock was poisoned");
+
+        let mut batch = WriteBatch::default();
+        self.prepare_batch_with_state_trie(&changes, block_number, &mut batch)?;
+        self.prepare_batch_with_execution_changes(changes, block_number, &mut batch)?;
+        self.write_in_batch_for_multiple_cfs(batch)
+    }
+
+    /// Saves an account read from the fork as the current state if it does not exist, without history and state trie changes.
+    pub fn save_fork_account(&self, account: Account) -> Result<()> {
+        let _current_state_lock = self.current_state_lock.lock_or_clear("rocks current state lock was poisoned");
+
+        let address: AddressRocksdb = account.address.into();
+        if self.accounts.get(&address)?.is_some() {
+            return Ok(());
+        }
+
+        let mut batch = WriteBatch::default();
+        if let Some(ref bytecode) = account.bytecode {
+            self.bytecodes
+                .prepare_batch_insertion([(account.code_hash.into(), bytecode.clone().into())], &mut batch)?;
+        }
+        let (address, account) = <(AddressRocksdb, AccountRocksdb)>::from(account);
+        self.accounts.prepare_batch_insertion([(address, account.into())], &mut batch)?;
+        self.write_in_batch_for_multiple_cfs(batch)
+    }
+
+    /// Saves a slot read from the fork as the current state if it does not exist, without history and state trie changes.
+    pub fn save_fork_slot(&self, address: &Address, slot: Slot) -> Result<()> {
+        let _current_state_lock = self.current_state_lock.lock_or_clear("rocks current state lock was poisoned");
+
+        let key = ((*address).into(), slot.index.into());
+        if self.account_slots.get(&key)?.is_some() {
+            return Ok(());
+        }
+
+        let mut batch = WriteBatch::default();
+        self.account_slots.prepare_batch_insertion([(key, slot.value.into())], &mut batch)?;
+        self.write_in_batch_for_multiple_cfs(batch)
+    }
+
     /// Write to DB in a batch
     pub fn write_in_batch_for_multiple_cfs(&self, batch: WriteBatch) -> Result<()> {
         write_in_batch_for_multiple_cfs_impl(&self.db, batch)
@@ -450,12 +1180,12 @@ impl RocksStorageState {
 
     #[cfg(test)]
     pub fn read_all_accounts(&self) -> Result<Vec<AccountRocksdb>> {
-        self.accounts.iter_start().map(|result| Ok(result?.1.into_inner())).collect()
+        self.accounts.iter_start().map(|result| Ok(result?.1.into_latest().0)).collect()
     }
 
     #[cfg(test)]
     pub fn read_all_historical_accounts(&self) -> Result<Vec<AccountRocksdb>> {
-        self.accounts_history.iter_start().map(|result| Ok(result?.1.into_inner())).collect()
+        self.accounts_history.iter_start().map(|result| Ok(result?.1.into_latest().0)).collect()
     }
 
     /// Clears in-memory state.
@@ -467,7 +1197,20 @@ impl RocksStorageState {
         self.transactions.clear().context("when clearing transactions")?;
         self.blocks_by_hash.clear().context("when clearing blocks_by_hash")?;
         self.blocks_by_number.clear().context("when clearing blocks_by_number")?;
+        self.block_hashes_by_number.clear().context("when clearing block_hashes_by_number")?;
         self.logs.clear().context("when clearing logs")?;
+        self.logs_by_address.clear().context("when clearing logs_by_address")?;
+        self.logs_by_topic0.clear().context("when clearing logs_by_topic0")?;
+        self.transactions_by_address.clear().context("when clearing transactions_by_address")?;
+        self.transactions_by_sender_nonce
+            .clear()
+            .context("when clearing transactions_by_sender_nonce")?;
+        self.contract_creations.clear().context("when clearing contract_creations")?;
+        self.state_diffs.clear().context("when clearing state_diffs")?;
+        self.state_trie_nodes.clear().context("when clearing state_trie_nodes")?;
+        self.state_roots.clear().context("when clearing state_roots")?;
+        self.bytecodes.clear().context("when clearing bytecodes")?;
+        self.indexes_backfill.clear().context("when clearing indexes_backfill")?;
         Ok(())
     }
 }
@@ -533,8 +1276,17 @@ impl RocksStorageState {
         self.accounts_history.export_metrics();
         self.blocks_by_hash.export_metrics();
         self.blocks_by_number.export_metrics();
+        self.bytecodes.export_metrics();
+        self.contract_creations.export_metrics();
         self.logs.export_metrics();
+        self.logs_by_address.export_metrics();
+        self.logs_by_topic0.export_metrics();
+        self.state_diffs.export_metrics();
+        self.state_roots.export_metrics();
+        self.state_trie_nodes.export_metrics();
         self.transactions.export_metrics();
+        self.transactions_by_address.export_metrics();
+        self.transactions_by_sender_nonce.export_metrics();
         Ok(())
     }
 
@@ -590,6 +1342,13 @@ impl Drop for RocksStorageState {
     }
 }
 
+impl TrieNodeSource for RocksStorageState {
+    fn read_trie_node(&self, hash: &Hash) -> Result<Option<Vec<u8>>> {
+        let node = self.state_trie_nodes.get(&(*hash).into())?;
+        Ok(node.map(|node| node.into_inner().0))
+    }
+}
+
 impl fmt::Debug for RocksStorageState {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("RocksStorageState").field("db_path", &self.db_path).finish()
@@ -606,8 +1365,12 @@ mod tests {
     use tempfile::tempdir;
 
     use super::*;
+    use crate::eth::primitives::logs_bloom::LogsBloom;
     use crate::eth::primitives::BlockHeader;
     use crate::eth::primitives::ExecutionValueChange;
+    use crate::eth::primitives::LogFilterInput;
+    use crate::eth::primitives::LogFilterInputTopic;
+    use crate::eth::primitives::LogTopic;
     use crate::eth::primitives::SlotValue;
 
     #[test]
@@ -648,7 +1411,7 @@ mod tests {
 
         let state = RocksStorageState::new(test_dir.path().display().to_string(), Duration::ZERO).unwrap();
 
-        assert_eq!(state.read_logs(&LogFilter::default()).unwrap(), vec![]);
+        assert_eq!(state.read_logs(&LogFilter::default(), usize::MAX).unwrap(), vec![]);
 
         // 100 blocks with 1 transaction, with 2 logs, total: 200 logs
         for number in 0..100 {
@@ -672,7 +1435,290 @@ mod tests {
             ..Default::default()
         };
 
-        assert_eq!(state.read_logs(&filter).unwrap().len(), 200);
+        assert_eq!(state.read_logs(&filter, usize::MAX).unwrap().len(), 200);
+    }
+
+    #[test]
+    fn read_logs_using_indexes_and_limit() {
+        let test_dir = tempdir().unwrap();
+        let state = RocksStorageState::new(test_dir.path().display().to_string(), Duration::ZERO).unwrap();
+
+        let address: Address = Faker.fake();
+        let topic: LogTopic = Faker.fake();
+
+        // 10 blocks with 1 transaction, with 2 logs, but only logs of even blocks are emitted by the filtered address and topic
+        for number in 0..10u64 {
+            let mut logs: Vec<LogMined> = vec![Faker.fake(), Faker.fake()];
+            if number % 2 == 0 {
+                logs[0].log.address = address;
+                logs[0].log.topic0 = Some(topic);
+            }
+            for (index, log) in logs.iter_mut().enumerate() {
+                log.block_number = number.into();
+                log.log_index = Index::new(index as u64);
+            }
+
+            let mut bloom = LogsBloom::default();
+            logs.iter().for_each(|log| bloom.accrue_log(&log.log));
+
+            let block = Block {
+                header: BlockHeader {
+                    number: number.into(),
+                    bloom,
+                    ..Faker.fake()
+                },
+                transactions: vec![TransactionMined {
+                    block_number: number.into(),
+                    logs,
+                    ..Faker.fake()
+                }],
+            };
+            state.save_block(block).unwrap();
+        }
+
+        let by_address = LogFilter {
+            addresses: vec![address],
+            ..Default::default()
+        };
+        let by_topic = LogFilter {
+            original_input: LogFilterInput {
+                topics: vec![LogFilterInputTopic(vec![Some(topic)])],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let by_other_address = LogFilter {
+            addresses: vec![Faker.fake()],
+            ..Default::default()
+        };
+
+        assert_eq!(state.read_logs(&by_address, usize::MAX).unwrap().len(), 5);
+        assert_eq!(state.read_logs(&by_topic, usize::MAX).unwrap().len(), 5);
+        assert_eq!(state.read_logs(&by_other_address, usize::MAX).unwrap().len(), 0);
+
+        // range
+        let by_address_and_topic_in_range = LogFilter {
+            from_block: 3.into(),
+            to_block: Some(6.into()),
+            addresses: vec![address],
+            ..by_topic.clone()
+        };
+        let logs = state.read_logs(&by_address_and_topic_in_range, usize::MAX).unwrap();
+        assert_eq!(
+            logs.iter().map(|log| log.block_number).collect::<Vec<_>>(),
+            vec![BlockNumber::from(4), BlockNumber::from(6)]
+        );
+
+        // limit stops after the block that exceeds it
+        let logs = state.read_logs(&by_address, 2).unwrap();
+        assert_eq!(
+            logs.iter().map(|log| log.block_number).collect::<Vec<_>>(),
+            vec![BlockNumber::from(0), BlockNumber::from(2), BlockNumber::from(4)]
+        );
+
+        // indexes are rebuilt from existing blocks, and blocks are searched without them until they are complete
+        state.logs_by_address.clear().unwrap();
+        state.logs_by_topic0.clear().unwrap();
+        state.prepare_indexes_backfill().unwrap();
+        assert!(state.has_indexes_backfill());
+        assert_eq!(state.read_logs(&by_address, usize::MAX).unwrap().len(), 5);
+        state.backfill_indexes().unwrap();
+        assert!(not(state.has_indexes_backfill()));
+        assert_eq!(state.read_logs(&by_address, usize::MAX).unwrap().len(), 5);
+        assert_eq!(state.read_logs(&by_topic, usize::MAX).unwrap().len(), 5);
+
+        // interrupted backfill resumes from the saved progress
+        state.logs_by_address.clear().unwrap();
+        state.logs_by_topic0.clear().unwrap();
+        state.prepare_indexes_backfill().unwrap();
+        let mut batch = WriteBatch::default();
+        state
+            .indexes_backfill
+            .prepare_batch_insertion([(LOGS_INDEXES.to_owned(), BlockNumber::from(5).into())], &mut batch)
+            .unwrap();
+        state.db.write(batch).unwrap();
+        state.backfill_indexes().unwrap();
+        let logs = state.read_logs(&by_address, usize::MAX).unwrap();
+        assert_eq!(
+            logs.iter().map(|log| log.block_number).collect::<Vec<_>>(),
+            vec![BlockNumber::from(6), BlockNumber::from(8)]
+        );
+        assert!(state.indexes_backfill.get(&LOGS_INDEXES.to_owned()).unwrap().is_none());
+    }
+
+    #[test]
+    fn read_transactions_using_indexes_and_limit() {
+        let test_dir = tempdir().unwrap();
+        let state = RocksStorageState::new(test_dir.path().display().to_string(), Duration::ZERO).unwrap();
+
+        let address: Address = Faker.fake();
+
+        // 5 blocks with 2 transactions, only the first transaction of each block is sent by the searched address
+        let mut hashes = vec![];
+        for number in 0..5u64 {
+            let transactions = (0..2u64)
+                .map(|index| {
+                    let mut transaction: TransactionMined = Faker.fake();
+                    transaction.block_number = number.into();
+                    transaction.transaction_index = Index::new(index);
+                    if index == 0 {
+                        transaction.input.from = address;
+                        transaction.input.nonce = number.into();
+                        hashes.push(transaction.input.hash);
+                    }
+                    transaction
+                })
+                .collect();
+            let block = Block {
+                header: BlockHeader {
+                    number: number.into(),
+                    ..Faker.fake()
+                },
+                transactions,
+            };
+            state.save_block(block).unwrap();
+        }
+
+        let tx_hashes = |transactions: Vec<TransactionMined>| transactions.into_iter().map(|tx| tx.input.hash).collect::<Vec<_>>();
+
+        // searches in both directions
+        let before = state.read_transactions_by_address(&address, BlockSearch::Before(4.into()), usize::MAX).unwrap();
+        assert_eq!(tx_hashes(before), vec![hashes[3], hashes[2], hashes[1], hashes[0]]);
+        let after = state.read_transactions_by_address(&address, BlockSearch::After(1.into()), 2).unwrap();
+        assert_eq!(tx_hashes(after), vec![hashes[2], hashes[3]]);
+        let other = state
+            .read_transactions_by_address(&Faker.fake(), BlockSearch::After(0.into()), usize::MAX)
+            .unwrap();
+        assert!(other.is_empty());
+
+        // sender and nonce
+        let tx = state.read_transaction_by_sender_and_nonce(&address, 3.into()).unwrap().unwrap();
+        assert_eq!(tx.input.hash, hashes[3]);
+        assert!(state.read_transaction_by_sender_and_nonce(&address, 5.into()).unwrap().is_none());
+
+        // indexes are rebuilt from existing blocks
+        state.transactions_by_address.clear().unwrap();
+        state.transactions_by_sender_nonce.clear().unwrap();
+        state.prepare_indexes_backfill().unwrap();
+        assert!(state.read_transaction_by_sender_and_nonce(&address, 3.into()).is_err());
+        state.backfill_indexes().unwrap();
+        assert!(state.read_transaction_by_sender_and_nonce(&address, 3.into()).unwrap().is_some());
+        let before = state.read_transactions_by_address(&address, BlockSearch::Before(5.into()), 1).unwrap();
+        assert_eq!(tx_hashes(before), vec![hashes[4]]);
+    }
+
+    #[test]
+    fn read_state_diff_of_saved_transaction() {
+        let test_dir = tempdir().unwrap();
+        let state = RocksStorageState::new(test_dir.path().display().to_string(), Duration::ZERO).unwrap();
+
+        let mut transaction: TransactionMined = Faker.fake();
+        transaction.block_number = 1.into();
+        let tx_hash = transaction.input.hash;
+        let expected = StateDiff::from_changes(&transaction.execution.changes);
+
+        let block = Block {
+            header: BlockHeader {
+                number: 1.into(),
+                ..Faker.fake()
+            },
+            transactions: vec![transaction],
+        };
+        state.save_block(block).unwrap();
+
+        assert_eq!(state.read_state_diff(&tx_hash).unwrap(), Some(expected));
+        assert!(state.read_state_diff(&Faker.fake()).unwrap().is_none());
+    }
+
+    #[test]
+    fn read_block_hash_of_saved_block() {
+        let test_dir = tempdir().unwrap();
+        let state = RocksStorageState::new(test_dir.path().display().to_string(), Duration::ZERO).unwrap();
+
+        let block = Block {
+            header: BlockHeader {
+                number: 1.into(),
+                ..Faker.fake()
+            },
+            transactions: vec![],
+        };
+        let hash = block.hash();
+        state.save_block(block).unwrap();
+        assert_eq!(state.read_block_hash(1.into()).unwrap(), Some(hash));
+
+        // blocks saved before block hashes were indexed
+        state.block_hashes_by_number.clear().unwrap();
+        assert_eq!(state.read_block_hash(1.into()).unwrap(), Some(hash));
+        assert_eq!(state.read_block_hash(2.into()).unwrap(), None);
+    }
+
+    #[test]
+    fn refuse_block_with_state_root_different_from_the_calculated_one() {
+        let test_dir = tempdir().unwrap();
+        let state = RocksStorageState::new(test_dir.path().display().to_string(), Duration::ZERO).unwrap();
+
+        let block_with_root = |number: u64, state_root: Hash| Block {
+            header: BlockHeader {
+                number: number.into(),
+                state_root,
+                ..Faker.fake()
+            },
+            transactions: vec![],
+        };
+        state.save_block(block_with_root(0, Hash::EMPTY_TRIE)).unwrap();
+
+        // nothing is written when the state root differs
+        assert!(state.save_block(block_with_root(1, Faker.fake())).is_err());
+        assert_eq!(state.read_block_hash(1.into()).unwrap(), None);
+
+        state.save_block(block_with_root(1, Hash::EMPTY_TRIE)).unwrap();
+        assert!(state.read_block_hash(1.into()).unwrap().is_some());
+    }
+
+    #[test]
+    fn reset_at_discards_blocks_and_restores_state() {
+        let test_dir = tempdir().unwrap();
+        let state = RocksStorageState::new(test_dir.path().display().to_string(), Duration::ZERO).unwrap();
+
+        let address: Address = Faker.fake();
+        let changes = |value: u64| {
+            let account = Account {
+                balance: value.into(),
+                ..Account::new_empty(address)
+            };
+            vec![ExecutionAccountChanges::from_modified_values(
+                account,
+                vec![Slot::new(SlotIndex::ZERO, value.into())],
+            )]
+        };
+        let block = |number: u64, transactions: Vec<TransactionMined>| Block {
+            header: BlockHeader {
+                number: number.into(),
+                ..Faker.fake()
+            },
+            transactions,
+        };
+
+        let mut tx: TransactionMined = Faker.fake();
+        tx.block_number = 2.into();
+        let tx_hash = tx.input.hash;
+        state.save_block_with_account_changes(block(1, vec![]), changes(10)).unwrap();
+        state.save_block_with_account_changes(block(2, vec![tx]), changes(20)).unwrap();
+
+        state.reset_at(1.into()).unwrap();
+
+        // discarded block
+        assert!(state.read_block(&BlockFilter::Number(2.into())).unwrap().is_none());
+        assert_eq!(state.read_block_hash(2.into()).unwrap(), None);
+        assert!(state.read_transaction(&tx_hash).unwrap().is_none());
+        assert!(state.read_state_diff(&tx_hash).unwrap().is_none());
+
+        // restored state
+        let account = state.read_account(&address, &StoragePointInTime::Mined).unwrap().unwrap();
+        assert_eq!(account.balance, 10u64.into());
+        let slot = state.read_slot(&address, &SlotIndex::ZERO, &StoragePointInTime::Mined).unwrap().unwrap();
+        assert_eq!(slot.value, 10u64.into());
+        assert!(state.read_block(&BlockFilter::Number(1.into())).unwrap().is_some());
     }
 
     #[test]
@@ -721,4 +1767,36 @@ mod tests {
         let history = state.read_all_historical_accounts().unwrap();
         assert_eq!(history.len(), 3);
     }
+
+    #[test]
+    fn test_bytecode_is_stored_by_code_hash() {
+        let test_dir = tempdir().unwrap();
+        let state = RocksStorageState::new(test_dir.path().display().to_string(), Duration::ZERO).unwrap();
+
+        let address: Address = Faker.fake();
+        let bytecode = Bytes(vec![0x60, 0x00, 0x60, 0x00, 0xf3]);
+        let code_hash = CodeHash::from_bytecode(Some(bytecode.clone()));
+        let change = ExecutionAccountChanges {
+            new_account: true,
+            address,
+            nonce: ExecutionValueChange::from_modified(Faker.fake()),
+            balance: ExecutionValueChange::from_modified(Faker.fake()),
+            bytecode: ExecutionValueChange::from_modified(Some(bytecode.clone())),
+            code_hash,
+            slots: HashMap::new(),
+        };
+
+        let mut batch = WriteBatch::default();
+        state.prepare_batch_with_execution_changes([change], 1.into(), &mut batch).unwrap();
+        state.write_in_batch_for_multiple_cfs(batch).unwrap();
+
+        let accounts = state.read_all_accounts().unwrap();
+        assert_eq!(accounts[0].code_hash, code_hash.into());
+        assert_eq!(state.read_bytecode(&code_hash).unwrap(), Some(bytecode.clone()));
+
+        let account = state.read_account(&address, &StoragePointInTime::Mined).unwrap().unwrap();
+        assert_eq!(account.bytecode, Some(bytecode.clone()));
+        let account = state.read_account(&address, &StoragePointInTime::MinedPast(1.into())).unwrap().unwrap();
+        assert_eq!(account.bytecode, Some(bytecode));
+    }
 }
@@ -1,44 +1,65 @@
 use std::fmt::Debug;
 
-use revm::primitives::KECCAK_EMPTY;
-
 use super::address::AddressRocksdb;
 use super::bytes::BytesRocksdb;
+use super::code_hash::CodeHashRocksdb;
 use super::nonce::NonceRocksdb;
 use super::wei::WeiRocksdb;
 use crate::eth::primitives::Account;
 use crate::eth::primitives::Address;
+use crate::eth::primitives::Bytes;
+use crate::eth::primitives::CodeHash;
 use crate::ext::OptionExt;
 
+/// Account with its bytecode referenced by hash.
+///
+/// The bytecode itself is stored only once in the `bytecodes` column family.
 #[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
 pub struct AccountRocksdb {
+    pub balance: WeiRocksdb,
+    pub nonce: NonceRocksdb,
+    pub code_hash: CodeHashRocksdb,
+}
+
+/// Layout used before bytecodes were moved to their own column family.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
+pub struct AccountRocksdbV1 {
     pub balance: WeiRocksdb,
     pub nonce: NonceRocksdb,
     pub bytecode: Option<BytesRocksdb>,
 }
 
 impl AccountRocksdb {
-    pub fn to_account(&self, address: &Address) -> Account {
+    /// Checks if the account references a bytecode.
+    pub fn has_bytecode(&self) -> bool {
+        CodeHash::from(self.code_hash) != CodeHash::default()
+    }
+
+    /// Converts to an account using the bytecode retrieved by its hash.
+    pub fn to_account(&self, address: &Address, bytecode: Option<Bytes>) -> Account {
         Account {
             address: *address,
             nonce: self.nonce.clone().into(),
             balance: self.balance.clone().into(),
-            bytecode: self.bytecode.clone().map_into(),
-            code_hash: KECCAK_EMPTY.into(),
+            bytecode,
+            code_hash: self.code_hash.into(),
+        }
+    }
+}
+
+impl From<AccountRocksdbV1> for AccountRocksdb {
+    fn from(value: AccountRocksdbV1) -> Self {
+        AccountRocksdb {
+            balance: value.balance,
+            nonce: value.nonce,
+            code_hash: CodeHash::from_bytecode(value.bytecode.map_into()).into(),
         }
     }
 }
 
 impl From<Account> for (AddressRocksdb, AccountRocksdb) {
     fn from(value: Account) -> Self {
-        (
-            value.address.into(),
-            AccountRocksdb {
-                balance: value.balance.into(),
-                nonce: value.nonce.into(),
-                bytecode: value.bytecode.map_into(),
-            },
-        )
+        (value.address.into(), value.into())
     }
 }
 
@@ -47,7 +68,7 @@ impl From<Account> for AccountRocksdb {
         AccountRocksdb {
             balance: value.balance.into(),
             nonce: value.nonce.into(),
-            bytecode: value.bytecode.map_into(),
+            code_hash: CodeHash::from_bytecode(value.bytecode).into(),
         }
     }
 }
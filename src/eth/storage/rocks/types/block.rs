@@ -2,9 +2,13 @@ use std::fmt::Debug;
 
 use super::address::AddressRocksdb;
 use super::block_header::BlockHeaderRocksdb;
+use super::block_header::BlockHeaderRocksdbV1;
 use super::block_number::BlockNumberRocksdb;
 use super::hash::HashRocksdb;
 use super::transaction_mined::TransactionMinedRocksdb;
+use super::transaction_mined::TransactionMinedRocksdbV1;
+use super::transaction_mined::TransactionMinedRocksdbV2;
+use super::transaction_mined::TransactionMinedRocksdbV3;
 use crate::eth::primitives::Address;
 use crate::eth::primitives::Block;
 use crate::eth::primitives::BlockHeader;
@@ -18,6 +22,27 @@ pub struct BlockRocksdb {
     pub transactions: Vec<TransactionMinedRocksdb>,
 }
 
+/// Layout used before internal transactions were added to transactions.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
+pub struct BlockRocksdbV3 {
+    pub header: BlockHeaderRocksdb,
+    pub transactions: Vec<TransactionMinedRocksdbV3>,
+}
+
+/// Layout used before receipt fields were added to transactions.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
+pub struct BlockRocksdbV2 {
+    pub header: BlockHeaderRocksdb,
+    pub transactions: Vec<TransactionMinedRocksdbV2>,
+}
+
+/// Layout used before fee market fields were added.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
+pub struct BlockRocksdbV1 {
+    pub header: BlockHeaderRocksdbV1,
+    pub transactions: Vec<TransactionMinedRocksdbV1>,
+}
+
 impl From<Block> for BlockRocksdb {
     fn from(item: Block) -> Self {
         BlockRocksdb {
@@ -40,6 +65,7 @@ impl From<Block> for BlockRocksdb {
                 state_root: HashRocksdb::from(item.header.state_root),
                 total_difficulty: item.header.total_difficulty.into(),
                 nonce: item.header.nonce.into(),
+                base_fee_per_gas: item.header.base_fee_per_gas.into(),
             },
             transactions: item.transactions.into_iter().map(TransactionMinedRocksdb::from).collect(),
         }
@@ -68,8 +94,45 @@ impl From<BlockRocksdb> for Block {
                 state_root: Hash::from(item.header.state_root),
                 total_difficulty: item.header.total_difficulty.into(),
                 nonce: item.header.nonce.into(),
+                base_fee_per_gas: item.header.base_fee_per_gas.into(),
             },
             transactions: item.transactions.into_iter().map(TransactionMined::from).collect(),
         }
     }
 }
+
+impl From<BlockRocksdbV3> for BlockRocksdb {
+    fn from(item: BlockRocksdbV3) -> Self {
+        Self {
+            header: item.header,
+            transactions: item.transactions.into_iter().map(TransactionMinedRocksdb::from).collect(),
+        }
+    }
+}
+
+impl From<BlockRocksdbV2> for BlockRocksdb {
+    fn from(item: BlockRocksdbV2) -> Self {
+        let block = BlockRocksdb {
+            header: item.header,
+            transactions: item
+                .transactions
+                .into_iter()
+                .map(|transaction| TransactionMinedRocksdbV3::from(transaction).into())
+                .collect(),
+        };
+
+        // receipt fields can be derived from the block, so they are calculated instead of being left empty
+        let mut block = Block::from(block);
+        block.fill_receipts();
+        block.into()
+    }
+}
+
+impl From<BlockRocksdbV1> for BlockRocksdbV2 {
+    fn from(item: BlockRocksdbV1) -> Self {
+        Self {
+            header: item.header.into(),
+            transactions: item.transactions.into_iter().map(TransactionMinedRocksdbV2::from).collect(),
+        }
+    }
+}
@@ -10,6 +10,7 @@ use super::logs_bloom::LogsBloomRocksdb;
 use super::miner_nonce::MinerNonceRocksdb;
 use super::size::SizeRocksdb;
 use super::unix_time::UnixTimeRocksdb;
+use super::wei::WeiRocksdb;
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
 pub struct BlockHeaderRocksdb {
@@ -31,4 +32,54 @@ pub struct BlockHeaderRocksdb {
     pub state_root: HashRocksdb,
     pub total_difficulty: DifficultyRocksdb,
     pub nonce: MinerNonceRocksdb,
+    pub base_fee_per_gas: WeiRocksdb,
+}
+
+/// Layout used before fee market fields were added.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
+pub struct BlockHeaderRocksdbV1 {
+    pub number: BlockNumberRocksdb,
+    pub hash: HashRocksdb,
+    pub transactions_root: HashRocksdb,
+    pub gas_used: GasRocksdb,
+    pub gas_limit: GasRocksdb,
+    pub bloom: LogsBloomRocksdb,
+    pub timestamp: UnixTimeRocksdb,
+    pub parent_hash: HashRocksdb,
+    pub author: AddressRocksdb,
+    pub extra_data: BytesRocksdb,
+    pub miner: AddressRocksdb,
+    pub difficulty: DifficultyRocksdb,
+    pub receipts_root: HashRocksdb,
+    pub uncle_hash: HashRocksdb,
+    pub size: SizeRocksdb,
+    pub state_root: HashRocksdb,
+    pub total_difficulty: DifficultyRocksdb,
+    pub nonce: MinerNonceRocksdb,
+}
+
+impl From<BlockHeaderRocksdbV1> for BlockHeaderRocksdb {
+    fn from(item: BlockHeaderRocksdbV1) -> Self {
+        Self {
+            number: item.number,
+            hash: item.hash,
+            transactions_root: item.transactions_root,
+            gas_used: item.gas_used,
+            gas_limit: item.gas_limit,
+            bloom: item.bloom,
+            timestamp: item.timestamp,
+            parent_hash: item.parent_hash,
+            author: item.author,
+            extra_data: item.extra_data,
+            miner: item.miner,
+            difficulty: item.difficulty,
+            receipts_root: item.receipts_root,
+            uncle_hash: item.uncle_hash,
+            size: item.size,
+            state_root: item.state_root,
+            total_difficulty: item.total_difficulty,
+            nonce: item.nonce,
+            base_fee_per_gas: WeiRocksdb::ZERO,
+        }
+    }
 }
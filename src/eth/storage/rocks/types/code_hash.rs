@@ -0,0 +1,24 @@
+use std::fmt::Debug;
+
+use crate::eth::primitives::CodeHash;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, fake::Dummy)]
+pub struct CodeHashRocksdb([u8; 32]);
+
+impl Default for CodeHashRocksdb {
+    fn default() -> Self {
+        CodeHash::default().into()
+    }
+}
+
+impl From<CodeHash> for CodeHashRocksdb {
+    fn from(item: CodeHash) -> Self {
+        CodeHashRocksdb(item.0.into())
+    }
+}
+
+impl From<CodeHashRocksdb> for CodeHash {
+    fn from(item: CodeHashRocksdb) -> Self {
+        item.0.into()
+    }
+}
@@ -4,9 +4,11 @@ use super::address::AddressRocksdb;
 use super::bytes::BytesRocksdb;
 use super::execution_result::ExecutionResultRocksdb;
 use super::gas::GasRocksdb;
+use super::internal_transaction::InternalTransactionRocksdb;
 use super::log::LogRocksdb;
 use super::unix_time::UnixTimeRocksdb;
 use crate::eth::primitives::EvmExecution;
+use crate::eth::primitives::InternalTransaction;
 use crate::eth::primitives::Log;
 use crate::ext::OptionExt;
 
@@ -19,6 +21,19 @@ pub struct ExecutionRocksdb {
     pub logs: Vec<LogRocksdb>,
     pub gas: GasRocksdb,
     pub deployed_contract_address: Option<AddressRocksdb>,
+    pub internal_transactions: Vec<InternalTransactionRocksdb>,
+}
+
+/// Layout used before internal transactions were added.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
+pub struct ExecutionRocksdbV1 {
+    pub block_timestamp: UnixTimeRocksdb,
+    pub execution_costs_applied: bool,
+    pub result: ExecutionResultRocksdb,
+    pub output: BytesRocksdb,
+    pub logs: Vec<LogRocksdb>,
+    pub gas: GasRocksdb,
+    pub deployed_contract_address: Option<AddressRocksdb>,
 }
 
 impl From<EvmExecution> for ExecutionRocksdb {
@@ -31,6 +46,7 @@ impl From<EvmExecution> for ExecutionRocksdb {
             logs: item.logs.into_iter().map(LogRocksdb::from).collect(),
             gas: GasRocksdb::from(item.gas),
             deployed_contract_address: item.deployed_contract_address.map_into(),
+            internal_transactions: item.internal_transactions.into_iter().map(InternalTransactionRocksdb::from).collect(),
         }
     }
 }
@@ -46,6 +62,22 @@ impl From<ExecutionRocksdb> for EvmExecution {
             gas: item.gas.into(),
             changes: HashMap::default(),
             deployed_contract_address: item.deployed_contract_address.map_into(),
+            internal_transactions: item.internal_transactions.into_iter().map(InternalTransaction::from).collect(),
+        }
+    }
+}
+
+impl From<ExecutionRocksdbV1> for ExecutionRocksdb {
+    fn from(item: ExecutionRocksdbV1) -> Self {
+        Self {
+            block_timestamp: item.block_timestamp,
+            execution_costs_applied: item.execution_costs_applied,
+            result: item.result,
+            output: item.output,
+            logs: item.logs,
+            gas: item.gas,
+            deployed_contract_address: item.deployed_contract_address,
+            internal_transactions: Vec::new(),
         }
     }
 }
@@ -12,7 +12,7 @@ impl From<ExecutionResult> for ExecutionResultRocksdb {
         match item {
             ExecutionResult::Success => ExecutionResultRocksdb::Success,
             ExecutionResult::Reverted => ExecutionResultRocksdb::Reverted,
-            ExecutionResult::Halted { reason } => ExecutionResultRocksdb::Halted { reason },
+            ExecutionResult::Halted { reason, .. } => ExecutionResultRocksdb::Halted { reason },
         }
     }
 }
@@ -22,7 +22,7 @@ impl From<ExecutionResultRocksdb> for ExecutionResult {
         match item {
             ExecutionResultRocksdb::Success => ExecutionResult::Success,
             ExecutionResultRocksdb::Reverted => ExecutionResult::Reverted,
-            ExecutionResultRocksdb::Halted { reason } => ExecutionResult::Halted { reason },
+            ExecutionResultRocksdb::Halted { reason } => ExecutionResult::Halted { reason, out_of_gas: false },
         }
     }
 }
@@ -0,0 +1,80 @@
+use super::address::AddressRocksdb;
+use super::bytes::BytesRocksdb;
+use super::wei::WeiRocksdb;
+use crate::eth::primitives::CallKind;
+use crate::eth::primitives::InternalTransaction;
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
+pub struct InternalTransactionRocksdb {
+    pub kind: CallKindRocksdb,
+    pub from: AddressRocksdb,
+    pub to: AddressRocksdb,
+    pub value: WeiRocksdb,
+    pub selector: BytesRocksdb,
+    pub depth: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
+pub enum CallKindRocksdb {
+    Call,
+    CallCode,
+    DelegateCall,
+    StaticCall,
+    Create,
+    Create2,
+    SelfDestruct,
+}
+
+impl From<InternalTransaction> for InternalTransactionRocksdb {
+    fn from(item: InternalTransaction) -> Self {
+        Self {
+            kind: item.kind.into(),
+            from: item.from.into(),
+            to: item.to.into(),
+            value: item.value.into(),
+            selector: item.selector.into(),
+            depth: item.depth as u64,
+        }
+    }
+}
+
+impl From<InternalTransactionRocksdb> for InternalTransaction {
+    fn from(item: InternalTransactionRocksdb) -> Self {
+        Self {
+            kind: item.kind.into(),
+            from: item.from.into(),
+            to: item.to.into(),
+            value: item.value.into(),
+            selector: item.selector.into(),
+            depth: item.depth as usize,
+        }
+    }
+}
+
+impl From<CallKind> for CallKindRocksdb {
+    fn from(item: CallKind) -> Self {
+        match item {
+            CallKind::Call => Self::Call,
+            CallKind::CallCode => Self::CallCode,
+            CallKind::DelegateCall => Self::DelegateCall,
+            CallKind::StaticCall => Self::StaticCall,
+            CallKind::Create => Self::Create,
+            CallKind::Create2 => Self::Create2,
+            CallKind::SelfDestruct => Self::SelfDestruct,
+        }
+    }
+}
+
+impl From<CallKindRocksdb> for CallKind {
+    fn from(item: CallKindRocksdb) -> Self {
+        match item {
+            CallKindRocksdb::Call => Self::Call,
+            CallKindRocksdb::CallCode => Self::CallCode,
+            CallKindRocksdb::DelegateCall => Self::DelegateCall,
+            CallKindRocksdb::StaticCall => Self::StaticCall,
+            CallKindRocksdb::Create => Self::Create,
+            CallKindRocksdb::Create2 => Self::Create2,
+            CallKindRocksdb::SelfDestruct => Self::SelfDestruct,
+        }
+    }
+}
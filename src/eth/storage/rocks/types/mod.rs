@@ -5,12 +5,14 @@ mod block_header;
 mod block_number;
 mod bytes;
 mod chain_id;
+mod code_hash;
 mod difficulty;
 mod execution;
 mod execution_result;
 mod gas;
 mod hash;
 mod index;
+mod internal_transaction;
 mod log;
 mod log_mined;
 mod logs_bloom;
@@ -18,29 +20,38 @@ mod miner_nonce;
 mod nonce;
 mod size;
 mod slot;
+mod state_diff;
 mod transaction_input;
 mod transaction_mined;
 mod unix_time;
 mod wei;
 
 pub use account::AccountRocksdb;
+pub use account::AccountRocksdbV1;
 pub use address::AddressRocksdb;
 pub use block::BlockRocksdb;
+pub use block::BlockRocksdbV1;
+pub use block::BlockRocksdbV2;
+pub use block::BlockRocksdbV3;
 pub use block_number::BlockNumberRocksdb;
+pub use bytes::BytesRocksdb;
+pub use code_hash::CodeHashRocksdb;
 pub use hash::HashRocksdb;
 pub use index::IndexRocksdb;
+pub use nonce::NonceRocksdb;
 pub use slot::SlotIndexRocksdb;
 pub use slot::SlotValueRocksdb;
+pub use state_diff::StateDiffRocksdb;
 
 #[cfg(test)]
 mod tests {
     use block_header::BlockHeaderRocksdb;
-    use bytes::BytesRocksdb;
     use chain_id::ChainIdRocksdb;
     use difficulty::DifficultyRocksdb;
     use execution::ExecutionRocksdb;
     use execution_result::ExecutionResultRocksdb;
     use gas::GasRocksdb;
+    use internal_transaction::InternalTransactionRocksdb;
     use log_mined::LogMinedRockdb;
     use logs_bloom::LogsBloomRocksdb;
     use miner_nonce::MinerNonceRocksdb;
@@ -56,18 +67,21 @@ mod tests {
     use crate::gen_test_bincode;
 
     gen_test_bincode!(AccountRocksdb);
+    gen_test_bincode!(AccountRocksdbV1);
     gen_test_bincode!(AddressRocksdb);
     gen_test_bincode!(BlockHeaderRocksdb);
     gen_test_bincode!(BlockNumberRocksdb);
     gen_test_bincode!(BlockRocksdb);
     gen_test_bincode!(BytesRocksdb);
     gen_test_bincode!(ChainIdRocksdb);
+    gen_test_bincode!(CodeHashRocksdb);
     gen_test_bincode!(DifficultyRocksdb);
     gen_test_bincode!(ExecutionResultRocksdb);
     gen_test_bincode!(ExecutionRocksdb);
     gen_test_bincode!(GasRocksdb);
     gen_test_bincode!(HashRocksdb);
     gen_test_bincode!(IndexRocksdb);
+    gen_test_bincode!(InternalTransactionRocksdb);
     gen_test_bincode!(LogMinedRockdb);
     gen_test_bincode!(LogRocksdb);
     gen_test_bincode!(LogsBloomRocksdb);
@@ -76,6 +90,7 @@ mod tests {
     gen_test_bincode!(SizeRocksdb);
     gen_test_bincode!(SlotIndexRocksdb);
     gen_test_bincode!(SlotValueRocksdb);
+    gen_test_bincode!(StateDiffRocksdb);
     gen_test_bincode!(TransactionInputRocksdb);
     gen_test_bincode!(TransactionMinedRocksdb);
     gen_test_bincode!(UnixTimeRocksdb);
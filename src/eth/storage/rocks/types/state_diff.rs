@@ -0,0 +1,93 @@
+use super::address::AddressRocksdb;
+use super::bytes::BytesRocksdb;
+use super::nonce::NonceRocksdb;
+use super::slot::SlotIndexRocksdb;
+use super::slot::SlotValueRocksdb;
+use super::wei::WeiRocksdb;
+use crate::eth::primitives::AccountStateDiff;
+use crate::eth::primitives::StateDiff;
+use crate::eth::primitives::ValueDiff;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
+pub struct StateDiffRocksdb {
+    pub accounts: Vec<AccountStateDiffRocksdb>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
+pub struct AccountStateDiffRocksdb {
+    pub address: AddressRocksdb,
+    pub balance: Option<ValueDiffRocksdb<WeiRocksdb>>,
+    pub nonce: Option<ValueDiffRocksdb<NonceRocksdb>>,
+    pub bytecode: Option<ValueDiffRocksdb<BytesRocksdb>>,
+    pub slots: Vec<(SlotIndexRocksdb, ValueDiffRocksdb<SlotValueRocksdb>)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
+pub struct ValueDiffRocksdb<T> {
+    pub from: Option<T>,
+    pub to: T,
+}
+
+impl From<StateDiff> for StateDiffRocksdb {
+    fn from(item: StateDiff) -> Self {
+        Self {
+            accounts: item.accounts.into_iter().map(AccountStateDiffRocksdb::from).collect(),
+        }
+    }
+}
+
+impl From<StateDiffRocksdb> for StateDiff {
+    fn from(item: StateDiffRocksdb) -> Self {
+        Self {
+            accounts: item.accounts.into_iter().map(AccountStateDiff::from).collect(),
+        }
+    }
+}
+
+impl From<AccountStateDiff> for AccountStateDiffRocksdb {
+    fn from(item: AccountStateDiff) -> Self {
+        Self {
+            address: item.address.into(),
+            balance: item.balance.map(ValueDiffRocksdb::from),
+            nonce: item.nonce.map(ValueDiffRocksdb::from),
+            bytecode: item.bytecode.map(ValueDiffRocksdb::from),
+            slots: item.slots.into_iter().map(|(index, diff)| (index.into(), diff.into())).collect(),
+        }
+    }
+}
+
+impl From<AccountStateDiffRocksdb> for AccountStateDiff {
+    fn from(item: AccountStateDiffRocksdb) -> Self {
+        Self {
+            address: item.address.into(),
+            balance: item.balance.map(ValueDiff::from),
+            nonce: item.nonce.map(ValueDiff::from),
+            bytecode: item.bytecode.map(ValueDiff::from),
+            slots: item.slots.into_iter().map(|(index, diff)| (index.into(), diff.into())).collect(),
+        }
+    }
+}
+
+impl<T, R> From<ValueDiff<T>> for ValueDiffRocksdb<R>
+where
+    R: From<T>,
+{
+    fn from(item: ValueDiff<T>) -> Self {
+        Self {
+            from: item.from.map(R::from),
+            to: item.to.into(),
+        }
+    }
+}
+
+impl<T, R> From<ValueDiffRocksdb<R>> for ValueDiff<T>
+where
+    T: From<R>,
+{
+    fn from(item: ValueDiffRocksdb<R>) -> Self {
+        Self {
+            from: item.from.map(T::from),
+            to: item.to.into(),
+        }
+    }
+}
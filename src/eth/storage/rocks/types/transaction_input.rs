@@ -28,6 +28,27 @@ pub struct TransactionInputRocksdb {
     pub v: u64,
     pub r: [u64; 4],
     pub s: [u64; 4],
+    pub max_fee_per_gas: Option<WeiRocksdb>,
+    pub max_priority_fee_per_gas: Option<WeiRocksdb>,
+}
+
+/// Layout used before fee market fields were added.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
+pub struct TransactionInputRocksdbV1 {
+    pub tx_type: Option<u64>,
+    pub chain_id: Option<ChainIdRocksdb>,
+    pub hash: HashRocksdb,
+    pub nonce: NonceRocksdb,
+    pub signer: AddressRocksdb,
+    pub from: AddressRocksdb,
+    pub to: Option<AddressRocksdb>,
+    pub value: WeiRocksdb,
+    pub input: BytesRocksdb,
+    pub gas_limit: GasRocksdb,
+    pub gas_price: WeiRocksdb,
+    pub v: u64,
+    pub r: [u64; 4],
+    pub s: [u64; 4],
 }
 
 impl From<TransactionInput> for TransactionInputRocksdb {
@@ -47,6 +68,8 @@ impl From<TransactionInput> for TransactionInputRocksdb {
             v: item.v.as_u64(),
             r: item.r.0,
             s: item.s.0,
+            max_fee_per_gas: item.max_fee_per_gas.map(WeiRocksdb::from),
+            max_priority_fee_per_gas: item.max_priority_fee_per_gas.map(WeiRocksdb::from),
         }
     }
 }
@@ -68,6 +91,31 @@ impl From<TransactionInputRocksdb> for TransactionInput {
             r: U256(item.r),
             s: U256(item.s),
             tx_type: item.tx_type.map_into(),
+            max_fee_per_gas: item.max_fee_per_gas.map_into(),
+            max_priority_fee_per_gas: item.max_priority_fee_per_gas.map_into(),
+        }
+    }
+}
+
+impl From<TransactionInputRocksdbV1> for TransactionInputRocksdb {
+    fn from(item: TransactionInputRocksdbV1) -> Self {
+        Self {
+            tx_type: item.tx_type,
+            chain_id: item.chain_id,
+            hash: item.hash,
+            nonce: item.nonce,
+            signer: item.signer,
+            from: item.from,
+            to: item.to,
+            value: item.value,
+            input: item.input,
+            gas_limit: item.gas_limit,
+            gas_price: item.gas_price,
+            v: item.v,
+            r: item.r,
+            s: item.s,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
         }
     }
 }
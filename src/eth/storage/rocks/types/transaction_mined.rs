@@ -2,11 +2,17 @@ use std::fmt::Debug;
 
 use super::block_number::BlockNumberRocksdb;
 use super::execution::ExecutionRocksdb;
+use super::execution::ExecutionRocksdbV1;
+use super::gas::GasRocksdb;
 use super::hash::HashRocksdb;
 use super::index::IndexRocksdb;
 use super::log_mined::LogMinedRockdb;
+use super::logs_bloom::LogsBloomRocksdb;
 use super::transaction_input::TransactionInputRocksdb;
+use super::transaction_input::TransactionInputRocksdbV1;
+use super::wei::WeiRocksdb;
 use crate::eth::primitives::LogMined;
+use crate::eth::primitives::LogsBloom;
 use crate::eth::primitives::TransactionMined;
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
@@ -17,6 +23,45 @@ pub struct TransactionMinedRocksdb {
     pub transaction_index: IndexRocksdb,
     pub block_number: BlockNumberRocksdb,
     pub block_hash: HashRocksdb,
+    pub cumulative_gas_used: GasRocksdb,
+    pub effective_gas_price: WeiRocksdb,
+    pub logs_bloom: LogsBloomRocksdb,
+}
+
+/// Layout used before internal transactions were added.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
+pub struct TransactionMinedRocksdbV3 {
+    pub input: TransactionInputRocksdb,
+    pub execution: ExecutionRocksdbV1,
+    pub logs: Vec<LogMinedRockdb>,
+    pub transaction_index: IndexRocksdb,
+    pub block_number: BlockNumberRocksdb,
+    pub block_hash: HashRocksdb,
+    pub cumulative_gas_used: GasRocksdb,
+    pub effective_gas_price: WeiRocksdb,
+    pub logs_bloom: LogsBloomRocksdb,
+}
+
+/// Layout used before receipt fields were added.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
+pub struct TransactionMinedRocksdbV2 {
+    pub input: TransactionInputRocksdb,
+    pub execution: ExecutionRocksdbV1,
+    pub logs: Vec<LogMinedRockdb>,
+    pub transaction_index: IndexRocksdb,
+    pub block_number: BlockNumberRocksdb,
+    pub block_hash: HashRocksdb,
+}
+
+/// Layout used before fee market fields were added.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, fake::Dummy)]
+pub struct TransactionMinedRocksdbV1 {
+    pub input: TransactionInputRocksdbV1,
+    pub execution: ExecutionRocksdbV1,
+    pub logs: Vec<LogMinedRockdb>,
+    pub transaction_index: IndexRocksdb,
+    pub block_number: BlockNumberRocksdb,
+    pub block_hash: HashRocksdb,
 }
 
 impl From<TransactionMined> for TransactionMinedRocksdb {
@@ -28,6 +73,9 @@ impl From<TransactionMined> for TransactionMinedRocksdb {
             transaction_index: IndexRocksdb::from(item.transaction_index),
             block_number: BlockNumberRocksdb::from(item.block_number),
             block_hash: HashRocksdb::from(item.block_hash),
+            cumulative_gas_used: item.cumulative_gas_used.into(),
+            effective_gas_price: item.effective_gas_price.into(),
+            logs_bloom: item.logs_bloom.into(),
         }
     }
 }
@@ -41,6 +89,55 @@ impl From<TransactionMinedRocksdb> for TransactionMined {
             transaction_index: item.transaction_index.into(),
             block_number: item.block_number.into(),
             block_hash: item.block_hash.into(),
+            cumulative_gas_used: item.cumulative_gas_used.into(),
+            effective_gas_price: item.effective_gas_price.into(),
+            logs_bloom: item.logs_bloom.into(),
+        }
+    }
+}
+
+impl From<TransactionMinedRocksdbV3> for TransactionMinedRocksdb {
+    fn from(item: TransactionMinedRocksdbV3) -> Self {
+        Self {
+            input: item.input,
+            execution: item.execution.into(),
+            logs: item.logs,
+            transaction_index: item.transaction_index,
+            block_number: item.block_number,
+            block_hash: item.block_hash,
+            cumulative_gas_used: item.cumulative_gas_used,
+            effective_gas_price: item.effective_gas_price,
+            logs_bloom: item.logs_bloom,
+        }
+    }
+}
+
+/// Receipt fields are zeroed because they depend on the block and must be filled by it.
+impl From<TransactionMinedRocksdbV2> for TransactionMinedRocksdbV3 {
+    fn from(item: TransactionMinedRocksdbV2) -> Self {
+        Self {
+            input: item.input,
+            execution: item.execution,
+            logs: item.logs,
+            transaction_index: item.transaction_index,
+            block_number: item.block_number,
+            block_hash: item.block_hash,
+            cumulative_gas_used: GasRocksdb::from(0),
+            effective_gas_price: WeiRocksdb::ZERO,
+            logs_bloom: LogsBloom::default().into(),
+        }
+    }
+}
+
+impl From<TransactionMinedRocksdbV1> for TransactionMinedRocksdbV2 {
+    fn from(item: TransactionMinedRocksdbV1) -> Self {
+        Self {
+            input: item.input.into(),
+            execution: item.execution,
+            logs: item.logs,
+            transaction_index: item.transaction_index,
+            block_number: item.block_number,
+            block_hash: item.block_hash,
         }
     }
 }
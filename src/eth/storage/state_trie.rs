@@ -0,0 +1,738 @@
+//! Merkle-Patricia trie used to calculate state roots and account proofs.
+//!
+//! Nodes are persisted by the permanent storage indexed by their hash and are never removed, so the state of past blocks
+//! can still be proved as long as their state root is known.
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::mem;
+
+use anyhow::anyhow;
+use anyhow::bail;
+use ethereum_types::H256;
+use ethereum_types::U256;
+use ethers_core::utils::keccak256;
+use rlp::Rlp;
+use rlp::RlpStream;
+
+use crate::eth::primitives::AccountProof;
+use crate::eth::primitives::Address;
+use crate::eth::primitives::Block;
+use crate::eth::primitives::Bytes;
+use crate::eth::primitives::CodeHash;
+use crate::eth::primitives::ExecutionAccountChanges;
+use crate::eth::primitives::Hash;
+use crate::eth::primitives::Nonce;
+use crate::eth::primitives::SlotIndex;
+use crate::eth::primitives::SlotValue;
+use crate::eth::primitives::StorageProof;
+use crate::eth::primitives::Wei;
+use crate::ext::not;
+
+/// Source of persisted trie nodes.
+pub trait TrieNodeSource {
+    /// Retrieves an encoded trie node by its hash. Returns None when not found.
+    fn read_trie_node(&self, hash: &Hash) -> anyhow::Result<Option<Vec<u8>>>;
+}
+
+impl<H: BuildHasher> TrieNodeSource for HashMap<Hash, Vec<u8>, H> {
+    fn read_trie_node(&self, hash: &Hash) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.get(hash).cloned())
+    }
+}
+
+/// Result of applying changes to the state trie.
+#[derive(Debug, Default)]
+pub struct StateTrieUpdate {
+    /// State root after the changes were applied.
+    pub root: Hash,
+
+    /// Encoded nodes created by the changes that must be persisted.
+    pub nodes: Vec<(Hash, Vec<u8>)>,
+}
+
+// -----------------------------------------------------------------------------
+// State trie
+// -----------------------------------------------------------------------------
+
+/// Applies account changes to the state trie with the specified root and returns the new root and the nodes that must be persisted.
+pub fn update_state_trie<'a, S, I>(source: &S, root: Hash, changes: I) -> anyhow::Result<StateTrieUpdate>
+where
+    S: TrieNodeSource + ?Sized,
+    I: IntoIterator<Item = &'a ExecutionAccountChanges>,
+{
+    let mut nodes = Vec::new();
+    let mut state_trie = MerkleTrie::new(source, root);
+
+    for change in changes {
+        let modified_slots = change.slots.values().filter_map(|slot| slot.take_modified_ref()).collect::<Vec<_>>();
+        if not(change.is_account_modified()) && modified_slots.is_empty() {
+            continue;
+        }
+
+        // retrieve current account state
+        let account_key = keccak256(change.address);
+        let mut account = match state_trie.get(&account_key)? {
+            Some(encoded) => TrieAccount::decode(&encoded)?,
+            None => TrieAccount::default(),
+        };
+
+        // apply account changes
+        if let Some(nonce) = change.nonce.take_modified_ref() {
+            account.nonce = *nonce;
+        }
+        if let Some(balance) = change.balance.take_modified_ref() {
+            account.balance = *balance;
+        }
+        if let Some(bytecode) = change.bytecode.take_modified_ref() {
+            account.code_hash = CodeHash::from_bytecode(bytecode.clone());
+        }
+
+        // apply slot changes
+        if not(modified_slots.is_empty()) {
+            let mut storage_trie = MerkleTrie::new(source, account.storage_root);
+            for slot in modified_slots {
+                let slot_key = keccak256(<[u8; 32]>::from(slot.index));
+                if slot.value.is_zero() {
+                    storage_trie.remove(&slot_key)?;
+                } else {
+                    storage_trie.insert(&slot_key, rlp::encode(&slot.value.as_u256()).to_vec())?;
+                }
+            }
+            account.storage_root = storage_trie.commit(&mut nodes);
+        }
+
+        state_trie.insert(&account_key, account.encode())?;
+    }
+
+    let root = state_trie.commit(&mut nodes);
+    Ok(StateTrieUpdate { root, nodes })
+}
+
+/// Checks the state root calculated while saving a block is the state root of the block.
+///
+/// Blocks saved while the state trie differs from the state of the parent block are not checked, because their state root could not be calculated locally.
+/// This happens when the previous blocks were not imported since the genesis.
+pub fn check_block_state_root<F>(block: &Block, previous_root: Hash, root: Hash, read_parent_state_root: F) -> anyhow::Result<()>
+where
+    F: FnOnce() -> anyhow::Result<Option<Hash>>,
+{
+    if root == block.header.state_root || read_parent_state_root()? != Some(previous_root) {
+        return Ok(());
+    }
+    bail!(
+        "state root {} calculated while saving block {} differs from the state root {} of the block",
+        root,
+        block.number(),
+        block.header.state_root
+    )
+}
+
+/// Generates the proof of an account and some of its slots against the state trie with the specified root.
+///
+/// If the account or the slots do not exist, the proof of their absence is generated.
+pub fn prove_state_trie<S>(source: &S, root: Hash, address: Address, indexes: &[SlotIndex]) -> anyhow::Result<AccountProof>
+where
+    S: TrieNodeSource + ?Sized,
+{
+    // account
+    let state_trie = MerkleTrie::new(source, root);
+    let (encoded_account, account_proof) = state_trie.prove(&keccak256(address))?;
+    let account = match encoded_account {
+        Some(encoded) => TrieAccount::decode(&encoded)?,
+        None => TrieAccount::default(),
+    };
+
+    // slots
+    let storage_trie = MerkleTrie::new(source, account.storage_root);
+    let mut storage_proof = Vec::with_capacity(indexes.len());
+    for index in indexes {
+        let (encoded_value, proof) = storage_trie.prove(&keccak256(<[u8; 32]>::from(*index)))?;
+        let value = match encoded_value {
+            Some(encoded) => Rlp::new(&encoded).as_val::<U256>()?.into(),
+            None => SlotValue::default(),
+        };
+        storage_proof.push(StorageProof { key: *index, value, proof });
+    }
+
+    Ok(AccountProof {
+        address,
+        account_proof,
+        balance: account.balance,
+        code_hash: account.code_hash,
+        nonce: account.nonce,
+        storage_hash: account.storage_root,
+        storage_proof,
+    })
+}
+
+/// Account as encoded in the leaves of the state trie.
+struct TrieAccount {
+    nonce: Nonce,
+    balance: Wei,
+    storage_root: Hash,
+    code_hash: CodeHash,
+}
+
+impl Default for TrieAccount {
+    fn default() -> Self {
+        Self {
+            nonce: Nonce::ZERO,
+            balance: Wei::ZERO,
+            storage_root: Hash::EMPTY_TRIE,
+            code_hash: CodeHash::default(),
+        }
+    }
+}
+
+impl TrieAccount {
+    fn encode(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(4);
+        stream.append(&self.nonce.as_u64());
+        stream.append(&U256::from(self.balance));
+        stream.append(&self.storage_root.0);
+        stream.append(&self.code_hash.0);
+        stream.out().to_vec()
+    }
+
+    fn decode(bytes: &[u8]) -> anyhow::Result<Self> {
+        let rlp = Rlp::new(bytes);
+        Ok(Self {
+            nonce: rlp.val_at::<u64>(0)?.into(),
+            balance: rlp.val_at::<U256>(1)?.into(),
+            storage_root: rlp.val_at::<H256>(2)?.into(),
+            code_hash: CodeHash::new(rlp.val_at::<H256>(3)?),
+        })
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Merkle-Patricia trie
+// -----------------------------------------------------------------------------
+
+/// Merkle-Patricia trie that loads persisted nodes on demand and keeps modified nodes in memory until committed.
+struct MerkleTrie<'a, S: ?Sized> {
+    source: &'a S,
+    root: Child,
+}
+
+/// Decoded trie node. Paths are represented as nibbles.
+#[derive(Debug, Clone)]
+enum Node {
+    Empty,
+    Leaf { path: Vec<u8>, value: Vec<u8> },
+    Extension { path: Vec<u8>, child: Child },
+    Branch { children: Box<[Child; 16]>, value: Option<Vec<u8>> },
+}
+
+/// Reference from a node to one of its children.
+#[derive(Debug, Clone)]
+enum Child {
+    /// Persisted node that was not loaded yet.
+    Hash(Hash),
+
+    /// Node loaded in memory or embedded in its parent.
+    Node(Box<Node>),
+}
+
+impl Child {
+    fn empty() -> Self {
+        Self::Node(Box::new(Node::Empty))
+    }
+
+    fn is_empty(&self) -> bool {
+        matches!(self, Self::Node(node) if matches!(**node, Node::Empty))
+    }
+}
+
+impl<'a, S> MerkleTrie<'a, S>
+where
+    S: TrieNodeSource + ?Sized,
+{
+    fn new(source: &'a S, root: Hash) -> Self {
+        let root = if root == Hash::EMPTY_TRIE { Child::empty() } else { Child::Hash(root) };
+        Self { source, root }
+    }
+
+    /// Retrieves the value associated with the key.
+    fn get(&self, key: &[u8]) -> anyhow::Result<Option<Vec<u8>>> {
+        let mut proof = Vec::new();
+        self.get_at(&self.root, &to_nibbles(key), &mut proof)
+    }
+
+    /// Retrieves the value associated with the key and the persisted nodes visited to find it.
+    ///
+    /// Must be called only before modifying the trie, otherwise modified nodes will be missing from the proof.
+    fn prove(&self, key: &[u8]) -> anyhow::Result<(Option<Vec<u8>>, Vec<Bytes>)> {
+        let mut proof = Vec::new();
+        let value = self.get_at(&self.root, &to_nibbles(key), &mut proof)?;
+        Ok((value, proof))
+    }
+
+    /// Associates the value with the key.
+    fn insert(&mut self, key: &[u8], value: Vec<u8>) -> anyhow::Result<()> {
+        let root = self.load(mem::replace(&mut self.root, Child::empty()))?;
+        self.root = Child::Node(Box::new(self.insert_at(root, &to_nibbles(key), value)?));
+        Ok(())
+    }
+
+    /// Removes the value associated with the key.
+    fn remove(&mut self, key: &[u8]) -> anyhow::Result<()> {
+        let root = self.load(mem::replace(&mut self.root, Child::empty()))?;
+        self.root = Child::Node(Box::new(self.remove_at(root, &to_nibbles(key))?));
+        Ok(())
+    }
+
+    /// Encodes all modified nodes, appending the ones that must be persisted to `nodes`, and returns the trie root.
+    fn commit(self, nodes: &mut Vec<(Hash, Vec<u8>)>) -> Hash {
+        match self.root {
+            Child::Hash(hash) => hash,
+            Child::Node(node) if matches!(*node, Node::Empty) => Hash::EMPTY_TRIE,
+            Child::Node(node) => {
+                // root is always persisted by hash, even if it is small enough to be embedded
+                let encoded = encode_node(&node, nodes);
+                let hash = Hash::new(keccak256(&encoded));
+                nodes.push((hash, encoded));
+                hash
+            }
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // Read
+    // -------------------------------------------------------------------------
+
+    fn get_at(&self, child: &Child, path: &[u8], proof: &mut Vec<Bytes>) -> anyhow::Result<Option<Vec<u8>>> {
+        match child {
+            Child::Hash(hash) => {
+                let encoded = self.read(hash)?;
+                let node = decode_node(&Rlp::new(&encoded))?;
+                proof.push(encoded.into());
+                self.get_in_node(&node, path, proof)
+            }
+            Child::Node(node) => self.get_in_node(node, path, proof),
+        }
+    }
+
+    fn get_in_node(&self, node: &Node, path: &[u8], proof: &mut Vec<Bytes>) -> anyhow::Result<Option<Vec<u8>>> {
+        match node {
+            Node::Empty => Ok(None),
+            Node::Leaf { path: leaf_path, value } => Ok((leaf_path.as_slice() == path).then(|| value.clone())),
+            Node::Extension { path: extension_path, child } => match path.strip_prefix(extension_path.as_slice()) {
+                Some(remaining) => self.get_at(child, remaining, proof),
+                None => Ok(None),
+            },
+            Node::Branch { children, value } => match path.split_first() {
+                Some((index, remaining)) => self.get_at(&children[*index as usize], remaining, proof),
+                None => Ok(value.clone()),
+            },
+        }
+    }
+
+    fn read(&self, hash: &Hash) -> anyhow::Result<Vec<u8>> {
+        match self.source.read_trie_node(hash)? {
+            Some(encoded) => Ok(encoded),
+            None => Err(anyhow!("trie node {} not found", hash)),
+        }
+    }
+
+    fn load(&self, child: Child) -> anyhow::Result<Node> {
+        match child {
+            Child::Hash(hash) => decode_node(&Rlp::new(&self.read(&hash)?)),
+            Child::Node(node) => Ok(*node),
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // Write
+    // -------------------------------------------------------------------------
+
+    fn insert_at(&self, node: Node, path: &[u8], value: Vec<u8>) -> anyhow::Result<Node> {
+        match node {
+            Node::Empty => Ok(Node::Leaf { path: path.to_vec(), value }),
+
+            Node::Leaf {
+                path: leaf_path,
+                value: leaf_value,
+            } => {
+                if leaf_path == path {
+                    return Ok(Node::Leaf { path: leaf_path, value });
+                }
+
+                // split leaf in a branch containing both values
+                let common = common_prefix_len(&leaf_path, path);
+                let branch = self.insert_at(new_branch(), &leaf_path[common..], leaf_value)?;
+                let branch = self.insert_at(branch, &path[common..], value)?;
+                Ok(with_extension(&path[..common], branch))
+            }
+
+            Node::Extension { path: extension_path, child } => {
+                let common = common_prefix_len(&extension_path, path);
+                if common == extension_path.len() {
+                    let child = self.load(child)?;
+                    let child = self.insert_at(child, &path[common..], value)?;
+                    return Ok(Node::Extension {
+                        path: extension_path,
+                        child: Child::Node(Box::new(child)),
+                    });
+                }
+
+                // split extension in a branch containing the remaining extension and the new value
+                let mut children = empty_children();
+                children[extension_path[common] as usize] = match &extension_path[common + 1..] {
+                    [] => child,
+                    remaining => Child::Node(Box::new(Node::Extension {
+                        path: remaining.to_vec(),
+                        child,
+                    })),
+                };
+                let branch = self.insert_at(Node::Branch { children, value: None }, &path[common..], value)?;
+                Ok(with_extension(&path[..common], branch))
+            }
+
+            Node::Branch {
+                mut children,
+                value: branch_value,
+            } => match path.split_first() {
+                Some((index, remaining)) => {
+                    let index = *index as usize;
+                    let child = self.load(mem::replace(&mut children[index], Child::empty()))?;
+                    children[index] = Child::Node(Box::new(self.insert_at(child, remaining, value)?));
+                    Ok(Node::Branch { children, value: branch_value })
+                }
+                None => Ok(Node::Branch { children, value: Some(value) }),
+            },
+        }
+    }
+
+    fn remove_at(&self, node: Node, path: &[u8]) -> anyhow::Result<Node> {
+        match node {
+            Node::Empty => Ok(Node::Empty),
+
+            Node::Leaf { path: leaf_path, value } =>
+                if leaf_path == path {
+                    Ok(Node::Empty)
+                } else {
+                    Ok(Node::Leaf { path: leaf_path, value })
+                },
+
+            Node::Extension { path: extension_path, child } => {
+                let Some(remaining) = path.strip_prefix(extension_path.as_slice()) else {
+                    return Ok(Node::Extension { path: extension_path, child });
+                };
+                let child = self.load(child)?;
+                let child = self.remove_at(child, remaining)?;
+                Ok(with_extension(&extension_path, child))
+            }
+
+            Node::Branch { mut children, mut value } => {
+                match path.split_first() {
+                    Some((index, remaining)) => {
+                        let index = *index as usize;
+                        let child = self.load(mem::replace(&mut children[index], Child::empty()))?;
+                        children[index] = Child::Node(Box::new(self.remove_at(child, remaining)?));
+                    }
+                    None => value = None,
+                }
+                self.normalize_branch(children, value)
+            }
+        }
+    }
+
+    /// Collapses a branch that does not need to be a branch anymore after a removal.
+    fn normalize_branch(&self, mut children: Box<[Child; 16]>, value: Option<Vec<u8>>) -> anyhow::Result<Node> {
+        let mut non_empty = children.iter().enumerate().filter(|(_, child)| not(child.is_empty())).map(|(index, _)| index);
+        let first = non_empty.next();
+        let has_more = non_empty.next().is_some();
+
+        match (first, has_more, value) {
+            // no children: becomes a leaf or disappears
+            (None, _, Some(value)) => Ok(Node::Leaf { path: Vec::new(), value }),
+            (None, _, None) => Ok(Node::Empty),
+
+            // single child: merges with it
+            (Some(index), false, None) => {
+                let child = self.load(mem::replace(&mut children[index], Child::empty()))?;
+                Ok(with_extension(&[index as u8], child))
+            }
+
+            // still a branch
+            (_, _, value) => Ok(Node::Branch { children, value }),
+        }
+    }
+}
+
+fn new_branch() -> Node {
+    Node::Branch {
+        children: empty_children(),
+        value: None,
+    }
+}
+
+fn empty_children() -> Box<[Child; 16]> {
+    Box::new(std::array::from_fn(|_| Child::empty()))
+}
+
+/// Prepends the path to the node, merging it with the node path when possible.
+fn with_extension(path: &[u8], node: Node) -> Node {
+    if path.is_empty() {
+        return node;
+    }
+    match node {
+        Node::Empty => Node::Empty,
+        Node::Leaf { path: node_path, value } => Node::Leaf {
+            path: [path, &node_path].concat(),
+            value,
+        },
+        Node::Extension { path: node_path, child } => Node::Extension {
+            path: [path, &node_path].concat(),
+            child,
+        },
+        branch @ Node::Branch { .. } => Node::Extension {
+            path: path.to_vec(),
+            child: Child::Node(Box::new(branch)),
+        },
+    }
+}
+
+fn common_prefix_len(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(a, b)| a == b).count()
+}
+
+fn to_nibbles(key: &[u8]) -> Vec<u8> {
+    key.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]).collect()
+}
+
+// -----------------------------------------------------------------------------
+// Encoding
+// -----------------------------------------------------------------------------
+
+/// Encodes a node, appending the encoded children that are not embedded in it to `nodes`.
+fn encode_node(node: &Node, nodes: &mut Vec<(Hash, Vec<u8>)>) -> Vec<u8> {
+    let mut stream = match node {
+        Node::Empty => {
+            let mut stream = RlpStream::new();
+            stream.append_empty_data();
+            stream
+        }
+        Node::Leaf { path, value } => {
+            let mut stream = RlpStream::new_list(2);
+            stream.append(&encode_path(path, true));
+            stream.append(value);
+            stream
+        }
+        Node::Extension { path, child } => {
+            let mut stream = RlpStream::new_list(2);
+            stream.append(&encode_path(path, false));
+            append_child(&mut stream, child, nodes);
+            stream
+        }
+        Node::Branch { children, value } => {
+            let mut stream = RlpStream::new_list(17);
+            for child in children.iter() {
+                append_child(&mut stream, child, nodes);
+            }
+            match value {
+                Some(value) => stream.append(value),
+                None => stream.append_empty_data(),
+            };
+            stream
+        }
+    };
+    stream.out().to_vec()
+}
+
+/// Appends a child reference to its parent: small children are embedded and larger children are referenced by their hash.
+fn append_child(stream: &mut RlpStream, child: &Child, nodes: &mut Vec<(Hash, Vec<u8>)>) {
+    match child {
+        Child::Hash(hash) => {
+            stream.append(&hash.0);
+        }
+        Child::Node(node) if matches!(**node, Node::Empty) => {
+            stream.append_empty_data();
+        }
+        Child::Node(node) => {
+            let encoded = encode_node(node, nodes);
+            if encoded.len() < 32 {
+                stream.append_raw(&encoded, 1);
+            } else {
+                let hash = Hash::new(keccak256(&encoded));
+                stream.append(&hash.0);
+                nodes.push((hash, encoded));
+            }
+        }
+    }
+}
+
+fn decode_node(rlp: &Rlp) -> anyhow::Result<Node> {
+    if rlp.is_data() && rlp.is_empty() {
+        return Ok(Node::Empty);
+    }
+
+    match rlp.item_count()? {
+        2 => {
+            let (path, is_leaf) = decode_path(rlp.at(0)?.data()?);
+            if is_leaf {
+                Ok(Node::Leaf {
+                    path,
+                    value: rlp.at(1)?.data()?.to_vec(),
+                })
+            } else {
+                Ok(Node::Extension {
+                    path,
+                    child: decode_child(&rlp.at(1)?)?,
+                })
+            }
+        }
+        17 => {
+            let mut children = empty_children();
+            for (index, child) in children.iter_mut().enumerate() {
+                *child = decode_child(&rlp.at(index)?)?;
+            }
+            let value = rlp.at(16)?.data()?;
+            Ok(Node::Branch {
+                children,
+                value: (not(value.is_empty())).then(|| value.to_vec()),
+            })
+        }
+        count => bail!("invalid trie node with {} items", count),
+    }
+}
+
+fn decode_child(rlp: &Rlp) -> anyhow::Result<Child> {
+    if rlp.is_list() {
+        return Ok(Child::Node(Box::new(decode_node(rlp)?)));
+    }
+    match rlp.data()? {
+        [] => Ok(Child::empty()),
+        hash if hash.len() == 32 => Ok(Child::Hash(Hash::new_from_h256(H256::from_slice(hash)))),
+        other => bail!("invalid trie node reference with {} bytes", other.len()),
+    }
+}
+
+/// Encodes nibbles using hex-prefix encoding.
+fn encode_path(path: &[u8], is_leaf: bool) -> Vec<u8> {
+    let flag = if is_leaf { 0x20 } else { 0x00 };
+    let mut encoded = Vec::with_capacity(path.len() / 2 + 1);
+
+    let pairs = if path.len() % 2 == 1 {
+        encoded.push(flag | 0x10 | path[0]);
+        &path[1..]
+    } else {
+        encoded.push(flag);
+        path
+    };
+    for pair in pairs.chunks(2) {
+        encoded.push((pair[0] << 4) | pair[1]);
+    }
+    encoded
+}
+
+/// Decodes nibbles encoded using hex-prefix encoding and returns if they belong to a leaf.
+fn decode_path(encoded: &[u8]) -> (Vec<u8>, bool) {
+    let Some((first, remaining)) = encoded.split_first() else {
+        return (Vec::new(), false);
+    };
+    let is_leaf = first & 0x20 != 0;
+    let is_odd = first & 0x10 != 0;
+
+    let mut path = Vec::with_capacity(remaining.len() * 2 + 1);
+    if is_odd {
+        path.push(first & 0x0f);
+    }
+    path.extend(to_nibbles(remaining));
+    (path, is_leaf)
+}
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use fake::Fake;
+    use fake::Faker;
+    use keccak_hasher::KeccakHasher;
+
+    use super::*;
+
+    fn insert_all(source: &mut HashMap<Hash, Vec<u8>>, root: Hash, entries: &[(Vec<u8>, Vec<u8>)]) -> Hash {
+        let mut trie = MerkleTrie::new(&*source, root);
+        for (key, value) in entries {
+            trie.insert(key, value.clone()).unwrap();
+        }
+        let mut nodes = Vec::new();
+        let root = trie.commit(&mut nodes);
+        source.extend(nodes);
+        root
+    }
+
+    fn random_entries(len: usize) -> Vec<(Vec<u8>, Vec<u8>)> {
+        (0..len)
+            .map(|_| {
+                let key: [u8; 32] = Faker.fake();
+                let value: [u8; 32] = Faker.fake();
+                (key.to_vec(), value.to_vec())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_trie_empty_root() {
+        let source = HashMap::new();
+        let trie = MerkleTrie::new(&source, Hash::EMPTY_TRIE);
+        assert_eq!(trie.commit(&mut Vec::new()), Hash::EMPTY_TRIE);
+    }
+
+    #[test]
+    fn test_trie_root_matches_reference_implementation() {
+        for len in [1, 2, 16, 100, 1000] {
+            let entries = random_entries(len);
+
+            let mut source = HashMap::new();
+            let root = insert_all(&mut source, Hash::EMPTY_TRIE, &entries);
+
+            let expected = triehash::trie_root::<KeccakHasher, _, _, _>(entries.clone());
+            assert_eq!(root, Hash::new(expected.into()), "root mismatch with {} entries", len);
+        }
+    }
+
+    #[test]
+    fn test_trie_incremental_updates_and_removals() {
+        let entries = random_entries(200);
+        let (kept, removed) = entries.split_at(100);
+
+        // insert in two commits
+        let mut source = HashMap::new();
+        let root = insert_all(&mut source, Hash::EMPTY_TRIE, kept);
+        let root = insert_all(&mut source, root, removed);
+        let expected = triehash::trie_root::<KeccakHasher, _, _, _>(entries.clone());
+        assert_eq!(root, Hash::new(expected.into()));
+
+        // remove half of the entries
+        let mut trie = MerkleTrie::new(&source, root);
+        for (key, _) in removed {
+            trie.remove(key).unwrap();
+        }
+        let root = trie.commit(&mut Vec::new());
+        let expected = triehash::trie_root::<KeccakHasher, _, _, _>(kept.to_vec());
+        assert_eq!(root, Hash::new(expected.into()));
+    }
+
+    #[test]
+    fn test_trie_get_and_prove() {
+        let entries = random_entries(100);
+        let mut source = HashMap::new();
+        let root = insert_all(&mut source, Hash::EMPTY_TRIE, &entries);
+
+        let trie = MerkleTrie::new(&source, root);
+        for (key, value) in &entries {
+            let (found, proof) = trie.prove(key).unwrap();
+            assert_eq!(found.as_ref(), Some(value));
+            assert_eq!(keccak256(&proof[0].0), root.0 .0);
+        }
+
+        let missing: [u8; 32] = Faker.fake();
+        assert_eq!(trie.get(&missing).unwrap(), None);
+    }
+}
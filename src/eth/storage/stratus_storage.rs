@@ -5,20 +5,31 @@ use display_json::DebugAsJson;
 use tracing::Span;
 
 use crate::eth::primitives::Account;
+use crate::eth::primitives::AccountProof;
 use crate::eth::primitives::Address;
 use crate::eth::primitives::Block;
 use crate::eth::primitives::BlockFilter;
 use crate::eth::primitives::BlockNumber;
+use crate::eth::primitives::BlockSearch;
+use crate::eth::primitives::Bytes;
+use crate::eth::primitives::CodeHash;
+use crate::eth::primitives::ExecutionAccountChanges;
+use crate::eth::primitives::Genesis;
 use crate::eth::primitives::Hash;
 use crate::eth::primitives::LogFilter;
 use crate::eth::primitives::LogMined;
+use crate::eth::primitives::Nonce;
 use crate::eth::primitives::PendingBlock;
 use crate::eth::primitives::PendingBlockHeader;
 use crate::eth::primitives::Slot;
 use crate::eth::primitives::SlotIndex;
+use crate::eth::primitives::StateDiff;
 use crate::eth::primitives::StratusError;
 use crate::eth::primitives::TransactionExecution;
+use crate::eth::primitives::TransactionMined;
 use crate::eth::primitives::TransactionStage;
+use crate::eth::storage::ForkStorage;
+use crate::eth::storage::ForkStorageConfig;
 use crate::eth::storage::PermanentStorage;
 use crate::eth::storage::PermanentStorageConfig;
 use crate::eth::storage::StoragePointInTime;
@@ -32,6 +43,7 @@ use crate::infra::tracing::SpanExt;
 mod label {
     pub(super) const TEMP: &str = "temporary";
     pub(super) const PERM: &str = "permanent";
+    pub(super) const FORK: &str = "fork";
 }
 
 /// Proxy that simplifies interaction with permanent and temporary storages.
@@ -40,6 +52,33 @@ mod label {
 pub struct StratusStorage {
     temp: Box<dyn TemporaryStorage>,
     perm: Box<dyn PermanentStorage>,
+
+    /// State of an external node used for accounts and slots missing in the permanent storage.
+    ///
+    /// Values read from it are not part of the state trie, so state roots are not valid and proofs are refused when it is set.
+    fork: Option<Box<dyn ForkStorage>>,
+
+    /// Genesis file the permanent storage is initialized with.
+    genesis: Option<Genesis>,
+
+    /// Snapshots that can be reverted, where the snapshot id is the position starting at 1.
+    #[cfg(feature = "dev")]
+    snapshots: std::sync::Mutex<Vec<Snapshot>>,
+}
+
+/// State recorded by a snapshot to be restored when it is reverted.
+#[cfg(feature = "dev")]
+struct Snapshot {
+    /// Last mined block when the snapshot was taken.
+    block_number: BlockNumber,
+
+    /// Offset from the system time and timestamp of the next block when the snapshot was taken.
+    time_offset: (i64, crate::eth::primitives::UnixTime),
+
+    /// Previous values of the accounts changed outside of transactions after the snapshot was taken, while the last mined block was the snapshot block.
+    ///
+    /// These changes overwrite the state of the snapshot block, so they are not discarded by resetting to it.
+    undo_changes: Vec<ExecutionAccountChanges>,
 }
 
 impl StratusStorage {
@@ -49,20 +88,68 @@ impl StratusStorage {
 
     /// Creates a new storage with the specified temporary and permanent implementations.
     pub fn new(temp: Box<dyn TemporaryStorage>, perm: Box<dyn PermanentStorage>) -> Result<Self, StratusError> {
-        let this = Self { temp, perm };
+        Self::builder(temp, perm).build()
+    }
+
+    /// Creates a builder of a storage with the specified temporary and permanent implementations, that can be forked or initialized with a genesis file.
+    pub fn builder(temp: Box<dyn TemporaryStorage>, perm: Box<dyn PermanentStorage>) -> StratusStorageBuilder {
+        StratusStorageBuilder {
+            temp,
+            perm,
+            fork: None,
+            genesis: None,
+        }
+    }
 
+    /// Initializes the permanent storage and the pending block of a storage built by [`StratusStorageBuilder`].
+    fn init(self) -> Result<Self, StratusError> {
         // create genesis block and accounts if necessary
+        if let Some(ref genesis) = self.genesis {
+            self.init_genesis(genesis)?;
+        }
         #[cfg(feature = "dev")]
-        {
-            let genesis = this.read_block(&crate::eth::primitives::BlockFilter::Number(crate::eth::primitives::BlockNumber::ZERO))?;
+        if self.genesis.is_none() {
+            let genesis = self.read_block(&crate::eth::primitives::BlockFilter::Number(crate::eth::primitives::BlockNumber::ZERO))?;
             if genesis.is_none() {
-                this.reset_to_genesis()?;
+                self.reset_to_genesis()?;
             }
         }
 
-        this.set_pending_block_number_as_next_if_not_set()?;
+        self.set_pending_block_number_as_next_if_not_set()?;
 
-        Ok(this)
+        Ok(self)
+    }
+
+    /// Saves the genesis block and accounts from the genesis file if the permanent storage is empty, or checks the stored genesis block is the same.
+    fn init_genesis(&self, genesis: &Genesis) -> Result<(), StratusError> {
+        let block = genesis.to_block()?;
+        match self.read_block(&BlockFilter::Number(BlockNumber::ZERO))? {
+            Some(stored) if stored.hash() != block.hash() => {
+                tracing::error!(stored = %stored.hash(), genesis = %block.hash(), "genesis block in permanent storage differs from genesis file");
+                Err(StratusError::StorageGenesisMismatch {
+                    stored: stored.hash(),
+                    genesis: block.hash(),
+                })
+            }
+            Some(_) => Ok(()),
+            None => self.save_genesis(block, genesis.account_changes()),
+        }
+    }
+
+    /// Saves atomically the genesis block and accounts, and sets the genesis block as the last mined block.
+    fn save_genesis(&self, block: Block, accounts: Vec<ExecutionAccountChanges>) -> Result<(), StratusError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("storage::save_genesis", block_hash = %block.hash()).entered();
+        tracing::info!(storage = %label::PERM, block_hash = %block.hash(), accounts = accounts.len(), "saving genesis block and accounts");
+
+        timed(|| self.perm.save_genesis(block, accounts)).with(|m| {
+            metrics::inc_storage_save_genesis(m.elapsed, label::PERM, m.result.is_ok());
+            if let Err(ref e) = m.result {
+                tracing::error!(reason = ?e, "failed to save genesis");
+            }
+        })?;
+
+        self.set_mined_block_number(BlockNumber::ZERO)
     }
 
     // -------------------------------------------------------------------------
@@ -207,6 +294,66 @@ impl StratusStorage {
             .map_err(Into::into)
     }
 
+    #[cfg(feature = "dev")]
+    /// Saves changes made to accounts outside of transactions as if they were made in the last mined block.
+    ///
+    /// Fails if there are pending transactions, because they were executed against the state before the changes.
+    pub fn save_account_changes(&self, changes: Vec<ExecutionAccountChanges>) -> Result<(), StratusError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("storage::save_account_changes").entered();
+
+        use crate::ext::MutexExt;
+
+        let pending_txs = self.temp.read_pending_executions().len();
+        if pending_txs > 0 {
+            return Err(StratusError::PendingTransactionsExist { pending_txs });
+        }
+
+        // keep the previous values for the last snapshot if the changes overwrite its block
+        let mut snapshots = self.snapshots.lock_or_clear("storage snapshots lock was poisoned");
+        if let Some(snapshot) = snapshots.last_mut() {
+            if snapshot.block_number == self.read_mined_block_number()? {
+                let undo_changes = self.read_previous_values(&changes)?;
+                snapshot.undo_changes.extend(undo_changes);
+            }
+        }
+
+        self.save_account_changes_to_perm(changes)?;
+
+        // temporary storage keeps values from previous blocks that would shadow the changes
+        self.reset_temp()
+    }
+
+    #[cfg(feature = "dev")]
+    fn save_account_changes_to_perm(&self, changes: Vec<ExecutionAccountChanges>) -> Result<(), StratusError> {
+        tracing::debug!(storage = %label::PERM, ?changes, "saving account changes");
+        timed(|| self.perm.save_account_changes(changes))
+            .with(|m| {
+                metrics::inc_storage_save_account_changes(m.elapsed, label::PERM, m.result.is_ok());
+                if let Err(ref e) = m.result {
+                    tracing::error!(reason = ?e, "failed to save account changes");
+                }
+            })
+            .map_err(Into::into)
+    }
+
+    #[cfg(feature = "dev")]
+    /// Reads the last mined values of the accounts and slots modified by the changes, as changes that restore them.
+    fn read_previous_values(&self, changes: &[ExecutionAccountChanges]) -> Result<Vec<ExecutionAccountChanges>, StratusError> {
+        changes
+            .iter()
+            .map(|change| {
+                let account = self.read_account(&change.address, &StoragePointInTime::Mined)?;
+                let slots = change
+                    .slots
+                    .keys()
+                    .map(|index| self.read_slot(&change.address, index, &StoragePointInTime::Mined))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(ExecutionAccountChanges::from_modified_values(account, slots))
+            })
+            .collect()
+    }
+
     pub fn read_account(&self, address: &Address, point_in_time: &StoragePointInTime) -> Result<Account, StratusError> {
         #[cfg(feature = "tracing")]
         let _span = tracing::debug_span!("storage::read_account", %address, %point_in_time).entered();
@@ -239,10 +386,13 @@ impl StratusStorage {
                 tracing::debug!(storage = %label::PERM, %address, ?account, "account found in permanent storage");
                 Ok(account)
             }
-            None => {
-                tracing::debug!(storage = %label::PERM, %address, "account not found, assuming default value");
-                Ok(Account::new_empty(*address))
-            }
+            None => match self.fork {
+                Some(ref fork) => self.read_fork_account(fork.as_ref(), address, point_in_time),
+                None => {
+                    tracing::debug!(storage = %label::PERM, %address, "account not found, assuming default value");
+                    Ok(Account::new_empty(*address))
+                }
+            },
         }
     }
 
@@ -278,11 +428,122 @@ impl StratusStorage {
                 tracing::debug!(storage = %label::PERM, %address, %index, value = %slot.value, "slot found in permanent storage");
                 Ok(slot)
             }
-            None => {
-                tracing::debug!(storage = %label::PERM, %address, %index, "slot not found, assuming default value");
-                Ok(Slot::new_empty(*index))
+            None => match self.fork {
+                Some(ref fork) => self.read_fork_slot(fork.as_ref(), address, index, point_in_time),
+                None => {
+                    tracing::debug!(storage = %label::PERM, %address, %index, "slot not found, assuming default value");
+                    Ok(Slot::new_empty(*index))
+                }
+            },
+        }
+    }
+
+    pub fn read_bytecode(&self, code_hash: &CodeHash) -> Result<Option<Bytes>, StratusError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("storage::read_bytecode", ?code_hash).entered();
+        tracing::debug!(storage = %label::PERM, ?code_hash, "reading bytecode");
+
+        timed(|| self.perm.read_bytecode(code_hash))
+            .with(|m| {
+                metrics::inc_storage_read_bytecode(m.elapsed, label::PERM, m.result.is_ok());
+                if let Err(ref e) = m.result {
+                    tracing::error!(reason = ?e, "failed to read bytecode");
+                }
+            })
+            .map_err(Into::into)
+    }
+
+    /// Reads an account missing in the permanent storage from the fork.
+    ///
+    /// When reading the current state, the account is cached in the permanent storage so it is found there in the next reads.
+    fn read_fork_account(&self, fork: &dyn ForkStorage, address: &Address, point_in_time: &StoragePointInTime) -> Result<Account, StratusError> {
+        tracing::debug!(storage = %label::FORK, %address, "account not found, reading from fork");
+        let account = timed(|| fork.read_account(address)).with(|m| {
+            metrics::inc_storage_read_account(m.elapsed, label::FORK, point_in_time, m.result.is_ok());
+            if let Err(ref e) = m.result {
+                tracing::error!(reason = ?e, "failed to read account from fork");
             }
+        })?;
+
+        // past states are never cached because the values may have been changed locally after the past block
+        if not(point_in_time.is_mined_past()) {
+            tracing::debug!(storage = %label::PERM, %address, "saving account read from fork");
+            timed(|| self.perm.save_fork_account(account.clone())).with(|m| {
+                metrics::inc_storage_save_fork_values(m.elapsed, label::PERM, m.result.is_ok());
+                if let Err(ref e) = m.result {
+                    tracing::error!(reason = ?e, "failed to save account read from fork");
+                }
+            })?;
+        }
+        Ok(account)
+    }
+
+    /// Reads a slot missing in the permanent storage from the fork.
+    ///
+    /// When reading the current state, the slot is cached in the permanent storage so it is found there in the next reads.
+    fn read_fork_slot(&self, fork: &dyn ForkStorage, address: &Address, index: &SlotIndex, point_in_time: &StoragePointInTime) -> Result<Slot, StratusError> {
+        tracing::debug!(storage = %label::FORK, %address, %index, "slot not found, reading from fork");
+        let slot = timed(|| fork.read_slot(address, index)).with(|m| {
+            metrics::inc_storage_read_slot(m.elapsed, label::FORK, point_in_time, m.result.is_ok());
+            if let Err(ref e) = m.result {
+                tracing::error!(reason = ?e, "failed to read slot from fork");
+            }
+        })?;
+
+        // past states are never cached because the values may have been changed locally after the past block
+        if not(point_in_time.is_mined_past()) {
+            tracing::debug!(storage = %label::PERM, %address, %index, "saving slot read from fork");
+            timed(|| self.perm.save_fork_slot(address, slot)).with(|m| {
+                metrics::inc_storage_save_fork_values(m.elapsed, label::PERM, m.result.is_ok());
+                if let Err(ref e) = m.result {
+                    tracing::error!(reason = ?e, "failed to save slot read from fork");
+                }
+            })?;
+        }
+        Ok(slot)
+    }
+
+    // -------------------------------------------------------------------------
+    // State trie
+    // -------------------------------------------------------------------------
+
+    /// Calculates the state root resulting from applying the changes to the last mined state without persisting it.
+    pub fn compute_state_root(&self, changes: &[ExecutionAccountChanges]) -> Result<Hash, StratusError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("storage::compute_state_root", changes = changes.len()).entered();
+        tracing::debug!(storage = %label::PERM, changes = changes.len(), "computing state root");
+
+        timed(|| self.perm.compute_state_root(changes))
+            .with(|m| {
+                metrics::inc_storage_compute_state_root(m.elapsed, label::PERM, m.result.is_ok());
+                if let Err(ref e) = m.result {
+                    tracing::error!(reason = ?e, "failed to compute state root");
+                }
+            })
+            .map_err(Into::into)
+    }
+
+    /// Generates the proof of an account and some of its slots.
+    ///
+    /// Fails when forked from another chain, because the state trie does not contain the values read from the fork.
+    pub fn read_proof(&self, address: &Address, indexes: &[SlotIndex], point_in_time: &StoragePointInTime) -> Result<AccountProof, StratusError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("storage::read_proof", %address, %point_in_time).entered();
+
+        if self.fork.is_some() {
+            return Err(StratusError::StorageProofUnsupported);
         }
+
+        tracing::debug!(storage = %label::PERM, %address, ?indexes, %point_in_time, "reading proof");
+
+        timed(|| self.perm.read_proof(address, indexes, point_in_time))
+            .with(|m| {
+                metrics::inc_storage_read_proof(m.elapsed, label::PERM, point_in_time, m.result.is_ok());
+                if let Err(ref e) = m.result {
+                    tracing::error!(reason = ?e, "failed to read proof");
+                }
+            })
+            .map_err(Into::into)
     }
 
     // -------------------------------------------------------------------------
@@ -304,6 +565,22 @@ impl StratusStorage {
             .map_err(Into::into)
     }
 
+    /// Removes an execution from the pending block, reverting its changes.
+    pub fn remove_execution(&self, tx_hash: &Hash) -> Result<Option<TransactionExecution>, StratusError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("storage::remove_execution", %tx_hash).entered();
+        tracing::debug!(storage = %label::TEMP, %tx_hash, "removing execution");
+
+        timed(|| self.temp.remove_pending_execution(tx_hash)).with(|m| {
+            metrics::inc_storage_remove_execution(m.elapsed, label::TEMP, m.result.is_ok());
+            if let Err(ref e) = m.result {
+                if e.is_internal() {
+                    tracing::error!(reason = ?e, "failed to remove execution");
+                }
+            }
+        })
+    }
+
     /// Retrieves pending transactions being mined.
     pub fn pending_transactions(&self) -> Vec<TransactionExecution> {
         self.temp.read_pending_executions()
@@ -330,6 +607,21 @@ impl StratusStorage {
         result
     }
 
+    pub fn restore_finished_block(&self) -> Result<(), StratusError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("storage::restore_finished_block").entered();
+        tracing::debug!(storage = %label::TEMP, "restoring finished block");
+
+        timed(|| self.temp.restore_finished_block())
+            .with(|m| {
+                metrics::inc_storage_restore_finished_block(m.elapsed, label::TEMP, m.result.is_ok());
+                if let Err(ref e) = m.result {
+                    tracing::error!(reason = ?e, "failed to restore finished block");
+                }
+            })
+            .map_err(Into::into)
+    }
+
     pub fn save_block(&self, block: Block) -> Result<(), StratusError> {
         let block_number = block.number();
 
@@ -392,6 +684,21 @@ impl StratusStorage {
             .map_err(Into::into)
     }
 
+    pub fn read_block_hash(&self, number: BlockNumber) -> Result<Option<Hash>, StratusError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("storage::read_block_hash", %number).entered();
+        tracing::debug!(storage = %label::PERM, %number, "reading block hash");
+
+        timed(|| self.perm.read_block_hash(number))
+            .with(|m| {
+                metrics::inc_storage_read_block_hash(m.elapsed, label::PERM, m.result.is_ok());
+                if let Err(ref e) = m.result {
+                    tracing::error!(reason = ?e, "failed to read block hash");
+                }
+            })
+            .map_err(Into::into)
+    }
+
     pub fn read_transaction(&self, tx_hash: &Hash) -> Result<Option<TransactionStage>, StratusError> {
         #[cfg(feature = "tracing")]
         let _span = tracing::info_span!("storage::read_transaction", %tx_hash).entered();
@@ -422,12 +729,12 @@ impl StratusStorage {
         }
     }
 
-    pub fn read_logs(&self, filter: &LogFilter) -> Result<Vec<LogMined>, StratusError> {
+    pub fn read_logs(&self, filter: &LogFilter, limit: usize) -> Result<Vec<LogMined>, StratusError> {
         #[cfg(feature = "tracing")]
-        let _span = tracing::info_span!("storage::read_logs", ?filter).entered();
-        tracing::debug!(storage = %label::PERM, ?filter, "reading logs");
+        let _span = tracing::info_span!("storage::read_logs", ?filter, %limit).entered();
+        tracing::debug!(storage = %label::PERM, ?filter, %limit, "reading logs");
 
-        timed(|| self.perm.read_logs(filter))
+        timed(|| self.perm.read_logs(filter, limit))
             .with(|m| {
                 metrics::inc_storage_read_logs(m.elapsed, label::PERM, m.result.is_ok());
                 if let Err(ref e) = m.result {
@@ -437,16 +744,75 @@ impl StratusStorage {
             .map_err(Into::into)
     }
 
+    pub fn read_transactions_by_address(&self, address: &Address, search: BlockSearch, limit: usize) -> Result<Vec<TransactionMined>, StratusError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("storage::read_transactions_by_address", %address, ?search, %limit).entered();
+        tracing::debug!(storage = %label::PERM, %address, ?search, %limit, "reading transactions by address");
+
+        timed(|| self.perm.read_transactions_by_address(address, search, limit))
+            .with(|m| {
+                metrics::inc_storage_read_transactions_by_address(m.elapsed, label::PERM, m.result.is_ok());
+                if let Err(ref e) = m.result {
+                    tracing::error!(reason = ?e, "failed to read transactions by address");
+                }
+            })
+            .map_err(Into::into)
+    }
+
+    pub fn read_transaction_by_sender_and_nonce(&self, sender: &Address, nonce: Nonce) -> Result<Option<TransactionMined>, StratusError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("storage::read_transaction_by_sender_and_nonce", %sender, %nonce).entered();
+        tracing::debug!(storage = %label::PERM, %sender, %nonce, "reading transaction by sender and nonce");
+
+        timed(|| self.perm.read_transaction_by_sender_and_nonce(sender, nonce))
+            .with(|m| {
+                metrics::inc_storage_read_transaction_by_sender_and_nonce(m.elapsed, label::PERM, m.result.is_ok());
+                if let Err(ref e) = m.result {
+                    tracing::error!(reason = ?e, "failed to read transaction by sender and nonce");
+                }
+            })
+            .map_err(Into::into)
+    }
+
+    pub fn read_contract_creation(&self, address: &Address) -> Result<Option<TransactionMined>, StratusError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("storage::read_contract_creation", %address).entered();
+        tracing::debug!(storage = %label::PERM, %address, "reading contract creation");
+
+        timed(|| self.perm.read_contract_creation(address))
+            .with(|m| {
+                metrics::inc_storage_read_contract_creation(m.elapsed, label::PERM, m.result.is_ok());
+                if let Err(ref e) = m.result {
+                    tracing::error!(reason = ?e, "failed to read contract creation");
+                }
+            })
+            .map_err(Into::into)
+    }
+
+    pub fn read_state_diff(&self, tx_hash: &Hash) -> Result<Option<StateDiff>, StratusError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("storage::read_state_diff", %tx_hash).entered();
+        tracing::debug!(storage = %label::PERM, %tx_hash, "reading state diff");
+
+        timed(|| self.perm.read_state_diff(tx_hash))
+            .with(|m| {
+                metrics::inc_storage_read_state_diff(m.elapsed, label::PERM, m.result.is_ok());
+                if let Err(ref e) = m.result {
+                    tracing::error!(reason = ?e, "failed to read state diff");
+                }
+            })
+            .map_err(Into::into)
+    }
+
     // -------------------------------------------------------------------------
     // General state
     // -------------------------------------------------------------------------
 
     #[cfg(feature = "dev")]
-    /// Resets the storage to the genesis state used in dev-mode.
-    ///
-    /// TODO: For now it uses the dev genesis block and test accounts, but it should be refactored to support genesis.json files.
+    /// Resets the storage to the genesis state of the genesis file, or to the dev genesis block and test accounts if there is no genesis file.
     pub fn reset_to_genesis(&self) -> Result<(), StratusError> {
         use crate::eth::primitives::test_accounts;
+        use crate::ext::MutexExt;
 
         tracing::info!("reseting storage to genesis state");
 
@@ -463,27 +829,115 @@ impl StratusStorage {
         })?;
 
         // reset temp
-        tracing::debug!(storage = %label::TEMP, "reseting temporary storage");
-        timed(|| self.temp.reset()).with(|m| {
-            metrics::inc_storage_reset(m.elapsed, label::TEMP, m.result.is_ok());
-            if let Err(ref e) = m.result {
-                tracing::error!(reason = ?e, "failed to reset temporary storage");
-            }
-        })?;
+        self.reset_temp()?;
 
-        // genesis block
-        self.save_block(Block::genesis())?;
+        // snapshots
+        self.snapshots.lock_or_clear("storage snapshots lock was poisoned").clear();
 
-        // test accounts
-        self.save_accounts(test_accounts())?;
+        // genesis block and accounts
+        match self.genesis {
+            Some(ref genesis) => self.save_genesis(genesis.to_block()?, genesis.account_changes())?,
+            None => {
+                self.save_block(Block::genesis())?;
+                self.save_accounts(test_accounts())?;
+                self.set_mined_block_number(BlockNumber::ZERO)?;
+            }
+        }
 
         // block number
-        self.set_mined_block_number(BlockNumber::ZERO)?;
         self.set_pending_block_number_as_next()?;
 
         Ok(())
     }
 
+    #[cfg(feature = "dev")]
+    /// Resets the storage to the state after the specified block was mined, discarding all blocks after it and the pending block.
+    pub fn reset_at(&self, block_number: BlockNumber) -> Result<(), StratusError> {
+        tracing::info!(%block_number, "reseting storage to block");
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("storage::reset_at", %block_number).entered();
+
+        // reset perm
+        tracing::debug!(storage = %label::PERM, %block_number, "reseting permanent storage to block");
+        timed(|| self.perm.reset_at(block_number)).with(|m| {
+            metrics::inc_storage_reset(m.elapsed, label::PERM, m.result.is_ok());
+            if let Err(ref e) = m.result {
+                tracing::error!(reason = ?e, "failed to reset permanent storage to block");
+            }
+        })?;
+
+        // reset temp
+        self.reset_temp()
+    }
+
+    #[cfg(feature = "dev")]
+    /// Records the last mined block number and the time offset so they can be restored later, returning the snapshot id.
+    ///
+    /// Fails if the permanent storage cannot be reset to the recorded block.
+    pub fn snapshot(&self) -> Result<u64, StratusError> {
+        use crate::eth::primitives::UnixTime;
+        use crate::ext::MutexExt;
+
+        if not(self.perm.supports_reset_at()) {
+            return Err(StratusError::StorageSnapshotUnsupported);
+        }
+
+        let mut snapshots = self.snapshots.lock_or_clear("storage snapshots lock was poisoned");
+        snapshots.push(Snapshot {
+            block_number: self.read_mined_block_number()?,
+            time_offset: UnixTime::read_offset(),
+            undo_changes: Vec::new(),
+        });
+        Ok(snapshots.len() as u64)
+    }
+
+    #[cfg(feature = "dev")]
+    /// Resets the storage and the time offset to the state recorded by a snapshot, discarding the snapshot and all snapshots taken after it.
+    ///
+    /// Returns `false` if the snapshot does not exist.
+    pub fn revert_to_snapshot(&self, id: u64) -> Result<bool, StratusError> {
+        use crate::eth::primitives::UnixTime;
+        use crate::ext::MutexExt;
+
+        let mut snapshots = self.snapshots.lock_or_clear("storage snapshots lock was poisoned");
+        let Some(index) = (id as usize).checked_sub(1).filter(|index| *index < snapshots.len()) else {
+            return Ok(false);
+        };
+        let (block_number, time_offset) = (snapshots[index].block_number, snapshots[index].time_offset);
+
+        // discard blocks mined after the snapshot
+        self.reset_at(block_number)?;
+
+        // undo changes that overwrote the snapshot block, from the latest to the earliest
+        let reverted = snapshots.split_off(index);
+        let undo_changes = reverted
+            .into_iter()
+            .rev()
+            .filter(|snapshot| snapshot.block_number == block_number)
+            .flat_map(|snapshot| snapshot.undo_changes.into_iter().rev());
+        for change in undo_changes {
+            self.save_account_changes_to_perm(vec![change])?;
+        }
+        self.reset_temp()?;
+
+        UnixTime::restore_offset(time_offset);
+        Ok(true)
+    }
+
+    #[cfg(feature = "dev")]
+    /// Discards the temporary storage state and starts a new pending block after the last mined block.
+    fn reset_temp(&self) -> Result<(), StratusError> {
+        tracing::debug!(storage = %label::TEMP, "reseting temporary storage");
+        timed(|| self.temp.reset()).with(|m| {
+            metrics::inc_storage_reset(m.elapsed, label::TEMP, m.result.is_ok());
+            if let Err(ref e) = m.result {
+                tracing::error!(reason = ?e, "failed to reset temporary storage");
+            }
+        })?;
+        self.set_pending_block_number_as_next()
+    }
+
     // -------------------------------------------------------------------------
     // Utils
     // -------------------------------------------------------------------------
@@ -492,7 +946,7 @@ impl StratusStorage {
     pub fn translate_to_point_in_time(&self, block_filter: &BlockFilter) -> Result<StoragePointInTime, StratusError> {
         match block_filter {
             BlockFilter::Pending => Ok(StoragePointInTime::Pending),
-            BlockFilter::Latest => Ok(StoragePointInTime::Mined),
+            BlockFilter::Latest | BlockFilter::Safe | BlockFilter::Finalized => Ok(StoragePointInTime::Mined),
             BlockFilter::Earliest => Ok(StoragePointInTime::MinedPast(BlockNumber::ZERO)),
             BlockFilter::Number(number) => Ok(StoragePointInTime::MinedPast(*number)),
             BlockFilter::Hash(_) => match self.read_block(block_filter)? {
@@ -503,6 +957,47 @@ impl StratusStorage {
     }
 }
 
+// -----------------------------------------------------------------------------
+// Builder
+// -----------------------------------------------------------------------------
+
+/// Builder of a [`StratusStorage`] created with [`StratusStorage::builder`].
+pub struct StratusStorageBuilder {
+    temp: Box<dyn TemporaryStorage>,
+    perm: Box<dyn PermanentStorage>,
+    fork: Option<Box<dyn ForkStorage>>,
+    genesis: Option<Genesis>,
+}
+
+impl StratusStorageBuilder {
+    /// Reads accounts and slots missing in the permanent storage from the fork.
+    ///
+    /// The state trie only contains the changes made locally, so state roots do not match the forked chain and proofs are not supported.
+    pub fn fork(mut self, fork: Box<dyn ForkStorage>) -> Self {
+        self.fork = Some(fork);
+        self
+    }
+
+    /// Initializes an empty permanent storage with the genesis file, and requires a non-empty permanent storage to have the same genesis block.
+    pub fn genesis(mut self, genesis: Genesis) -> Self {
+        self.genesis = Some(genesis);
+        self
+    }
+
+    /// Builds the storage, initializing the permanent storage if necessary.
+    pub fn build(self) -> Result<StratusStorage, StratusError> {
+        let storage = StratusStorage {
+            temp: self.temp,
+            perm: self.perm,
+            fork: self.fork,
+            genesis: self.genesis,
+            #[cfg(feature = "dev")]
+            snapshots: std::sync::Mutex::new(Vec::new()),
+        };
+        storage.init()
+    }
+}
+
 // -----------------------------------------------------------------------------
 // Config
 // -----------------------------------------------------------------------------
@@ -515,15 +1010,182 @@ pub struct StratusStorageConfig {
 
     #[clap(flatten)]
     pub perm_storage: PermanentStorageConfig,
+
+    #[clap(flatten)]
+    pub fork_storage: ForkStorageConfig,
+
+    /// Genesis file in the geth format used to initialize an empty permanent storage.
+    #[arg(long = "genesis-path", env = "GENESIS_PATH")]
+    pub genesis_path: Option<String>,
 }
 
 impl StratusStorageConfig {
     /// Initializes Stratus storage.
-    pub fn init(&self) -> Result<Arc<StratusStorage>, StratusError> {
+    pub async fn init(&self) -> Result<Arc<StratusStorage>, StratusError> {
         let temp_storage = self.temp_storage.init()?;
         let perm_storage = self.perm_storage.init()?;
-        let storage = StratusStorage::new(temp_storage, perm_storage)?;
+        let fork_storage = self.fork_storage.init().await?;
+        let genesis = self.genesis_path.as_deref().map(Genesis::from_file).transpose()?;
+
+        let mut builder = StratusStorage::builder(temp_storage, perm_storage);
+        if let Some(fork_storage) = fork_storage {
+            builder = builder.fork(fork_storage);
+        }
+        if let Some(genesis) = genesis {
+            builder = builder.genesis(genesis);
+        }
+        let storage = builder.build()?;
 
         Ok(Arc::new(storage))
     }
 }
+
+// -----------------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+    use crate::eth::primitives::SlotValue;
+    use crate::eth::primitives::Wei;
+    use crate::eth::storage::InMemoryPermanentStorage;
+    use crate::eth::storage::InMemoryTemporaryStorage;
+    use crate::if_else;
+    use crate::utils::test_utils::fake_first;
+
+    /// Fork storage with a single account and slot that counts how many times it was read.
+    struct TestForkStorage {
+        account: Account,
+        slot: Slot,
+        reads: Arc<AtomicUsize>,
+    }
+
+    impl ForkStorage for TestForkStorage {
+        fn read_account(&self, address: &Address) -> anyhow::Result<Account> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            Ok(if_else!(*address == self.account.address, self.account.clone(), Account::new_empty(*address)))
+        }
+
+        fn read_slot(&self, address: &Address, index: &SlotIndex) -> anyhow::Result<Slot> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            Ok(if_else!(
+                *address == self.account.address && *index == self.slot.index,
+                self.slot,
+                Slot::new_empty(*index)
+            ))
+        }
+    }
+
+    fn forked_storage() -> (StratusStorage, Account, Slot, Arc<AtomicUsize>) {
+        let bytecode = Some(Bytes(vec![0x00]));
+        let account = Account {
+            address: fake_first::<Address>(),
+            nonce: Nonce::from(1u64),
+            balance: Wei::ONE,
+            code_hash: CodeHash::from_bytecode(bytecode.clone()),
+            bytecode,
+        };
+        let slot = Slot::new(SlotIndex::ONE, SlotValue::from(2u64));
+        let reads = Arc::new(AtomicUsize::new(0));
+
+        let fork = TestForkStorage {
+            account: account.clone(),
+            slot,
+            reads: Arc::clone(&reads),
+        };
+        let storage = StratusStorage::builder(Box::<InMemoryTemporaryStorage>::default(), Box::<InMemoryPermanentStorage>::default())
+            .fork(Box::new(fork))
+            .build()
+            .unwrap();
+
+        (storage, account, slot, reads)
+    }
+
+    #[test]
+    fn fork_values_are_cached_when_reading_current_state() {
+        let (storage, account, slot, reads) = forked_storage();
+        let root = storage.compute_state_root(&[]).unwrap();
+
+        // first reads miss the permanent storage and fetch from the fork
+        assert_eq!(storage.read_account(&account.address, &StoragePointInTime::Mined).unwrap(), account);
+        assert_eq!(storage.read_slot(&account.address, &slot.index, &StoragePointInTime::Mined).unwrap(), slot);
+        assert_eq!(reads.load(Ordering::SeqCst), 2);
+
+        // next reads are served by the permanent storage
+        assert_eq!(storage.read_account(&account.address, &StoragePointInTime::Mined).unwrap(), account);
+        assert_eq!(storage.read_slot(&account.address, &slot.index, &StoragePointInTime::Pending).unwrap(), slot);
+        assert_eq!(reads.load(Ordering::SeqCst), 2);
+        assert_eq!(storage.perm.read_account(&account.address, &StoragePointInTime::Mined).unwrap(), Some(account));
+
+        // cached values do not change the state trie
+        assert_eq!(storage.compute_state_root(&[]).unwrap(), root);
+    }
+
+    #[test]
+    fn fork_refuses_proofs() {
+        let (storage, account, slot, _) = forked_storage();
+        let proof = storage.read_proof(&account.address, &[slot.index], &StoragePointInTime::Mined);
+        assert!(matches!(proof, Err(StratusError::StorageProofUnsupported)));
+    }
+
+    #[test]
+    fn fork_values_do_not_overwrite_stored_values() {
+        let (storage, account, _, _) = forked_storage();
+        let stored = Account {
+            balance: Wei::from(2u64),
+            ..account.clone()
+        };
+
+        storage.perm.save_fork_account(stored.clone()).unwrap();
+        storage.perm.save_fork_account(account.clone()).unwrap();
+        assert_eq!(storage.read_account(&account.address, &StoragePointInTime::Mined).unwrap(), stored);
+    }
+
+    #[test]
+    fn fork_values_are_not_cached_when_reading_past_state() {
+        let (storage, account, slot, reads) = forked_storage();
+        let past = StoragePointInTime::MinedPast(BlockNumber::ZERO);
+
+        for _ in 0..2 {
+            assert_eq!(storage.read_account(&account.address, &past).unwrap(), account);
+            assert_eq!(storage.read_slot(&account.address, &slot.index, &past).unwrap(), slot);
+        }
+        assert_eq!(reads.load(Ordering::SeqCst), 4);
+        assert_eq!(storage.perm.read_account(&account.address, &StoragePointInTime::Mined).unwrap(), None);
+    }
+
+    #[test]
+    fn genesis_must_match_stored_genesis_block() {
+        let genesis = Genesis {
+            timestamp: crate::eth::primitives::UnixTime::from(16u64),
+            ..Genesis::default()
+        };
+        let stored = Genesis::default().to_block().unwrap();
+
+        // a storage initialized with the same genesis file is accepted
+        let perm = InMemoryPermanentStorage::default();
+        perm.save_genesis(genesis.to_block().unwrap(), genesis.account_changes()).unwrap();
+        let builder = StratusStorage::builder(Box::<InMemoryTemporaryStorage>::default(), Box::new(perm));
+        assert!(builder.genesis(genesis.clone()).build().is_ok());
+
+        // a storage initialized with another genesis block is refused
+        let perm = InMemoryPermanentStorage::default();
+        perm.save_genesis(stored.clone(), vec![]).unwrap();
+        let builder = StratusStorage::builder(Box::<InMemoryTemporaryStorage>::default(), Box::new(perm));
+        match builder.genesis(genesis.clone()).build() {
+            Err(StratusError::StorageGenesisMismatch {
+                stored: stored_hash,
+                genesis: genesis_hash,
+            }) => {
+                assert_eq!(stored_hash, stored.hash());
+                assert_eq!(genesis_hash, genesis.to_block().unwrap().hash());
+            }
+            Err(e) => panic!("unexpected error: {e:?}"),
+            Ok(_) => panic!("storage with another genesis block was not refused"),
+        }
+    }
+}
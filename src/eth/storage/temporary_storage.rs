@@ -35,6 +35,11 @@ pub trait TemporaryStorage: Send + Sync + 'static {
     /// Finishes the mining of the pending block and starts a new block.
     fn finish_pending_block(&self) -> anyhow::Result<PendingBlock>;
 
+    /// Restores the last finished block as the pending block, discarding the block started when it was finished.
+    ///
+    /// Fails if transactions were already saved to the block started when it was finished.
+    fn restore_finished_block(&self) -> anyhow::Result<()>;
+
     /// Saves a transaction execution to the pending mined block.
     fn save_pending_execution(&self, tx: TransactionExecution, check_conflicts: bool) -> Result<(), StratusError>;
 
@@ -44,6 +49,11 @@ pub trait TemporaryStorage: Send + Sync + 'static {
     /// Retrieves a single transaction execution from the pending block.
     fn read_pending_execution(&self, hash: &Hash) -> anyhow::Result<Option<TransactionExecution>>;
 
+    /// Removes a transaction execution from the pending block, reverting its changes.
+    ///
+    /// Fails if an execution saved after it accessed an account it modified. Returns `None` if the execution is not in the pending block.
+    fn remove_pending_execution(&self, hash: &Hash) -> Result<Option<TransactionExecution>, StratusError>;
+
     // -------------------------------------------------------------------------
     // Accounts and slots
     // -------------------------------------------------------------------------
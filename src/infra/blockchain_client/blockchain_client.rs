@@ -17,9 +17,13 @@ use crate::alias::EthersTransaction;
 use crate::alias::JsonValue;
 use crate::eth::primitives::Address;
 use crate::eth::primitives::BlockNumber;
+use crate::eth::primitives::Bytes;
 use crate::eth::primitives::ExternalBlock;
 use crate::eth::primitives::ExternalReceipt;
 use crate::eth::primitives::Hash;
+use crate::eth::primitives::Nonce;
+use crate::eth::primitives::SlotIndex;
+use crate::eth::primitives::SlotValue;
 use crate::eth::primitives::StratusError;
 use crate::eth::primitives::Wei;
 use crate::eth::rpc::RpcClientApp;
@@ -198,6 +202,49 @@ impl BlockchainClient {
         }
     }
 
+    /// Fetches account nonce by address and block number.
+    pub async fn fetch_transaction_count(&self, address: &Address, block_number: Option<BlockNumber>) -> anyhow::Result<Nonce> {
+        tracing::debug!(%address, block_number = %block_number.or_empty(), "fetching account nonce");
+
+        let address = to_json_value(address);
+        let number = to_json_value(block_number);
+        let result = self.http.request::<Nonce, _>("eth_getTransactionCount", [address, number]).await;
+
+        match result {
+            Ok(nonce) => Ok(nonce),
+            Err(e) => log_and_err!(reason = e, "failed to fetch account nonce"),
+        }
+    }
+
+    /// Fetches account bytecode by address and block number.
+    pub async fn fetch_code(&self, address: &Address, block_number: Option<BlockNumber>) -> anyhow::Result<Bytes> {
+        tracing::debug!(%address, block_number = %block_number.or_empty(), "fetching account bytecode");
+
+        let address = to_json_value(address);
+        let number = to_json_value(block_number);
+        let result = self.http.request::<Bytes, _>("eth_getCode", [address, number]).await;
+
+        match result {
+            Ok(bytecode) => Ok(bytecode),
+            Err(e) => log_and_err!(reason = e, "failed to fetch account bytecode"),
+        }
+    }
+
+    /// Fetches slot value by address, slot index and block number.
+    pub async fn fetch_storage_at(&self, address: &Address, index: &SlotIndex, block_number: Option<BlockNumber>) -> anyhow::Result<SlotValue> {
+        tracing::debug!(%address, %index, block_number = %block_number.or_empty(), "fetching slot value");
+
+        let address = to_json_value(address);
+        let index = to_json_value(index);
+        let number = to_json_value(block_number);
+        let result = self.http.request::<SlotValue, _>("eth_getStorageAt", [address, index, number]).await;
+
+        match result {
+            Ok(value) => Ok(value),
+            Err(e) => log_and_err!(reason = e, "failed to fetch slot value"),
+        }
+    }
+
     // -------------------------------------------------------------------------
     // RPC mutations
     // -------------------------------------------------------------------------
@@ -33,14 +33,38 @@ metrics! {
     "Time executing storage read_block operation."
     histogram_duration storage_read_block{storage, success},
 
+    "Time executing storage read_block_hash operation."
+    histogram_duration storage_read_block_hash{storage, success},
+
+    "Time executing storage read_bytecode operation."
+    histogram_duration storage_read_bytecode{storage, success},
+
     "Time executing storage read_logs operation."
     histogram_duration storage_read_logs{storage, success},
 
+    "Time executing storage read_proof operation."
+    histogram_duration storage_read_proof{storage, point_in_time, success},
+
     "Time executing storage read_slot operation."
     histogram_duration storage_read_slot{storage, point_in_time, success},
 
     "Time executing storage read_transaction operation."
-    histogram_duration storage_read_transaction{storage, success}
+    histogram_duration storage_read_transaction{storage, success},
+
+    "Time executing storage read_transactions_by_address operation."
+    histogram_duration storage_read_transactions_by_address{storage, success},
+
+    "Time executing storage read_transaction_by_sender_and_nonce operation."
+    histogram_duration storage_read_transaction_by_sender_and_nonce{storage, success},
+
+    "Time executing storage read_contract_creation operation."
+    histogram_duration storage_read_contract_creation{storage, success},
+
+    "Time executing storage read_state_diff operation."
+    histogram_duration storage_read_state_diff{storage, success},
+
+    "Time executing storage compute_state_root operation."
+    histogram_duration storage_compute_state_root{storage, success}
 }
 
 // Storage writes.
@@ -57,14 +81,29 @@ metrics! {
     histogram_duration storage_save_accounts{storage, success},
 
     "Time executing storage save_account_changes operation."
+    histogram_duration storage_save_account_changes{storage, success},
+
+    "Time executing storage save_fork_values operation."
+    histogram_duration storage_save_fork_values{storage, success},
+
+    "Time executing storage save_genesis operation."
+    histogram_duration storage_save_genesis{storage, success},
+
+    "Time executing storage save_execution operation."
     histogram_duration storage_save_execution{storage, success},
 
+    "Time executing storage remove_execution operation."
+    histogram_duration storage_remove_execution{storage, success},
+
     "Time executing storage set_pending_external_block operation."
     histogram_duration storage_set_pending_external_block{storage, success},
 
     "Time executing storage finish_pending_block operation."
     histogram_duration storage_finish_pending_block{storage, success},
 
+    "Time executing storage restore_finished_block operation."
+    histogram_duration storage_restore_finished_block{storage, success},
+
     "Time executing storage save_block operation."
     histogram_duration storage_save_block{storage, size_by_tx, size_by_gas, success},
 